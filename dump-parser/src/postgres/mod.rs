@@ -3,8 +3,9 @@ use std::iter::Peekable;
 use std::str::Chars;
 
 use crate::postgres::Keyword::{
-    Add, Alter, Constraint, Copy, Create, Database, Foreign, From, Function, Insert,
-    Into as KeywordInto, Key, NoKeyword, Not, Null, Only, Primary, References, Replace, Table,
+    Add, Alter, Attach, Column, Comment, Constraint, Copy, Create, Database, Foreign, From,
+    Function, Insert, Into as KeywordInto, Is, Key, NoKeyword, Not, Null, On, Only, Partition,
+    Primary, References, Replace, Table,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -144,6 +145,12 @@ impl Token {
                     "REFERENCES" => References,
                     "KEY" => Key,
                     "FUNCTION" => Function,
+                    "COMMENT" => Comment,
+                    "ON" => On,
+                    "COLUMN" => Column,
+                    "IS" => Is,
+                    "ATTACH" => Attach,
+                    "PARTITION" => Partition,
                     _ => NoKeyword,
                 }
             } else {
@@ -190,6 +197,12 @@ pub enum Keyword {
     References,
     Key,
     Function,
+    Comment,
+    On,
+    Column,
+    Is,
+    Attach,
+    Partition,
     NoKeyword,
 }
 
@@ -0,0 +1,60 @@
+// Example of a Replibyte plugin providing a `reverse-string` transformer.
+// Build it as a shared library with: rustc --edition 2021 --crate-type cdylib reverse_string.rs
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_void};
+use std::ptr;
+
+/// the transformers of the plugin
+enum ReverseString {
+    Reverse,
+}
+
+#[no_mangle]
+pub extern "C" fn replibyte_plugin_abi_version() -> u32 {
+    1
+}
+
+#[no_mangle]
+pub extern "C" fn replibyte_transformer_new(
+    id: *const c_char,
+    _options: *const c_char,
+) -> *mut c_void {
+    let id = unsafe { CStr::from_ptr(id) };
+
+    match id.to_str() {
+        Ok("reverse-string") => Box::into_raw(Box::new(ReverseString::Reverse)) as *mut c_void,
+        // not provided by this plugin
+        _ => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn replibyte_transformer_transform(
+    transformer: *mut c_void,
+    value: *const c_char,
+) -> *mut c_char {
+    let transformer = unsafe { &*(transformer as *const ReverseString) };
+    let value = match unsafe { CStr::from_ptr(value) }.to_str() {
+        Ok(value) => value,
+        Err(_) => return ptr::null_mut(),
+    };
+
+    let new_value: String = match transformer {
+        ReverseString::Reverse => value.chars().rev().collect(),
+    };
+
+    match CString::new(new_value) {
+        Ok(new_value) => new_value.into_raw(),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn replibyte_transformer_free(transformer: *mut c_void) {
+    drop(unsafe { Box::from_raw(transformer as *mut ReverseString) });
+}
+
+#[no_mangle]
+pub extern "C" fn replibyte_string_free(value: *mut c_char) {
+    drop(unsafe { CString::from_raw(value) });
+}
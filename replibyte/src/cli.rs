@@ -7,9 +7,12 @@ use clap::{Args, Parser, Subcommand};
 #[clap(version, about, long_about = None)]
 #[clap(propagate_version = true)]
 pub struct CLI {
-    /// Replibyte configuration file
+    /// Replibyte configuration file - `-` to read it from stdin, or an http(s) URL to fetch it
     #[clap(short, long, parse(from_os_str), value_name = "configuration file")]
     pub config: PathBuf,
+    /// file with `KEY=VALUE` lines loaded as environment variables - the variables already set take precedence
+    #[clap(long, parse(from_os_str), value_name = "env file")]
+    pub env_file: Option<PathBuf>,
     #[clap(subcommand)]
     pub sub_commands: SubCommand,
     /// disable telemetry
@@ -26,6 +29,9 @@ pub enum SubCommand {
     /// all transformers command
     #[clap(subcommand)]
     Transformer(TransformerCommand),
+    /// all configuration commands
+    #[clap(subcommand)]
+    Config(ConfigCommand),
 }
 
 /// all dump commands
@@ -40,6 +46,14 @@ pub enum DumpCommand {
     Restore(RestoreCommand),
     /// delete a dump from the defined datastore
     Delete(DumpDeleteArgs),
+    /// export the index file of the defined datastore into a local JSON file
+    ExportIndex(DumpIndexFileArgs),
+    /// import dumps from a local JSON index file into the defined datastore
+    ImportIndex(DumpIndexFileArgs),
+    /// check a dump has not been corrupted in the defined datastore
+    Verify(DumpVerifyArgs),
+    /// rewrite a dump of the defined datastore with compression, e.g. a dump created without it
+    Recompress(DumpRecompressArgs),
 }
 
 /// all transformer commands
@@ -49,6 +63,13 @@ pub enum TransformerCommand {
     List,
 }
 
+/// all configuration commands
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    /// show the effective configuration (environment variables substituted and secrets redacted)
+    Check,
+}
+
 /// all restore commands
 #[derive(Subcommand, Debug)]
 pub enum RestoreCommand {
@@ -67,6 +88,27 @@ pub struct RestoreArgs {
     /// stream output on stdout
     #[clap(short, long)]
     pub output: bool,
+    /// write the `COPY ... FROM stdin` blocks as batched INSERT statements - requires --output
+    #[clap(long, requires = "output")]
+    pub inserts: bool,
+    /// translate a dump from another database type - only `postgresql` into MySQL is supported
+    #[clap(long, value_name = "postgresql", possible_values = &["postgresql"])]
+    pub translate_from: Option<String>,
+    /// do not ask for confirmation before wiping the destination database
+    #[clap(short, long)]
+    pub yes: bool,
+    /// number of concurrent connections writing the data of the tables - PostgreSQL only
+    #[clap(long, value_name = "number of connections")]
+    pub workers: Option<usize>,
+    /// datastore to read the dump from when several are configured - the first one by default
+    #[clap(long, value_name = "[aws | gcp | local_disk]", possible_values = &["aws", "gcp", "local_disk"])]
+    pub datastore: Option<String>,
+    /// restore only the schema of the dump (e.g. tables, indexes, constraints) - not supported for MongoDB
+    #[clap(long, conflicts_with = "data_only")]
+    pub schema_only: bool,
+    /// restore only the data of the dump, into an existing schema - not supported for MongoDB
+    #[clap(long)]
+    pub data_only: bool,
 }
 
 /// restore dump in a local Docker container
@@ -78,6 +120,9 @@ pub struct RestoreLocalArgs {
     /// stream output on stdout
     #[clap(short, long)]
     pub output: bool,
+    /// write the `COPY ... FROM stdin` blocks as batched INSERT statements - requires --output
+    #[clap(long, requires = "output")]
+    pub inserts: bool,
     /// Docker image tag for the container to spawn
     #[clap(short, long)]
     pub tag: Option<String>,
@@ -90,6 +135,18 @@ pub struct RestoreLocalArgs {
     /// Docker image type
     #[clap(short, long, value_name = "[postgresql | mysql | mongodb]")]
     pub image: Option<String>,
+    /// translate a dump from another database type - only `postgresql` into MySQL is supported
+    #[clap(long, value_name = "postgresql", possible_values = &["postgresql"])]
+    pub translate_from: Option<String>,
+    /// datastore to read the dump from when several are configured - the first one by default
+    #[clap(long, value_name = "[aws | gcp | local_disk]", possible_values = &["aws", "gcp", "local_disk"])]
+    pub datastore: Option<String>,
+    /// restore only the schema of the dump (e.g. tables, indexes, constraints) - not supported for MongoDB
+    #[clap(long, conflicts_with = "data_only")]
+    pub schema_only: bool,
+    /// restore only the data of the dump, into an existing schema - not supported for MongoDB
+    #[clap(long)]
+    pub data_only: bool,
 }
 
 /// all dump run commands
@@ -107,6 +164,46 @@ pub struct DumpCreateArgs {
     /// dump name
     #[clap(short, long)]
     pub name: Option<String>,
+    /// replace the dump with the same name, once the new dump is created
+    #[clap(long, requires = "name")]
+    pub force: bool,
+    /// fail if a configured transformer never matched a column
+    #[clap(long)]
+    pub strict_transformers: bool,
+    /// emit the progress of the dump as events (table started, rows processed, table finished, error)
+    #[clap(long, value_name = "format", possible_values = &["ndjson"])]
+    pub events: Option<String>,
+    /// file to write the events to (default to stdout)
+    #[clap(
+        long,
+        parse(from_os_str),
+        value_name = "events file",
+        requires = "events"
+    )]
+    pub events_file: Option<PathBuf>,
+}
+
+/// export or import the index file
+#[derive(Args, Debug)]
+pub struct DumpIndexFileArgs {
+    #[clap(short, long, parse(from_os_str), value_name = "index file")]
+    /// local JSON index file
+    pub file: PathBuf,
+}
+
+#[derive(Args, Debug)]
+pub struct DumpVerifyArgs {
+    /// Name of the dump to verify
+    pub dump: String,
+}
+
+#[derive(Args, Debug)]
+pub struct DumpRecompressArgs {
+    /// Name of the dump to recompress
+    pub dump: String,
+    /// Store the dump uncompressed instead
+    #[clap(long)]
+    pub no_compression: bool,
 }
 
 #[derive(Args, Debug)]
@@ -121,4 +218,7 @@ pub struct DumpDeleteArgs {
     /// Keep only the last N dumps
     #[clap(long, group = "delete-mode")]
     pub keep_last: Option<usize>,
+    /// Do not ask for confirmation before deleting
+    #[clap(short, long)]
+    pub yes: bool,
 }
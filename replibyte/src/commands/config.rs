@@ -0,0 +1,10 @@
+use crate::config::Config;
+
+/// check the policy and display the configuration as it is used by the other commands
+pub fn check(config: &Config) -> anyhow::Result<()> {
+    config.check_policy()?;
+
+    let effective_config = config.effective()?;
+    print!("{}", serde_yaml::to_string(&effective_config)?);
+    Ok(())
+}
@@ -1,15 +1,21 @@
 use std::fs::File;
-use std::io::{stdin, BufReader, Error, ErrorKind, Read};
+use std::io::{stdin, BufRead, BufReader, Error, ErrorKind, Read, Write};
+use std::path::Path;
 use std::sync::mpsc;
 use std::time::Duration;
 
+use chrono::{TimeZone, Utc};
 use timeago::Formatter;
 
-use crate::cli::{DumpCreateArgs, DumpDeleteArgs};
+use crate::cli::{
+    DumpCreateArgs, DumpDeleteArgs, DumpIndexFileArgs, DumpRecompressArgs, DumpVerifyArgs,
+};
 use crate::cli::{RestoreArgs, RestoreLocalArgs};
-use crate::config::{Config, ConnectionUri};
-use crate::datastore::Datastore;
+use crate::config::{
+    Config, ConnectionUri, DatabaseSubsetConfigStrategy, SourceConfig, TransformerTypeConfig,
+};
 use crate::datastore::ReadOptions;
+use crate::datastore::{Datastore, IndexFile};
 use crate::destination::generic_stdout::GenericStdout;
 use crate::destination::mongodb_docker::{MongoDBDocker, DEFAULT_MONGO_CONTAINER_PORT};
 use crate::destination::mysql_docker::{
@@ -26,9 +32,18 @@ use crate::source::mysql_stdin::MysqlStdin;
 use crate::source::postgres::Postgres;
 use crate::source::postgres_stdin::PostgresStdin;
 use crate::source::SourceOptions;
+use crate::ssh_tunnel::SshTunnel;
+use crate::tasks::events::DumpEvents;
 use crate::tasks::full_dump::FullDumpTask;
-use crate::tasks::full_restore::FullRestoreTask;
+use crate::tasks::full_restore::{FullRestoreTask, RestoreFilter};
 use crate::tasks::Task;
+use crate::transformer::foreign_key::link_foreign_keys;
+use crate::transformer::seeded::seed_transformers;
+use crate::transformer::tracked::{track, TransformersUsage};
+use crate::transformer::unique::UniqueTransformer;
+use crate::transformer::Transformer;
+use crate::translation::copy_to_insert::CopyToInsert;
+use crate::translation::postgres_to_mysql::PostgresToMysql;
 use crate::utils::{epoch_millis, table, to_human_readable_unit};
 use crate::{destination, CLI};
 use clap::CommandFactory;
@@ -64,6 +79,57 @@ pub fn list(datastore: &mut Box<dyn Datastore>) -> Result<(), Error> {
     Ok(())
 }
 
+/// Name the dump to create - the name is its directory (or key prefix) in the datastore, so it must be unique and safe.
+/// With `force`, the dump with the same name is replaced once the new dump is committed.
+fn set_dump_name(datastore: &mut Box<dyn Datastore>, name: &str, force: bool) -> Result<(), Error> {
+    if name.is_empty()
+        || name.starts_with('.')
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        return Err(Error::new(
+            ErrorKind::Other,
+            format!(
+                "dump name '{}' must only contain letters, digits, '-', '_' and '.'",
+                name
+            ),
+        ));
+    }
+
+    // `latest` is the keyword used by `restore` to pick the most recent dump
+    if name == "latest" {
+        return Err(Error::new(
+            ErrorKind::Other,
+            "dump name 'latest' is reserved",
+        ));
+    }
+
+    let index_file = datastore.index_file()?;
+    if index_file
+        .dumps
+        .iter()
+        .any(|dump| dump.directory_name == name)
+    {
+        if !force {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!(
+                    "a dump named '{}' already exists - use --force to replace it",
+                    name
+                ),
+            ));
+        }
+
+        datastore.set_replaced_dump(name.to_string());
+        return Ok(());
+    }
+
+    datastore.set_dump_name(name.to_string());
+
+    Ok(())
+}
+
 // Create a new dump
 pub fn run<F>(
     args: &DumpCreateArgs,
@@ -78,28 +144,115 @@ where
         datastore.set_encryption_key(encryption_key)
     }
 
+    if let Some(name) = &args.name {
+        set_dump_name(&mut datastore, name, args.force)?;
+    }
+
+    let tmp_dir = config.tmp_dir()?;
+    config.check_policy()?;
+
+    // the events are written to stdout unless a file is given - `ndjson` is the only format
+    let events = match (&args.events, &args.events_file) {
+        (Some(_), Some(events_file)) => DumpEvents::ndjson(File::create(events_file)?),
+        (Some(_), None) => DumpEvents::ndjson(std::io::stdout()),
+        (None, _) => DumpEvents::default(),
+    };
+    let events_to_stdout = args.events.is_some() && args.events_file.is_none();
+
     match config.source {
-        Some(source) => {
+        Some(mut source) => {
             // Configure datastore options (compression is enabled by default)
             datastore.set_compression(source.compression.unwrap_or(true));
 
+            // the source database is read when there is no source type
+            // the tunnel is closed when dropped, once the dump is done
+            let (connection_uri, _ssh_tunnel) = match args.source_type {
+                None => {
+                    let connection_uri = source.connection_uri()?;
+
+                    let ssh_tunnel = match &source.ssh_tunnel {
+                        Some(ssh_tunnel_config) => {
+                            Some(SshTunnel::open(ssh_tunnel_config, &connection_uri)?)
+                        }
+                        None => None,
+                    };
+
+                    let connection_uri = match &ssh_tunnel {
+                        Some(ssh_tunnel) => ssh_tunnel.connection_uri(connection_uri)?,
+                        None => connection_uri,
+                    };
+
+                    (Some(connection_uri), ssh_tunnel)
+                }
+                Some(_) => (None, None),
+            };
+
+            let ssl_mode = source.ssl_mode()?;
+
+            if let Some(ConnectionUri::Postgres(host, port, username, password, database)) =
+                &connection_uri
+            {
+                let mut postgres = Postgres::new(
+                    host.as_str(),
+                    *port,
+                    database.as_str(),
+                    username.as_str(),
+                    password.as_str(),
+                );
+                if let Some(ssl_mode) = &ssl_mode {
+                    postgres.set_ssl_mode(ssl_mode.as_str());
+                }
+
+                set_column_stats(&mut source, &postgres)?;
+            }
+
+            set_subset_watermark(&mut source, datastore.as_ref())?;
+
             // Match the transformers from the config
+            let _ = source.check_transformers()?;
             let transformers = match &source.transformers {
                 Some(transformers) => transformers
                     .iter()
                     .flat_map(|transformer| {
                         transformer.columns.iter().map(|column| {
-                            column.transformer.transformer(
+                            let column_transformer = column.transformer.transformer(
                                 transformer.database.as_str(),
                                 transformer.table.as_str(),
                                 column.name.as_str(),
-                            )
+                            );
+
+                            match column.unique {
+                                Some(true) => Box::new(UniqueTransformer::new(column_transformer))
+                                    as Box<dyn Transformer>,
+                                _ => column_transformer,
+                            }
                         })
                     })
                     .collect::<Vec<_>>(),
                 None => vec![],
             };
 
+            // Share the transformed values of the primary keys with the foreign keys referencing them
+            let transformers = match &source.foreign_keys {
+                Some(foreign_keys) => link_foreign_keys(transformers, foreign_keys)?,
+                None => transformers,
+            };
+
+            // Derive the generated values from the seed and the original values, so two dumps are identical
+            let transformers = match source.seed {
+                Some(seed) => seed_transformers(transformers, seed),
+                None => transformers,
+            };
+
+            // Record the transformers applied to fail on the unused ones
+            let (transformers, transformers_usage) = match args.strict_transformers {
+                true => {
+                    let (transformers, usage) = track(transformers);
+                    (transformers, Some(usage))
+                }
+                false => (transformers, None),
+            };
+
             let empty_config = vec![];
             let skip_config = match &source.skip {
                 Some(config) => config,
@@ -127,25 +280,96 @@ where
                 }
             }
 
+            let empty_config = vec![];
+            let schema_only_tables_config = match &source.schema_only_tables {
+                Some(config) => config,
+                None => &empty_config,
+            };
+
+            for schema_only_table in schema_only_tables_config {
+                for skip in skip_config {
+                    if schema_only_table.database == skip.database
+                        && schema_only_table.table == skip.table
+                    {
+                        return Err(anyhow::Error::from(Error::new(
+                            ErrorKind::Other,
+                            format!(
+                                "Table \"{}.{}\" cannot be both in \"schema_only_tables\" and in \"skip\" at the same time",
+                                schema_only_table.database,
+                                schema_only_table.table
+                            )
+                        )));
+                    }
+                }
+            }
+
+            if let Some(subset_config) = &source.database_subset {
+                if let DatabaseSubsetConfigStrategy::Limit(opt) = &subset_config.strategy {
+                    if opt.rows == 0 {
+                        return Err(anyhow::Error::from(Error::new(
+                            ErrorKind::Other,
+                            "the \"limit\" database subset strategy must keep at least 1 row",
+                        )));
+                    }
+                }
+            }
+
+            let empty_config = vec![];
+            let amplify_config = match &source.amplify {
+                Some(config) => config,
+                None => &empty_config,
+            };
+
+            for amplify in amplify_config {
+                if amplify.factor == 0 {
+                    return Err(anyhow::Error::from(Error::new(
+                        ErrorKind::Other,
+                        format!(
+                            "Table \"{}.{}\" must have an amplification factor of at least 1",
+                            amplify.database, amplify.table
+                        ),
+                    )));
+                }
+            }
+
             let options = SourceOptions {
                 transformers: &transformers,
                 skip_config: &skip_config,
                 database_subset: &source.database_subset,
                 only_tables: &only_tables_config,
+                schema_only_tables: schema_only_tables_config,
+                max_table_size: source.max_table_size,
+                tmp_dir: tmp_dir.as_deref().map(Path::new),
+                detect_pii_comments: source.detect_pii_comments.unwrap_or(false),
+                capture_sequences: source.capture_sequences.unwrap_or(false),
+                on_transform_error: source.on_transform_error,
+                amplify: amplify_config,
+                seed: source.seed,
             };
 
-            match args.source_type.as_ref().map(|x| x.as_str()) {
-                None => match source.connection_uri()? {
+            // each table is written in its own parts (PostgreSQL and MySQL only)
+            let split_by_table = source.split_by_table.unwrap_or(false);
+
+            match (
+                args.source_type.as_ref().map(|x| x.as_str()),
+                connection_uri,
+            ) {
+                (None, Some(connection_uri)) => match connection_uri {
                     ConnectionUri::Postgres(host, port, username, password, database) => {
-                        let postgres = Postgres::new(
+                        let mut postgres = Postgres::new(
                             host.as_str(),
                             port,
                             database.as_str(),
                             username.as_str(),
                             password.as_str(),
                         );
+                        if let Some(ssl_mode) = &ssl_mode {
+                            postgres.set_ssl_mode(ssl_mode.as_str());
+                        }
 
-                        let task = FullDumpTask::new(postgres, datastore, options);
+                        let mut task = FullDumpTask::new(postgres, datastore, options);
+                        task.set_split_by_table(split_by_table);
+                        task.set_events(events);
                         task.run(progress_callback)?
                     }
                     ConnectionUri::Mysql(host, port, username, password, database) => {
@@ -157,18 +381,21 @@ where
                             password.as_str(),
                         );
 
-                        let task = FullDumpTask::new(mysql, datastore, options);
+                        let mut task = FullDumpTask::new(mysql, datastore, options);
+                        task.set_split_by_table(split_by_table);
+                        task.set_events(events);
                         task.run(progress_callback)?
                     }
                     ConnectionUri::MongoDB(uri, database) => {
                         let mongodb = MongoDB::new(uri.as_str(), database.as_str());
 
-                        let task = FullDumpTask::new(mongodb, datastore, options);
+                        let mut task = FullDumpTask::new(mongodb, datastore, options);
+                        task.set_events(events);
                         task.run(progress_callback)?
                     }
                 },
                 // some user use "postgres" and "postgresql" both are valid
-                Some(v) if v == "postgres" || v == "postgresql" => {
+                (Some(v), _) if v == "postgres" || v == "postgresql" => {
                     if args.file.is_some() {
                         let dump_file = File::open(args.file.as_ref().unwrap())?;
                         let mut stdin = stdin(); // FIXME
@@ -177,10 +404,12 @@ where
                     }
 
                     let postgres = PostgresStdin::default();
-                    let task = FullDumpTask::new(postgres, datastore, options);
+                    let mut task = FullDumpTask::new(postgres, datastore, options);
+                    task.set_split_by_table(split_by_table);
+                    task.set_events(events);
                     task.run(progress_callback)?
                 }
-                Some(v) if v == "mysql" => {
+                (Some(v), _) if v == "mysql" => {
                     if args.file.is_some() {
                         let dump_file = File::open(args.file.as_ref().unwrap())?;
                         let mut stdin = stdin(); // FIXME
@@ -189,10 +418,12 @@ where
                     }
 
                     let mysql = MysqlStdin::default();
-                    let task = FullDumpTask::new(mysql, datastore, options);
+                    let mut task = FullDumpTask::new(mysql, datastore, options);
+                    task.set_split_by_table(split_by_table);
+                    task.set_events(events);
                     task.run(progress_callback)?
                 }
-                Some(v) if v == "mongodb" => {
+                (Some(v), _) if v == "mongodb" => {
                     if args.file.is_some() {
                         let dump_file = File::open(args.file.as_ref().unwrap())?;
                         let mut stdin = stdin(); // FIXME
@@ -201,18 +432,27 @@ where
                     }
 
                     let mongodb = MongoDBStdin::default();
-                    let task = FullDumpTask::new(mongodb, datastore, options);
+                    let mut task = FullDumpTask::new(mongodb, datastore, options);
+                    task.set_events(events);
                     task.run(progress_callback)?
                 }
-                Some(v) => {
+                (Some(v), _) => {
                     return Err(anyhow::Error::from(Error::new(
                         ErrorKind::Other,
                         format!("source type '{}' not recognized", v),
                     )));
                 }
+                (None, None) => unreachable!("the connection uri is read without source type"),
+            }
+
+            if let Some(transformers_usage) = &transformers_usage {
+                let _ = check_transformers_usage(transformers_usage)?;
             }
 
-            println!("Dump created successfully!");
+            // the output is only made of events when they are written to stdout
+            if !events_to_stdout {
+                println!("Dump created successfully!");
+            }
             Ok(())
         }
         None => {
@@ -224,12 +464,147 @@ where
     }
 }
 
+/// Compute the statistics missing in the configuration of the number-distribution transformers
+fn set_column_stats(source: &mut SourceConfig, postgres: &Postgres) -> Result<(), Error> {
+    for transformer in source.transformers.iter_mut().flatten() {
+        for column in transformer.columns.iter_mut() {
+            if let TransformerTypeConfig::NumberDistribution(options) = &mut column.transformer {
+                let configured_stats = options.unwrap_or_default();
+
+                if !configured_stats.is_complete() {
+                    let stats = postgres.column_stats(
+                        transformer.database.as_str(),
+                        transformer.table.as_str(),
+                        column.name.as_str(),
+                    )?;
+
+                    *options = Some(configured_stats.or(stats));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Set the date the last dump of the datastore started to read the source as the start of the incremental database
+/// subset, when missing
+fn set_subset_watermark(source: &mut SourceConfig, datastore: &dyn Datastore) -> Result<(), Error> {
+    if let Some(subset_config) = source.database_subset.as_mut() {
+        if let DatabaseSubsetConfigStrategy::Incremental(opt) = &mut subset_config.strategy {
+            if opt.since.is_none() {
+                // the first dump keeps all the rows
+                opt.since = datastore
+                    .index_file()?
+                    .dumps
+                    .iter()
+                    .map(|dump| dump.started_at.unwrap_or(dump.created_at))
+                    .max()
+                    .and_then(|created_at| Utc.timestamp_millis_opt(created_at as i64).single())
+                    .map(|created_at| created_at.format("%Y-%m-%d %H:%M:%S").to_string());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Fail if a configured transformer has never been applied
+fn check_transformers_usage(transformers_usage: &TransformersUsage) -> Result<(), Error> {
+    let unused_transformers = transformers_usage.unused_transformers();
+
+    if !unused_transformers.is_empty() {
+        return Err(Error::new(
+            ErrorKind::Other,
+            format!(
+                "transformers never matched a column: {}",
+                unused_transformers.join(", ")
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
 pub fn delete(datastore: Box<dyn Datastore>, args: &DumpDeleteArgs) -> anyhow::Result<()> {
+    let message = match (&args.dump, &args.older_than, &args.keep_last) {
+        (Some(dump), _, _) => format!("Dump \"{}\" will be deleted.", dump),
+        (_, Some(older_than), _) => format!("Dumps older than {} will be deleted.", older_than),
+        (_, _, Some(keep_last)) => format!("All dumps but the last {} will be deleted.", keep_last),
+        _ => "Dumps will be deleted.".to_string(),
+    };
+
+    confirm(message.as_str(), args.yes, stdin().lock())?;
     let _ = datastore.delete(args)?;
     println!("Dump deleted!");
     Ok(())
 }
 
+/// Check the content of a dump still matches the checksum recorded when it was created
+pub fn verify(datastore: Box<dyn Datastore>, args: &DumpVerifyArgs) -> anyhow::Result<()> {
+    datastore.verify(args.dump.as_str())?;
+    println!("Dump \"{}\" is intact!", args.dump);
+    Ok(())
+}
+
+/// Rewrite a dump with compression (or without it with `--no-compression`), without the source database
+pub fn recompress(
+    mut datastore: Box<dyn Datastore>,
+    config: Config,
+    args: &DumpRecompressArgs,
+) -> anyhow::Result<()> {
+    let options = ReadOptions::Dump {
+        name: args.dump.clone(),
+    };
+
+    // only an encrypted dump is encrypted again
+    if datastore.index_file()?.find_dump(&options)?.encrypted {
+        if let Some(encryption_key) = config.encryption_key()? {
+            datastore.set_encryption_key(encryption_key)
+        }
+    }
+
+    datastore.recompress(args.dump.as_str(), !args.no_compression)?;
+    println!("Dump \"{}\" recompressed!", args.dump);
+    Ok(())
+}
+
+/// Export the index file of the datastore into a local JSON file
+pub fn export_index(datastore: Box<dyn Datastore>, args: &DumpIndexFileArgs) -> anyhow::Result<()> {
+    let index_file = datastore.index_file()?;
+    let file = File::create(&args.file)?;
+    let _ = serde_json::to_writer_pretty(file, &index_file)?;
+    println!("Index exported to {}!", args.file.display());
+    Ok(())
+}
+
+/// Import the dumps of a local JSON index file into the index file of the datastore.
+/// Dumps already referenced by the datastore are kept as is.
+pub fn import_index(datastore: Box<dyn Datastore>, args: &DumpIndexFileArgs) -> anyhow::Result<()> {
+    let file = File::open(&args.file)?;
+    let imported_index_file: IndexFile = serde_json::from_reader(BufReader::new(file))?;
+
+    let mut index_file = datastore.index_file()?;
+    let mut imported_dumps = 0;
+
+    for dump in imported_index_file.dumps {
+        if index_file
+            .dumps
+            .iter()
+            .any(|d| d.directory_name == dump.directory_name)
+        {
+            continue;
+        }
+
+        index_file.dumps.push(dump);
+        imported_dumps += 1;
+    }
+
+    let _ = datastore.write_index_file(&index_file)?;
+    println!("{} dump(s) imported!", imported_dumps);
+    Ok(())
+}
+
 /// Restore a dump in a local container
 pub fn restore_local<F>(
     args: &RestoreLocalArgs,
@@ -251,9 +626,20 @@ where
         },
     };
 
+    let filter = restore_filter(args.schema_only, args.data_only);
+
     if args.output {
         let mut generic_stdout = GenericStdout::new();
-        let task = FullRestoreTask::new(&mut generic_stdout, datastore, options);
+        let mut task = FullRestoreTask::new(&mut generic_stdout, datastore, options);
+        if args.inserts {
+            task.set_copy_to_insert(CopyToInsert::default());
+        }
+        if let Some(translation) = translation(&args.translate_from) {
+            task.set_translation(translation);
+        }
+        if let Some(filter) = filter {
+            task.set_filter(filter);
+        }
         let _ = task.run(|_, _| {})?; // do not display the progress bar
         return Ok(());
     }
@@ -277,8 +663,14 @@ where
             None => DEFAULT_POSTGRES_IMAGE_TAG,
         };
 
+        check_translation_is_supported(&args.translate_from, "PostgreSQL")?;
+
         let mut postgres = PostgresDocker::new(tag.to_string(), port);
-        let task = FullRestoreTask::new(&mut postgres, datastore, options);
+        let mut task = FullRestoreTask::new(&mut postgres, datastore, options);
+        task.set_prerequisites_first(true);
+        if let Some(filter) = filter {
+            task.set_filter(filter);
+        }
         let _ = task.run(progress_callback)?;
 
         print_connection_string_and_wait(
@@ -325,6 +717,9 @@ where
             None => crate::destination::mongodb_docker::DEFAULT_MONGO_IMAGE_TAG,
         };
 
+        check_translation_is_supported(&args.translate_from, "MongoDB")?;
+        check_filter_is_supported(filter, "MongoDB")?;
+
         let mut mongodb = MongoDBDocker::new(tag.to_string(), port);
         let task = FullRestoreTask::new(&mut mongodb, datastore, options);
         let _ = task.run(progress_callback)?;
@@ -371,7 +766,13 @@ where
         };
 
         let mut mysql = MysqlDocker::new(tag.to_string(), port);
-        let task = FullRestoreTask::new(&mut mysql, datastore, options);
+        let mut task = FullRestoreTask::new(&mut mysql, datastore, options);
+        if let Some(translation) = translation(&args.translate_from) {
+            task.set_translation(translation);
+        }
+        if let Some(filter) = filter {
+            task.set_filter(filter);
+        }
         let _ = task.run(progress_callback)?;
 
         print_connection_string_and_wait(
@@ -432,9 +833,20 @@ where
         },
     };
 
+    let filter = restore_filter(args.schema_only, args.data_only);
+
     if args.output {
         let mut generic_stdout = GenericStdout::new();
-        let task = FullRestoreTask::new(&mut generic_stdout, datastore, options);
+        let mut task = FullRestoreTask::new(&mut generic_stdout, datastore, options);
+        if args.inserts {
+            task.set_copy_to_insert(CopyToInsert::default());
+        }
+        if let Some(translation) = translation(&args.translate_from) {
+            task.set_translation(translation);
+        }
+        if let Some(filter) = filter {
+            task.set_filter(filter);
+        }
         let _ = task.run(|_, _| {})?; // do not display the progress bar
         return Ok(());
     }
@@ -443,19 +855,40 @@ where
         Some(destination) => {
             match destination.connection_uri()? {
                 ConnectionUri::Postgres(host, port, username, password, database) => {
+                    check_translation_is_supported(&args.translate_from, "PostgreSQL")?;
+
+                    let wipe_database = wipe_database(destination.wipe_database, filter);
+                    if wipe_database {
+                        let message = format!(
+                            "Database \"{}\" on {}:{} will be wiped before the restore.",
+                            database, host, port
+                        );
+
+                        confirm(message.as_str(), args.yes, stdin().lock())?;
+                    }
+
                     let mut postgres = destination::postgres::Postgres::new(
                         host.as_str(),
                         port,
                         database.as_str(),
                         username.as_str(),
                         password.as_str(),
-                        destination.wipe_database.unwrap_or(true),
+                        wipe_database,
                     );
 
-                    let task = FullRestoreTask::new(&mut postgres, datastore, options);
+                    let mut task = FullRestoreTask::new(&mut postgres, datastore, options);
+                    task.set_prerequisites_first(true);
+                    if let Some(workers) = args.workers {
+                        task.set_workers(workers);
+                    }
+                    if let Some(filter) = filter {
+                        task.set_filter(filter);
+                    }
                     task.run(progress_callback)?
                 }
                 ConnectionUri::Mysql(host, port, username, password, database) => {
+                    check_workers_are_supported(args.workers, "MySQL")?;
+
                     let mut mysql = destination::mysql::Mysql::new(
                         host.as_str(),
                         port,
@@ -463,10 +896,20 @@ where
                         username.as_str(),
                         password.as_str(),
                     );
-                    let task = FullRestoreTask::new(&mut mysql, datastore, options);
+                    let mut task = FullRestoreTask::new(&mut mysql, datastore, options);
+                    if let Some(translation) = translation(&args.translate_from) {
+                        task.set_translation(translation);
+                    }
+                    if let Some(filter) = filter {
+                        task.set_filter(filter);
+                    }
                     task.run(progress_callback)?;
                 }
                 ConnectionUri::MongoDB(uri, database) => {
+                    check_translation_is_supported(&args.translate_from, "MongoDB")?;
+                    check_workers_are_supported(args.workers, "MongoDB")?;
+                    check_filter_is_supported(filter, "MongoDB")?;
+
                     let mut mongodb =
                         destination::mongodb::MongoDB::new(uri.as_str(), database.as_str());
 
@@ -487,6 +930,96 @@ where
     }
 }
 
+/// translation of the dump statements for a MySQL destination
+fn translation(translate_from: &Option<String>) -> Option<PostgresToMysql> {
+    // `postgresql` is the only value accepted by the CLI
+    translate_from.as_ref().map(|_| PostgresToMysql::default())
+}
+
+fn check_translation_is_supported(
+    translate_from: &Option<String>,
+    destination: &str,
+) -> Result<(), Error> {
+    match translate_from {
+        Some(translate_from) => Err(Error::new(
+            ErrorKind::Other,
+            format!(
+                "translating a {} dump is only supported into MySQL, not {}",
+                translate_from, destination
+            ),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// part of the dump to restore, from the `--schema-only` and `--data-only` flags
+fn restore_filter(schema_only: bool, data_only: bool) -> Option<RestoreFilter> {
+    match (schema_only, data_only) {
+        (true, _) => Some(RestoreFilter::SchemaOnly),
+        (_, true) => Some(RestoreFilter::DataOnly),
+        _ => None,
+    }
+}
+
+/// whether the destination database is wiped before the restore - never for `--data-only`, the dump has no schema to
+/// create the wiped tables again
+fn wipe_database(wipe_database: Option<bool>, filter: Option<RestoreFilter>) -> bool {
+    match filter {
+        Some(RestoreFilter::DataOnly) => false,
+        _ => wipe_database.unwrap_or(true),
+    }
+}
+
+fn check_filter_is_supported(
+    filter: Option<RestoreFilter>,
+    destination: &str,
+) -> Result<(), Error> {
+    match filter {
+        Some(_) => Err(Error::new(
+            ErrorKind::Other,
+            format!(
+                "restoring only the schema or the data is not supported into {}",
+                destination
+            ),
+        )),
+        None => Ok(()),
+    }
+}
+
+fn check_workers_are_supported(workers: Option<usize>, destination: &str) -> Result<(), Error> {
+    match workers {
+        Some(_) => Err(Error::new(
+            ErrorKind::Other,
+            format!(
+                "restoring with several workers is only supported into PostgreSQL, not {}",
+                destination
+            ),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// ask for a confirmation before a destructive operation - `yes` skips the prompt
+fn confirm<R: BufRead>(message: &str, yes: bool, mut input: R) -> Result<(), Error> {
+    if yes {
+        return Ok(());
+    }
+
+    print!("{} Continue? [y/N] ", message);
+    let _ = std::io::stdout().flush();
+
+    let mut answer = String::new();
+    let _ = input.read_line(&mut answer)?;
+
+    match answer.trim().to_lowercase().as_str() {
+        "y" | "yes" => Ok(()),
+        _ => Err(Error::new(
+            ErrorKind::Other,
+            "operation aborted - use `--yes` to skip the confirmation",
+        )),
+    }
+}
+
 fn wait_until_ctrlc(msg: &str) {
     let (tx, rx) = mpsc::channel();
     ctrlc::set_handler(move || tx.send(()).expect("cound not send signal on channel"))
@@ -500,3 +1033,302 @@ fn print_connection_string_and_wait(msg: &str, connection_string: &str) {
     println!("> {}", connection_string);
     wait_until_ctrlc("Waiting for Ctrl-C to stop the container");
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs::read_dir;
+    use std::io::BufReader;
+
+    use tempfile::tempdir;
+
+    use chrono::{TimeZone, Utc};
+
+    use crate::cli::DumpIndexFileArgs;
+    use crate::commands::dump::{
+        check_transformers_usage, confirm, export_index, import_index, restore_filter,
+        set_dump_name, set_subset_watermark, wipe_database,
+    };
+    use crate::config::{DatabaseSubsetConfigStrategy, SourceConfig};
+    use crate::connector::Connector;
+    use crate::datastore::local_disk::LocalDisk;
+    use crate::datastore::{Datastore, ReadOptions};
+    use crate::source::postgres::read_and_transform;
+    use crate::source::SourceOptions;
+    use crate::tasks::full_restore::RestoreFilter;
+    use crate::transformer::random::RandomTransformer;
+    use crate::transformer::tracked::track;
+    use crate::transformer::Transformer;
+
+    fn get_local_disk(dir: &str) -> Box<dyn Datastore> {
+        let mut local_disk = LocalDisk::new(dir.to_string());
+        let _ = local_disk.init().unwrap();
+        Box::new(local_disk)
+    }
+
+    /// source with an incremental database subset on `public.orders`
+    fn incremental_source(since: Option<&str>) -> SourceConfig {
+        let mut yaml = r#"
+database_subset:
+  database: public
+  table: orders
+  strategy_name: incremental
+  strategy_options:
+    column: updated_at
+"#
+        .to_string();
+
+        if let Some(since) = since {
+            yaml.push_str(format!("    since: '{}'\n", since).as_str());
+        }
+
+        serde_yaml::from_str(yaml.as_str()).unwrap()
+    }
+
+    fn subset_condition(source: &SourceConfig) -> Option<String> {
+        match &source.database_subset.as_ref().unwrap().strategy {
+            DatabaseSubsetConfigStrategy::Incremental(opt) => opt.condition(),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn incremental_subset_starts_at_the_last_dump() {
+        let dir = tempdir().expect("cannot create tempdir");
+        let mut datastore = get_local_disk(dir.path().to_str().unwrap());
+
+        // the first dump keeps all the rows
+        let mut source = incremental_source(None);
+        set_subset_watermark(&mut source, datastore.as_ref()).unwrap();
+        assert_eq!(subset_condition(&source), None);
+
+        datastore.set_dump_name("dump-1".to_string());
+        let size = datastore.write(1, b"hello world".to_vec()).unwrap();
+        datastore.commit(size, None).unwrap();
+
+        let created_at = datastore.index_file().unwrap().dumps[0].created_at;
+        let watermark = Utc
+            .timestamp_millis_opt(created_at as i64)
+            .unwrap()
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+
+        // the next dumps keep the rows changed since the last one
+        let mut source = incremental_source(None);
+        set_subset_watermark(&mut source, datastore.as_ref()).unwrap();
+        assert_eq!(
+            subset_condition(&source),
+            Some(format!("updated_at > '{}'", watermark))
+        );
+
+        // the next dumps start when the last one started to read the source, not when it was committed
+        datastore.set_dump_name("dump-2".to_string());
+        datastore.set_dump_started_at(created_at + 60_000);
+        let size = datastore.write(1, b"hello world".to_vec()).unwrap();
+        datastore.commit(size, None).unwrap();
+
+        let watermark = Utc
+            .timestamp_millis_opt((created_at + 60_000) as i64)
+            .unwrap()
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+
+        let mut source = incremental_source(None);
+        set_subset_watermark(&mut source, datastore.as_ref()).unwrap();
+        assert_eq!(
+            subset_condition(&source),
+            Some(format!("updated_at > '{}'", watermark))
+        );
+
+        // a configured date takes precedence
+        let mut source = incremental_source(Some("2022-05-01 00:00:00"));
+        set_subset_watermark(&mut source, datastore.as_ref()).unwrap();
+        assert_eq!(
+            subset_condition(&source),
+            Some("updated_at > '2022-05-01 00:00:00'".to_string())
+        );
+    }
+
+    #[test]
+    fn export_and_import_index() {
+        let source_dir = tempdir().expect("cannot create tempdir");
+        let target_dir = tempdir().expect("cannot create tempdir");
+        let index_dir = tempdir().expect("cannot create tempdir");
+        let source_dir = source_dir.path().to_str().unwrap();
+        let target_dir = target_dir.path().to_str().unwrap();
+
+        let mut local_disk = LocalDisk::new(source_dir.to_string());
+        let _ = local_disk.init().unwrap();
+        local_disk.set_dump_name("dump-1".to_string());
+        let size = local_disk.write(1, b"hello world".to_vec()).unwrap();
+        let _ = local_disk.commit(size, None).unwrap();
+
+        let args = DumpIndexFileArgs {
+            file: index_dir.path().join("index.json"),
+        };
+
+        let _ = export_index(get_local_disk(source_dir), &args).unwrap();
+        let _ = import_index(get_local_disk(target_dir), &args).unwrap();
+
+        let index_file = get_local_disk(target_dir).index_file().unwrap();
+        assert_eq!(index_file.dumps.len(), 1);
+        assert_eq!(
+            index_file.dumps,
+            get_local_disk(source_dir).index_file().unwrap().dumps
+        );
+
+        // importing the same index twice must not duplicate the dumps
+        let _ = import_index(get_local_disk(target_dir), &args).unwrap();
+        assert_eq!(
+            get_local_disk(target_dir).index_file().unwrap().dumps.len(),
+            1
+        );
+    }
+
+    #[test]
+    fn create_a_named_dump() {
+        let dir = tempdir().expect("cannot create tempdir");
+        let dir = dir.path().to_str().unwrap();
+
+        let mut datastore = get_local_disk(dir);
+        set_dump_name(&mut datastore, "pre-release-2024-06", false).unwrap();
+        let size = datastore.write(1, b"hello world".to_vec()).unwrap();
+        datastore.commit(size, None).unwrap();
+
+        let index_file = get_local_disk(dir).index_file().unwrap();
+        assert_eq!(index_file.dumps.len(), 1);
+        assert_eq!(index_file.dumps[0].directory_name, "pre-release-2024-06");
+
+        let mut data = vec![];
+        get_local_disk(dir)
+            .read(
+                &ReadOptions::Dump {
+                    name: "pre-release-2024-06".to_string(),
+                },
+                &mut |bytes| data.extend(bytes),
+            )
+            .unwrap();
+        assert_eq!(data, b"hello world".to_vec());
+
+        // the name of a dump is unique
+        assert!(set_dump_name(&mut get_local_disk(dir), "pre-release-2024-06", false).is_err());
+
+        // the name is a directory of the datastore
+        assert!(set_dump_name(&mut get_local_disk(dir), "", false).is_err());
+        assert!(set_dump_name(&mut get_local_disk(dir), "..", false).is_err());
+        assert!(set_dump_name(&mut get_local_disk(dir), "../pre-release", false).is_err());
+        assert!(set_dump_name(&mut get_local_disk(dir), "pre release", false).is_err());
+        assert!(set_dump_name(&mut get_local_disk(dir), "latest", false).is_err());
+        assert!(set_dump_name(&mut get_local_disk(dir), "release_2024.06", false).is_ok());
+    }
+
+    #[test]
+    fn force_replaces_a_named_dump() {
+        let dir = tempdir().expect("cannot create tempdir");
+        let dir = dir.path().to_str().unwrap();
+
+        let read_dump = |name: &str| {
+            let mut data = vec![];
+            get_local_disk(dir)
+                .read(
+                    &ReadOptions::Dump {
+                        name: name.to_string(),
+                    },
+                    &mut |bytes| data.extend(bytes),
+                )
+                .unwrap();
+            data
+        };
+
+        let mut datastore = get_local_disk(dir);
+        set_dump_name(&mut datastore, "nightly", false).unwrap();
+        let size = datastore.write(1, b"hello world".to_vec()).unwrap();
+        datastore.commit(size, None).unwrap();
+
+        // a name collision fails without --force
+        assert!(set_dump_name(&mut get_local_disk(dir), "nightly", false).is_err());
+
+        let mut datastore = get_local_disk(dir);
+        set_dump_name(&mut datastore, "nightly", true).unwrap();
+        let size = datastore.write(1, b"goodbye world".to_vec()).unwrap();
+
+        // the dump is kept as it is until the new one is committed
+        assert_eq!(get_local_disk(dir).index_file().unwrap().dumps.len(), 1);
+        assert_eq!(read_dump("nightly"), b"hello world".to_vec());
+
+        datastore.commit(size, None).unwrap();
+
+        let index_file = get_local_disk(dir).index_file().unwrap();
+        assert_eq!(index_file.dumps.len(), 1);
+        assert_eq!(index_file.dumps[0].directory_name, "nightly");
+        assert_eq!(read_dump("nightly"), b"goodbye world".to_vec());
+
+        // the new dump is not left under its temporary name
+        let dump_dirs = read_dir(dir)
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_dir())
+            .count();
+        assert_eq!(dump_dirs, 1);
+    }
+
+    #[test]
+    fn strict_transformers_fail_on_unused_transformer() {
+        let dump = r#"
+INSERT INTO public.employees (employee_id, last_name, email) VALUES (1, 'Davolio', 'nancy.davolio@northwind.com');
+"#;
+
+        let t1: Box<dyn Transformer> =
+            Box::new(RandomTransformer::new("public", "employees", "last_name"));
+        let t2: Box<dyn Transformer> =
+            Box::new(RandomTransformer::new("public", "employees", "emial"));
+        let (transformers, usage) = track(vec![t1, t2]);
+
+        let source_options = SourceOptions {
+            transformers: &transformers,
+            skip_config: &vec![],
+            database_subset: &None,
+            only_tables: &vec![],
+            schema_only_tables: &vec![],
+            max_table_size: None,
+            tmp_dir: None,
+            detect_pii_comments: false,
+            capture_sequences: false,
+            on_transform_error: None,
+            amplify: &vec![],
+            seed: None,
+        };
+
+        read_and_transform(BufReader::new(dump.as_bytes()), source_options, |_, _| {}).unwrap();
+
+        let err = check_transformers_usage(&usage).unwrap_err();
+        assert!(err.to_string().contains("public.employees.emial"));
+        assert!(!err.to_string().contains("public.employees.last_name"));
+    }
+
+    #[test]
+    fn confirm_destructive_operation() {
+        let message = "Dump \"dump-1\" will be deleted.";
+
+        // `--yes` proceeds without reading the input
+        assert!(confirm(message, true, "".as_bytes()).is_ok());
+
+        assert!(confirm(message, false, "y\n".as_bytes()).is_ok());
+        assert!(confirm(message, false, "YES\n".as_bytes()).is_ok());
+        assert!(confirm(message, false, "n\n".as_bytes()).is_err());
+        // no answer (e.g. in a CI) must not be taken as a confirmation
+        assert!(confirm(message, false, "".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn keep_the_destination_database_for_a_data_only_restore() {
+        assert!(wipe_database(None, restore_filter(false, false)));
+        assert!(wipe_database(None, restore_filter(true, false)));
+        assert!(!wipe_database(Some(false), restore_filter(false, false)));
+
+        // the rows would be written into a database without tables
+        let filter = restore_filter(false, true);
+        assert_eq!(filter, Some(RestoreFilter::DataOnly));
+        assert!(!wipe_database(None, filter));
+        assert!(!wipe_database(Some(true), filter));
+    }
+}
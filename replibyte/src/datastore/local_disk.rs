@@ -1,4 +1,4 @@
-use std::fs::{read, read_dir, remove_dir_all, write, DirBuilder, OpenOptions};
+use std::fs::{read, read_dir, remove_dir_all, rename, write, DirBuilder, OpenOptions};
 use std::io::{BufReader, Error, Read, Write};
 use std::path::Path;
 
@@ -9,11 +9,18 @@ use crate::connector::Connector;
 use crate::types;
 use crate::utils::epoch_millis;
 
-use super::{compress, decompress, decrypt, encrypt, Datastore, Dump, IndexFile, INDEX_FILE_NAME};
+use super::{
+    compress, decompress, decrypt, encrypt, part_number, Datastore, Dump, DumpTable, IndexFile,
+    ReadOptions, INDEX_FILE_NAME,
+};
 
 pub struct LocalDisk {
     dir: String,
+    index_file_name: String,
     dump_name: String,
+    dump_tables: Vec<DumpTable>,
+    dump_started_at: Option<u128>,
+    replaced_dump: Option<String>,
     enable_compression: bool,
     encryption_key: Option<String>,
 }
@@ -22,12 +29,83 @@ impl LocalDisk {
     pub fn new<S: Into<String>>(dir: S) -> Self {
         Self {
             dir: dir.into(),
+            index_file_name: INDEX_FILE_NAME.to_string(),
             enable_compression: true,
             encryption_key: None,
             dump_name: format!("dump-{}", epoch_millis()),
+            dump_tables: vec![],
+            dump_started_at: None,
+            replaced_dump: None,
         }
     }
 
+    /// keep the dumps in a separate index file, so several pipelines can share the same directory
+    pub fn set_index_file_name(&mut self, name: String) {
+        self.index_file_name = name;
+    }
+
+    /// swap the committed dump with the dump `name`, which is deleted - it is moved aside until the committed dump
+    /// takes its place, so a failure never leaves no dump under this name
+    fn replace_dump(&self, name: &str) -> Result<(), Error> {
+        let dump_dir_path = format!("{}/{}", self.dir, self.dump_name);
+        let replaced_dir_path = format!("{}/{}", self.dir, name);
+        let aside_dir_path = format!("{}.replaced", dump_dir_path);
+
+        let mut index_file = self.index_file()?;
+        let is_replacing = index_file.dumps.iter().any(|b| b.directory_name == name);
+
+        if is_replacing {
+            rename(&replaced_dir_path, &aside_dir_path).map_err(|err| {
+                error!(
+                    "error while renaming the dump directory: {}",
+                    replaced_dir_path
+                );
+                err
+            })?;
+        }
+
+        // the replaced dump is put back on failure
+        let restore_replaced_dump = || {
+            if is_replacing {
+                let _ = rename(&aside_dir_path, &replaced_dir_path);
+            }
+        };
+
+        if let Err(err) = rename(&dump_dir_path, &replaced_dir_path) {
+            error!("error while renaming the dump directory: {}", dump_dir_path);
+            restore_replaced_dump();
+            return Err(err);
+        }
+
+        index_file.dumps.retain(|b| b.directory_name != name);
+        if let Some(dump) = index_file
+            .dumps
+            .iter_mut()
+            .find(|b| b.directory_name == self.dump_name)
+        {
+            dump.directory_name = name.to_string();
+        }
+
+        if let Err(err) = self.write_index_file(&index_file) {
+            let _ = rename(&replaced_dir_path, &dump_dir_path);
+            restore_replaced_dump();
+            return Err(err);
+        }
+
+        // the replaced dump is deleted last, once the committed dump is listed under its name
+        if is_replacing {
+            remove_dir_all(&aside_dir_path).map_err(|err| {
+                error!(
+                    "error while removing the dump directory: {}",
+                    aside_dir_path
+                );
+                err
+            })?;
+        }
+
+        Ok(())
+    }
+
     fn create_index_file(&self) -> Result<IndexFile, Error> {
         match self.index_file() {
             Ok(index_file) => Ok(index_file),
@@ -54,7 +132,7 @@ impl Datastore for LocalDisk {
 
         let file = OpenOptions::new()
             .read(true)
-            .open(format!("{}/{}", self.dir, INDEX_FILE_NAME))?;
+            .open(format!("{}/{}", self.dir, self.index_file_name))?;
 
         let reader = BufReader::new(file);
 
@@ -69,7 +147,7 @@ impl Datastore for LocalDisk {
 
         let file = OpenOptions::new()
             .read(true)
-            .open(format!("{}/{}", self.dir, INDEX_FILE_NAME))?;
+            .open(format!("{}/{}", self.dir, self.index_file_name))?;
 
         let reader = BufReader::new(file);
 
@@ -80,7 +158,7 @@ impl Datastore for LocalDisk {
 
     fn write_index_file(&self, index_file: &IndexFile) -> Result<(), Error> {
         info!("writing index_file");
-        let index_file_path = format!("{}/{}", self.dir, INDEX_FILE_NAME);
+        let index_file_path = format!("{}/{}", self.dir, self.index_file_name);
 
         debug!("opening index_file at {}", index_file_path);
         let file = OpenOptions::new()
@@ -95,7 +173,7 @@ impl Datastore for LocalDisk {
 
     fn write_raw_index_file(&self, raw_index_file: &Value) -> Result<(), Error> {
         info!("writing raw index_file");
-        let index_file_path = format!("{}/{}", self.dir, INDEX_FILE_NAME);
+        let index_file_path = format!("{}/{}", self.dir, self.index_file_name);
 
         debug!("opening index_file at {}", index_file_path);
         let file = OpenOptions::new()
@@ -108,7 +186,7 @@ impl Datastore for LocalDisk {
         serde_json::to_writer(file, raw_index_file).map_err(|err| Error::from(err))
     }
 
-    fn write(&self, file_part: u16, data: types::Bytes) -> Result<(), Error> {
+    fn write(&self, file_part: u16, data: types::Bytes) -> Result<usize, Error> {
         // compress data?
         let data = if self.compression_enabled() {
             compress(data)?
@@ -141,15 +219,26 @@ impl Datastore for LocalDisk {
             err
         })?;
 
+        Ok(data_size)
+    }
+
+    fn commit(&self, size: usize, checksum: Option<String>) -> Result<(), Error> {
         // update index file
         let mut index_file = self.index_file()?;
 
         let mut new_dump = Dump {
             directory_name: self.dump_name.to_string(),
+            location: None,
             size: 0,
             created_at: epoch_millis(),
+            started_at: self.dump_started_at,
             compressed: self.compression_enabled(),
             encrypted: self.encryption_key().is_some(),
+            checksum,
+            tables: match self.dump_tables.is_empty() {
+                true => None,
+                false => Some(self.dump_tables.clone()),
+            },
         };
 
         // find or create Dump
@@ -162,15 +251,22 @@ impl Datastore for LocalDisk {
         if dump.size == 0 {
             // it means it's a new dump.
             // We need to add it into the index_file.dumps
-            new_dump.size = data_size;
+            new_dump.size = size;
             index_file.dumps.push(new_dump);
         } else {
-            // update total dump size
-            dump.size = dump.size + data_size;
+            // update total dump size - the checksum only covers the new parts
+            dump.size = dump.size + size;
+            dump.checksum = None;
+            dump.tables = None;
         }
 
         // save index file
-        self.write_index_file(&index_file)
+        self.write_index_file(&index_file)?;
+
+        match &self.replaced_dump {
+            Some(name) => self.replace_dump(name),
+            None => Ok(()),
+        }
     }
 
     fn read(
@@ -180,11 +276,13 @@ impl Datastore for LocalDisk {
     ) -> Result<(), Error> {
         let mut index_file = self.index_file()?;
         let dump = index_file.find_dump(options)?;
-        let entries = read_dir(format!("{}/{}", self.dir, dump.directory_name))?;
+        let mut paths = read_dir(format!("{}/{}", self.dir, dump.directory_name))?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, Error>>()?;
+        paths.sort_by_key(|path| part_number(path.to_str().unwrap_or_default()));
 
-        for entry in entries {
-            let entry = entry?;
-            let data = read(entry.path())?;
+        for path in paths {
+            let data = read(path)?;
 
             // decrypt data?
             let data = if dump.encrypted {
@@ -234,8 +332,41 @@ impl Datastore for LocalDisk {
         self.dump_name = name
     }
 
+    fn set_dump_tables(&mut self, tables: Vec<DumpTable>) {
+        self.dump_tables = tables
+    }
+
+    fn set_dump_started_at(&mut self, started_at: u128) {
+        self.dump_started_at = Some(started_at)
+    }
+
+    fn set_replaced_dump(&mut self, name: String) {
+        self.dump_name = format!("{}.new-{}", name, epoch_millis());
+        self.replaced_dump = Some(name);
+    }
+
+    fn set_temporary_dump(&mut self) {
+        if self.replaced_dump.is_none() {
+            self.set_replaced_dump(self.dump_name.to_string());
+        }
+    }
+
+    fn discard(&self) -> Result<(), Error> {
+        let dump_dir_path = format!("{}/{}", self.dir, self.dump_name);
+        if !Path::new(&dump_dir_path).exists() {
+            return Ok(());
+        }
+
+        remove_dir_all(&dump_dir_path).map_err(|err| {
+            error!("error while removing the dump directory: {}", dump_dir_path);
+            err
+        })
+    }
+
     fn delete_by_name(&self, name: String) -> Result<(), Error> {
         let mut index_file = self.index_file()?;
+        // the directory may be shared with other index files, only their own dumps are deleted
+        let _ = index_file.find_dump(&ReadOptions::Dump { name: name.clone() })?;
 
         let dump_dir_path = format!("{}/{}", self.dir, name);
         remove_dir_all(&dump_dir_path).map_err(|err| {
@@ -257,12 +388,13 @@ mod tests {
 
     use chrono::{Duration, Utc};
     use serde_json::json;
+    use sha2::{Digest, Sha256};
     use tempfile::tempdir;
 
     use crate::{
         cli::DumpDeleteArgs,
         connector::Connector,
-        datastore::{Datastore, Dump, ReadOptions, INDEX_FILE_NAME},
+        datastore::{Datastore, Dump, DumpTable, ReadOptions, INDEX_FILE_NAME},
         migration::{
             rename_backups_to_dumps::RenameBackupsToDump,
             update_version_number::UpdateVersionNumber, Migrator,
@@ -304,7 +436,8 @@ mod tests {
 
         let bytes: Vec<u8> = b"hello world".to_vec();
 
-        assert!(local_disk.write(1, bytes).is_ok());
+        let size = local_disk.write(1, bytes).unwrap();
+        assert!(local_disk.commit(size, None).is_ok());
 
         // index_file should contain 1 dump
         let mut index_file = local_disk.index_file().unwrap();
@@ -330,6 +463,142 @@ mod tests {
         assert_eq!(dump_content, b"hello world".to_vec())
     }
 
+    #[test]
+    fn test_write_and_read_encrypted_dump_part_by_part() {
+        let dir = tempdir().expect("cannot create tempdir");
+        let mut local_disk = LocalDisk::new(dir.path().to_str().unwrap().to_string());
+        let _ = local_disk.init().expect("local_disk init failed");
+        local_disk.set_encryption_key("this is my secret".to_string());
+
+        // 8 parts of 1MB each
+        let parts = (0..8u8)
+            .map(|i| vec![b'a' + i; 1024 * 1024])
+            .collect::<Vec<_>>();
+
+        let mut size = 0;
+        for (i, part) in parts.iter().enumerate() {
+            size += local_disk.write(i as u16 + 1, part.clone()).unwrap();
+        }
+        assert!(local_disk.commit(size, None).is_ok());
+
+        let mut index_file = local_disk.index_file().unwrap();
+        assert!(index_file.find_dump(&ReadOptions::Latest).unwrap().encrypted);
+
+        // each part must be decrypted and handed over on its own, never the whole dump at once
+        let mut chunks = 0;
+        assert!(local_disk
+            .read(&ReadOptions::Latest, &mut |bytes| {
+                chunks += 1;
+                assert!(parts.contains(&bytes));
+            })
+            .is_ok());
+        assert_eq!(chunks, parts.len());
+    }
+
+    #[test]
+    fn test_recompress() {
+        let dir = tempdir().expect("cannot create tempdir");
+        let dir = dir.path().to_str().unwrap();
+
+        // an uncompressed dump of 2 parts
+        let mut local_disk = LocalDisk::new(dir.to_string());
+        let _ = local_disk.init().expect("local_disk init failed");
+        local_disk.set_compression(false);
+        local_disk.set_dump_name("dump-1".to_string());
+        let tables = vec![DumpTable {
+            name: "public.orders".to_string(),
+            parts: vec![2],
+        }];
+        local_disk.set_dump_tables(tables.clone());
+
+        let parts = [vec![b'a'; 1024 * 1024], vec![b'b'; 1024 * 1024]];
+        let mut size = 0;
+        for (i, part) in parts.iter().enumerate() {
+            size += local_disk.write(i as u16 + 1, part.clone()).unwrap();
+        }
+        let checksum = format!("{:x}", Sha256::digest(parts.concat()));
+        assert!(local_disk.commit(size, Some(checksum.clone())).is_ok());
+
+        let mut index_file = local_disk.index_file().unwrap();
+        let created_at = index_file.dumps[0].created_at;
+        assert!(!index_file.dumps[0].compressed);
+
+        let mut local_disk = LocalDisk::new(dir.to_string());
+        assert!(local_disk.recompress("dump-1", true).is_ok());
+
+        // the dump is replaced by its compressed version
+        index_file = local_disk.index_file().unwrap();
+        assert_eq!(index_file.dumps.len(), 1);
+        let dump = &index_file.dumps[0];
+        assert_eq!(dump.directory_name, "dump-1");
+        assert!(dump.compressed);
+        assert!(dump.size < size);
+        assert_eq!(dump.created_at, created_at);
+        assert_eq!(dump.checksum, Some(checksum));
+        assert_eq!(dump.tables, Some(tables));
+        assert_eq!(std::fs::read_dir(dir).unwrap().count(), 2);
+
+        let mut dump_content: Vec<u8> = vec![];
+        assert!(local_disk
+            .read(&ReadOptions::Latest, &mut |mut bytes| {
+                dump_content.append(&mut bytes);
+            })
+            .is_ok());
+        assert_eq!(dump_content, parts.concat());
+        assert!(local_disk.verify("dump-1").is_ok());
+    }
+
+    #[test]
+    fn keep_the_replaced_dump_when_the_replacement_fails() {
+        let dir = tempdir().expect("cannot create tempdir");
+        let dir = dir.path().to_str().unwrap();
+
+        let mut local_disk = LocalDisk::new(dir.to_string());
+        let _ = local_disk.init().expect("local_disk init failed");
+        local_disk.set_dump_name("prod".to_string());
+        let size = local_disk.write(1, b"INSERT INTO customers VALUES (1);".to_vec());
+        assert!(local_disk.commit(size.unwrap(), None).is_ok());
+
+        let read_prod = |local_disk: &LocalDisk| {
+            let mut dump_content: Vec<u8> = vec![];
+            let options = ReadOptions::Dump {
+                name: "prod".to_string(),
+            };
+            assert!(local_disk
+                .read(&options, &mut |mut bytes| dump_content.append(&mut bytes))
+                .is_ok());
+            dump_content
+        };
+
+        // the parts of the new dump are missing, it can't take the place of the replaced dump
+        let mut local_disk = LocalDisk::new(dir.to_string());
+        local_disk.set_replaced_dump("prod".to_string());
+        assert!(local_disk.commit(0, None).is_err());
+        assert_eq!(read_prod(&local_disk), b"INSERT INTO customers VALUES (1);");
+
+        let mut local_disk = LocalDisk::new(dir.to_string());
+        local_disk.set_replaced_dump("prod".to_string());
+        let size = local_disk.write(1, b"INSERT INTO customers VALUES (2);".to_vec());
+        assert!(local_disk.commit(size.unwrap(), None).is_ok());
+        assert_eq!(read_prod(&local_disk), b"INSERT INTO customers VALUES (2);");
+
+        // the replaced dump is deleted
+        let index_file = local_disk.index_file().unwrap();
+        assert_eq!(
+            index_file
+                .dumps
+                .iter()
+                .filter(|dump| dump.directory_name == "prod")
+                .count(),
+            1
+        );
+        let dir_names = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        assert!(!dir_names.iter().any(|name| name.ends_with(".replaced")));
+    }
+
     #[test]
     fn test_index_file() {
         let dir = tempdir().expect("cannot create tempdir");
@@ -344,10 +613,14 @@ mod tests {
 
         index_file.dumps.push(Dump {
             directory_name: "dump-1".to_string(),
+            location: None,
             size: 0,
             created_at: epoch_millis(),
+            started_at: None,
             compressed: true,
             encrypted: false,
+            checksum: None,
+            tables: None,
         });
 
         assert!(local_disk.write_index_file(&index_file).is_ok());
@@ -379,14 +652,16 @@ mod tests {
         // create dump 1
         local_disk.set_dump_name("dump-1".to_string());
         let bytes: Vec<u8> = b"hello world from dump-1".to_vec();
-        assert!(local_disk.write(1, bytes).is_ok());
+        let size = local_disk.write(1, bytes).unwrap();
+        assert!(local_disk.commit(size, None).is_ok());
         assert_eq!(local_disk.index_file().unwrap().dumps.len(), 1);
         assert!(Path::new(&format!("{}/dump-1", dir.path().to_str().unwrap())).exists());
 
         // create dump 2
         local_disk.set_dump_name("dump-2".to_string());
         let bytes: Vec<u8> = b"hello world from dump-2".to_vec();
-        assert!(local_disk.write(1, bytes).is_ok());
+        let size = local_disk.write(1, bytes).unwrap();
+        assert!(local_disk.commit(size, None).is_ok());
         assert_eq!(local_disk.index_file().unwrap().dumps.len(), 2);
         assert!(Path::new(&format!("{}/dump-2", dir.path().to_str().unwrap())).exists());
 
@@ -395,7 +670,8 @@ mod tests {
             .delete(&DumpDeleteArgs {
                 dump: Some("dump-1".to_string()),
                 older_than: None,
-                keep_last: None
+                keep_last: None,
+                yes: true,
             })
             .is_ok());
         assert_eq!(local_disk.index_file().unwrap().dumps.len(), 1);
@@ -406,7 +682,8 @@ mod tests {
             .delete(&DumpDeleteArgs {
                 dump: Some("dump-2".to_string()),
                 older_than: None,
-                keep_last: None
+                keep_last: None,
+                yes: true,
             })
             .is_ok());
         assert_eq!(local_disk.index_file().unwrap().dumps.len(), 0);
@@ -427,7 +704,8 @@ mod tests {
         // create dump 1
         local_disk.set_dump_name("dump-1".to_string());
         let bytes: Vec<u8> = b"hello world from dump-1".to_vec();
-        assert!(local_disk.write(1, bytes).is_ok());
+        let size = local_disk.write(1, bytes).unwrap();
+        assert!(local_disk.commit(size, None).is_ok());
         assert_eq!(local_disk.index_file().unwrap().dumps.len(), 1);
         assert!(Path::new(&format!("{}/dump-1", dir.path().to_str().unwrap())).exists());
         update_dump_date(&local_disk, "dump-1".to_string(), 3);
@@ -435,7 +713,8 @@ mod tests {
         // create dump 2
         local_disk.set_dump_name("dump-2".to_string());
         let bytes: Vec<u8> = b"hello world from dump-2".to_vec();
-        assert!(local_disk.write(1, bytes).is_ok());
+        let size = local_disk.write(1, bytes).unwrap();
+        assert!(local_disk.commit(size, None).is_ok());
         assert_eq!(local_disk.index_file().unwrap().dumps.len(), 2);
         assert!(Path::new(&format!("{}/dump-2", dir.path().to_str().unwrap())).exists());
         update_dump_date(&local_disk, "dump-2".to_string(), 2);
@@ -443,7 +722,8 @@ mod tests {
         // create dump 3
         local_disk.set_dump_name("dump-3".to_string());
         let bytes: Vec<u8> = b"hello world from dump-3".to_vec();
-        assert!(local_disk.write(1, bytes).is_ok());
+        let size = local_disk.write(1, bytes).unwrap();
+        assert!(local_disk.commit(size, None).is_ok());
         assert_eq!(local_disk.index_file().unwrap().dumps.len(), 3);
         assert!(Path::new(&format!("{}/dump-3", dir.path().to_str().unwrap())).exists());
         update_dump_date(&local_disk, "dump-3".to_string(), 1);
@@ -453,6 +733,7 @@ mod tests {
                 dump: None,
                 older_than: None,
                 keep_last: Some(2),
+                yes: true,
             })
             .is_ok());
         assert_eq!(local_disk.index_file().unwrap().dumps.len(), 2);
@@ -466,6 +747,7 @@ mod tests {
                 dump: None,
                 older_than: None,
                 keep_last: Some(1),
+                yes: true,
             })
             .is_ok());
         assert_eq!(local_disk.index_file().unwrap().dumps.len(), 1);
@@ -489,7 +771,8 @@ mod tests {
         // create dump 1
         local_disk.set_dump_name("dump-1".to_string());
         let bytes: Vec<u8> = b"hello world from dump-1".to_vec();
-        assert!(local_disk.write(1, bytes).is_ok());
+        let size = local_disk.write(1, bytes).unwrap();
+        assert!(local_disk.commit(size, None).is_ok());
         assert_eq!(local_disk.index_file().unwrap().dumps.len(), 1);
         assert!(Path::new(&format!("{}/dump-1", dir.path().to_str().unwrap())).exists());
         update_dump_date(&local_disk, "dump-1".to_string(), 5);
@@ -497,7 +780,8 @@ mod tests {
         // create dump 2
         local_disk.set_dump_name("dump-2".to_string());
         let bytes: Vec<u8> = b"hello world from dump-2".to_vec();
-        assert!(local_disk.write(1, bytes).is_ok());
+        let size = local_disk.write(1, bytes).unwrap();
+        assert!(local_disk.commit(size, None).is_ok());
         assert_eq!(local_disk.index_file().unwrap().dumps.len(), 2);
         assert!(Path::new(&format!("{}/dump-2", dir.path().to_str().unwrap())).exists());
         update_dump_date(&local_disk, "dump-2".to_string(), 3);
@@ -505,7 +789,8 @@ mod tests {
         // create dump 3
         local_disk.set_dump_name("dump-3".to_string());
         let bytes: Vec<u8> = b"hello world from dump-3".to_vec();
-        assert!(local_disk.write(1, bytes).is_ok());
+        let size = local_disk.write(1, bytes).unwrap();
+        assert!(local_disk.commit(size, None).is_ok());
         assert_eq!(local_disk.index_file().unwrap().dumps.len(), 3);
         assert!(Path::new(&format!("{}/dump-3", dir.path().to_str().unwrap())).exists());
 
@@ -515,6 +800,7 @@ mod tests {
                 dump: None,
                 older_than: Some("6d".to_string()),
                 keep_last: None,
+                yes: true,
             })
             .is_ok());
         assert_eq!(local_disk.index_file().unwrap().dumps.len(), 3);
@@ -528,6 +814,7 @@ mod tests {
                 dump: None,
                 older_than: Some("4d".to_string()),
                 keep_last: None,
+                yes: true,
             })
             .is_ok());
         assert_eq!(local_disk.index_file().unwrap().dumps.len(), 2);
@@ -541,6 +828,7 @@ mod tests {
                 dump: None,
                 older_than: Some("1d".to_string()),
                 keep_last: None,
+                yes: true,
             })
             .is_ok());
         assert_eq!(local_disk.index_file().unwrap().dumps.len(), 1);
@@ -554,6 +842,7 @@ mod tests {
                 dump: None,
                 older_than: Some("0d".to_string()),
                 keep_last: None,
+                yes: true,
             })
             .is_ok());
         assert_eq!(local_disk.index_file().unwrap().dumps.len(), 0);
@@ -623,21 +912,69 @@ mod tests {
             local_disk.index_file().unwrap().dumps.get(0),
             Some(&Dump {
                 directory_name: "dump-1653170039392".to_string(),
+                location: None,
                 size: 62279,
                 created_at: 1234,
+                started_at: None,
                 compressed: true,
-                encrypted: false
+                encrypted: false,
+                checksum: None,
+                tables: None
             })
         );
         assert_eq!(
             local_disk.index_file().unwrap().dumps.get(1),
             Some(&Dump {
                 directory_name: "dump-1653170570014".to_string(),
+                location: None,
                 size: 62283,
                 created_at: 5678,
+                started_at: None,
                 compressed: true,
-                encrypted: false
+                encrypted: false,
+                checksum: None,
+                tables: None
             })
         );
     }
+
+    #[test]
+    fn test_index_names_keep_dumps_separate() {
+        let dir = tempdir().expect("cannot create tempdir");
+        let dir = dir.path().to_str().unwrap();
+
+        let mut local_disk_a = LocalDisk::new(dir);
+        local_disk_a.set_index_file_name("project-a.json".to_string());
+        local_disk_a.set_dump_name("dump-a".to_string());
+        local_disk_a.init().expect("local_disk init failed");
+
+        let mut local_disk_b = LocalDisk::new(dir);
+        local_disk_b.set_index_file_name("project-b.json".to_string());
+        local_disk_b.set_dump_name("dump-b".to_string());
+        local_disk_b.init().expect("local_disk init failed");
+
+        for local_disk in [&local_disk_a, &local_disk_b] {
+            let size = local_disk.write(1, b"hello world".to_vec()).unwrap();
+            local_disk.commit(size, None).unwrap();
+        }
+
+        // each index file only lists the dump of its pipeline
+        let dumps_a = local_disk_a.index_file().unwrap().dumps;
+        assert_eq!(dumps_a.len(), 1);
+        assert_eq!(dumps_a[0].directory_name, "dump-a");
+
+        let dumps_b = local_disk_b.index_file().unwrap().dumps;
+        assert_eq!(dumps_b.len(), 1);
+        assert_eq!(dumps_b[0].directory_name, "dump-b");
+
+        assert!(!Path::new(&format!("{}/{}", dir, INDEX_FILE_NAME)).exists());
+
+        // the dumps of the other index file can't be deleted
+        assert!(local_disk_a.delete_by_name("dump-b".to_string()).is_err());
+        assert!(Path::new(&format!("{}/dump-b/1.dump", dir)).exists());
+
+        assert!(local_disk_b.delete_by_name("dump-b".to_string()).is_ok());
+        assert!(local_disk_b.index_file().unwrap().dumps.is_empty());
+        assert_eq!(local_disk_a.index_file().unwrap().dumps.len(), 1);
+    }
 }
@@ -8,6 +8,7 @@ use flate2::read::ZlibDecoder;
 use flate2::write::ZlibEncoder;
 use flate2::Compression;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 use crate::cli::DumpDeleteArgs;
 use crate::connector::Connector;
@@ -15,6 +16,7 @@ use crate::types::Bytes;
 use crate::utils::get_replibyte_version;
 
 pub mod local_disk;
+pub mod multi;
 pub mod s3;
 
 const INDEX_FILE_NAME: &str = "metadata.json";
@@ -25,7 +27,13 @@ pub trait Datastore: Connector + Send + Sync {
     fn raw_index_file(&self) -> Result<Value, Error>;
     fn write_index_file(&self, index_file: &IndexFile) -> Result<(), Error>;
     fn write_raw_index_file(&self, raw_index_file: &Value) -> Result<(), Error>;
-    fn write(&self, file_part: u16, data: Bytes) -> Result<(), Error>;
+    /// Write a dump part without adding it to the index file, and return the size written.
+    /// The dump is not visible (list, restore) until it is committed.
+    fn write(&self, file_part: u16, data: Bytes) -> Result<usize, Error>;
+    /// Add the dump to the index file with the checksum of its content, or increase its size if it is already there
+    fn commit(&self, size: usize, checksum: Option<String>) -> Result<(), Error>;
+    /// Read a dump part by part: each part is decrypted and decompressed on its own
+    /// before being passed to `data_callback`, so the whole dump is never held in memory.
     fn read(
         &self,
         options: &ReadOptions,
@@ -36,8 +44,129 @@ pub trait Datastore: Connector + Send + Sync {
     fn encryption_key(&self) -> &Option<String>;
     fn set_encryption_key(&mut self, key: String);
     fn set_dump_name(&mut self, name: String);
+    /// Record in the index file the parts holding the rows of each table, for a dump split per table
+    fn set_dump_tables(&mut self, tables: Vec<DumpTable>);
+    /// Record in the index file when the source started to be read (epoch in millis)
+    fn set_dump_started_at(&mut self, started_at: u128);
+    /// Write the dump under a temporary name, then replace the dump `name` by it once it is committed.
+    /// The dump `name` is kept as it is if the new dump is never committed.
+    fn set_replaced_dump(&mut self, name: String);
+    /// Write the dump under a temporary name until it is committed, as a dump replacing the dump of its name - it is
+    /// already the case for a replaced dump
+    fn set_temporary_dump(&mut self);
+    /// Delete the parts written so far of a dump that is not committed (e.g. when the source fails)
+    fn discard(&self) -> Result<(), Error>;
     fn delete_by_name(&self, name: String) -> Result<(), Error>;
 
+    /// Read the dump again and check its content still matches the checksum recorded when it was created
+    fn verify(&self, name: &str) -> Result<(), Error> {
+        let options = ReadOptions::Dump {
+            name: name.to_string(),
+        };
+
+        let checksum = self.index_file()?.find_dump(&options)?.checksum.clone();
+        let checksum = checksum.ok_or_else(|| {
+            Error::new(
+                ErrorKind::Other,
+                format!(
+                    "dump '{}' has no checksum - it was created by an older version of Replibyte",
+                    name
+                ),
+            )
+        })?;
+
+        let mut hasher = Sha256::new();
+        self.read(&options, &mut |data| hasher.update(&data))?;
+        let actual_checksum = format!("{:x}", hasher.finalize());
+
+        if actual_checksum != checksum {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!(
+                    "dump '{}' is corrupted: its checksum is {} instead of {}",
+                    name, actual_checksum, checksum
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Rewrite the dump `name` with or without compression, part by part. The new dump is written under a temporary
+    /// name and only replaces the dump `name` once it is complete, so an interrupted rewrite leaves it untouched.
+    fn recompress(&mut self, name: &str, compression: bool) -> Result<(), Error> {
+        let options = ReadOptions::Dump {
+            name: name.to_string(),
+        };
+
+        let mut index_file = self.index_file()?;
+        let dump = index_file.find_dump(&options)?;
+
+        if dump.encrypted && self.encryption_key().is_none() {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!("dump '{}' is encrypted - set the encryption key", name),
+            ));
+        }
+
+        let created_at = dump.created_at;
+        let started_at = dump.started_at;
+        let checksum = dump.checksum.clone();
+
+        self.set_compression(compression);
+        self.set_replaced_dump(name.to_string());
+        if let Some(tables) = dump.tables.clone() {
+            // the parts are written again with the same numbers
+            self.set_dump_tables(tables);
+        }
+
+        let mut hasher = Sha256::new();
+        let mut size = 0;
+        let mut file_part = 0u16;
+        let mut write_result = Ok(());
+        self.read(&options, &mut |data| {
+            if write_result.is_err() {
+                return;
+            }
+
+            file_part += 1;
+            hasher.update(&data);
+            match self.write(file_part, data) {
+                Ok(part_size) => size += part_size,
+                Err(err) => write_result = Err(err),
+            }
+        })?;
+        write_result?;
+
+        let actual_checksum = format!("{:x}", hasher.finalize());
+        if let Some(checksum) = checksum {
+            if actual_checksum != checksum {
+                return Err(Error::new(
+                    ErrorKind::Other,
+                    format!(
+                        "dump '{}' is corrupted: its checksum is {} instead of {}",
+                        name, actual_checksum, checksum
+                    ),
+                ));
+            }
+        }
+
+        self.commit(size, Some(actual_checksum))?;
+
+        // the rewritten dump keeps its dates, e.g. to stay the latest one or not
+        let mut index_file = self.index_file()?;
+        if let Some(dump) = index_file
+            .dumps
+            .iter_mut()
+            .find(|dump| dump.directory_name == name)
+        {
+            dump.created_at = created_at;
+            dump.started_at = started_at;
+        }
+
+        self.write_index_file(&index_file)
+    }
+
     fn delete(&self, args: &DumpDeleteArgs) -> Result<(), Error> {
         if let Some(dump_name) = &args.dump {
             return self.delete_by_name(dump_name.to_string());
@@ -163,10 +292,41 @@ impl IndexFile {
 #[derive(Debug, Serialize, Deserialize, Ord, PartialOrd, Eq, PartialEq)]
 pub struct Dump {
     pub directory_name: String,
+    /// directory of the parts when it differs from the name of the dump - a dump replacing another one on S3 keeps
+    /// the objects it was written to, they can't be renamed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
     pub size: usize,
     pub created_at: u128,
+    /// when the source started to be read - the rows changed after it are in the next dumps. None for the dumps
+    /// created by an older version of Replibyte
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<u128>,
     pub compressed: bool,
     pub encrypted: bool,
+    /// SHA-256 of the dump content (before compression and encryption)
+    #[serde(default)]
+    pub checksum: Option<String>,
+    /// parts of each table when the dump is split per table - none for a single stream of parts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tables: Option<Vec<DumpTable>>,
+}
+
+impl Dump {
+    /// directory of the parts of the dump
+    pub fn location(&self) -> &str {
+        self.location
+            .as_deref()
+            .unwrap_or(self.directory_name.as_str())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Ord, PartialOrd, Eq, PartialEq, Clone)]
+pub struct DumpTable {
+    /// table name as written in the dump (e.g. public.orders)
+    pub name: String,
+    /// parts holding the rows of the table - the other parts hold the schema
+    pub parts: Vec<u16>,
 }
 
 #[derive(Serialize, Deserialize, Ord, PartialOrd, Eq, PartialEq, Clone)]
@@ -175,6 +335,11 @@ pub enum ReadOptions {
     Dump { name: String },
 }
 
+/// part number of a dump part (e.g. `dump-1653170039392/12.dump` -> 12), to read the parts in the order they were written
+fn part_number(path: &str) -> Option<u16> {
+    path.rsplit('/').next()?.strip_suffix(".dump")?.parse().ok()
+}
+
 fn compress(data: Bytes) -> Result<Bytes, Error> {
     let mut enc = ZlibEncoder::new(Vec::new(), Compression::default());
     let _ = enc.write_all(data.as_slice());
@@ -0,0 +1,181 @@
+use std::io::{Error, ErrorKind};
+
+use log::info;
+use serde_json::Value;
+
+use crate::cli::DumpDeleteArgs;
+use crate::connector::Connector;
+use crate::types::Bytes;
+use crate::utils::epoch_millis;
+
+use super::{Datastore, DumpTable, IndexFile, ReadOptions};
+
+/// Fan out a dump to several datastores (e.g. AWS S3 and a local disk mirror) - each of them maintains its own index file.
+/// The dumps are read (list, restore) from the first datastore, the primary one.
+pub struct MultiDatastore {
+    datastores: Vec<Box<dyn Datastore>>,
+}
+
+impl MultiDatastore {
+    pub fn new(mut datastores: Vec<Box<dyn Datastore>>) -> Self {
+        // the dump must have the same name in all the datastores
+        let dump_name = format!("dump-{}", epoch_millis());
+        for datastore in datastores.iter_mut() {
+            datastore.set_dump_name(dump_name.clone());
+        }
+
+        MultiDatastore { datastores }
+    }
+
+    fn primary(&self) -> &dyn Datastore {
+        self.datastores[0].as_ref()
+    }
+}
+
+impl Connector for MultiDatastore {
+    fn init(&mut self) -> Result<(), Error> {
+        for datastore in self.datastores.iter_mut() {
+            datastore.init()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Datastore for MultiDatastore {
+    fn index_file(&self) -> Result<IndexFile, Error> {
+        self.primary().index_file()
+    }
+
+    fn raw_index_file(&self) -> Result<Value, Error> {
+        self.primary().raw_index_file()
+    }
+
+    fn write_index_file(&self, index_file: &IndexFile) -> Result<(), Error> {
+        self.primary().write_index_file(index_file)
+    }
+
+    fn write_raw_index_file(&self, raw_index_file: &Value) -> Result<(), Error> {
+        self.primary().write_raw_index_file(raw_index_file)
+    }
+
+    fn write(&self, file_part: u16, data: Bytes) -> Result<usize, Error> {
+        // the datastores share the compression and encryption settings, so the size written is the same for all
+        let mut size = 0;
+        for datastore in self.datastores.iter() {
+            size = datastore.write(file_part, data.clone())?;
+        }
+
+        Ok(size)
+    }
+
+    fn commit(&self, size: usize, checksum: Option<String>) -> Result<(), Error> {
+        for datastore in self.datastores.iter() {
+            datastore.commit(size, checksum.clone())?;
+        }
+
+        Ok(())
+    }
+
+    fn read(
+        &self,
+        options: &ReadOptions,
+        data_callback: &mut dyn FnMut(Bytes),
+    ) -> Result<(), Error> {
+        self.primary().read(options, data_callback)
+    }
+
+    fn compression_enabled(&self) -> bool {
+        self.primary().compression_enabled()
+    }
+
+    fn set_compression(&mut self, enable: bool) {
+        for datastore in self.datastores.iter_mut() {
+            datastore.set_compression(enable);
+        }
+    }
+
+    fn encryption_key(&self) -> &Option<String> {
+        self.primary().encryption_key()
+    }
+
+    fn set_encryption_key(&mut self, key: String) {
+        for datastore in self.datastores.iter_mut() {
+            datastore.set_encryption_key(key.clone());
+        }
+    }
+
+    fn set_dump_name(&mut self, name: String) {
+        for datastore in self.datastores.iter_mut() {
+            datastore.set_dump_name(name.clone());
+        }
+    }
+
+    fn set_dump_tables(&mut self, tables: Vec<DumpTable>) {
+        for datastore in self.datastores.iter_mut() {
+            datastore.set_dump_tables(tables.clone());
+        }
+    }
+
+    fn set_dump_started_at(&mut self, started_at: u128) {
+        for datastore in self.datastores.iter_mut() {
+            datastore.set_dump_started_at(started_at);
+        }
+    }
+
+    fn set_replaced_dump(&mut self, name: String) {
+        for datastore in self.datastores.iter_mut() {
+            datastore.set_replaced_dump(name.clone());
+        }
+    }
+
+    fn set_temporary_dump(&mut self) {
+        for datastore in self.datastores.iter_mut() {
+            datastore.set_temporary_dump();
+        }
+    }
+
+    fn discard(&self) -> Result<(), Error> {
+        for datastore in self.datastores.iter() {
+            datastore.discard()?;
+        }
+
+        Ok(())
+    }
+
+    fn delete_by_name(&self, name: String) -> Result<(), Error> {
+        let options = ReadOptions::Dump { name: name.clone() };
+        let mut deleted = false;
+
+        // a datastore added to the configuration later does not have the older dumps
+        for datastore in self.datastores.iter() {
+            if datastore.index_file()?.find_dump(&options).is_ok() {
+                datastore.delete_by_name(name.clone())?;
+                deleted = true;
+            } else {
+                info!("dump '{}' not found in one of the datastores", name);
+            }
+        }
+
+        match deleted {
+            true => Ok(()),
+            false => Err(Error::new(
+                ErrorKind::Other,
+                format!("Can't find dump with name '{}'", name),
+            )),
+        }
+    }
+
+    fn delete(&self, args: &DumpDeleteArgs) -> Result<(), Error> {
+        // `--older-than` and `--keep-last` are applied to the index file of each datastore
+        if let Some(dump_name) = &args.dump {
+            return self.delete_by_name(dump_name.to_string());
+        }
+
+        for datastore in self.datastores.iter() {
+            datastore.delete(args)?;
+        }
+
+        Ok(())
+    }
+}
@@ -18,7 +18,8 @@ use crate::config::{AwsCredentials, Endpoint};
 use crate::connector::Connector;
 use crate::datastore::s3::S3Error::FailedObjectUpload;
 use crate::datastore::{
-    compress, decompress, decrypt, encrypt, Datastore, Dump, IndexFile, ReadOptions,
+    compress, decompress, decrypt, encrypt, part_number, Datastore, Dump, DumpTable, IndexFile,
+    ReadOptions,
 };
 use crate::runtime::block_on;
 use crate::types::Bytes;
@@ -30,7 +31,12 @@ const GOOGLE_CLOUD_STORAGE_URL: &str = "https://storage.googleapis.com";
 
 pub struct S3 {
     bucket: String,
+    prefix: String,
+    index_file_name: String,
     root_key: String,
+    dump_tables: Vec<DumpTable>,
+    dump_started_at: Option<u128>,
+    replaced_dump: Option<String>,
     region: Option<String>,
     endpoint: Endpoint,
     client: Client,
@@ -106,7 +112,12 @@ impl S3 {
 
         Ok(S3 {
             bucket: bucket.as_ref().into(),
+            prefix: String::new(),
+            index_file_name: INDEX_FILE_NAME.to_string(),
             root_key: format!("dump-{}", epoch_millis()),
+            dump_tables: vec![],
+            dump_started_at: None,
+            replaced_dump: None,
             region: region.map(|region| region.as_ref().into()),
             endpoint,
             client: Client::from_conf(s3_config),
@@ -145,6 +156,58 @@ impl S3 {
         )
     }
 
+    /// isolate the index file and the dumps under a key prefix (e.g. replibyte/project-a/)
+    pub fn set_prefix(&mut self, prefix: String) {
+        let prefix = prefix.trim_matches('/');
+
+        self.prefix = match prefix.is_empty() {
+            true => String::new(),
+            false => format!("{}/", prefix),
+        };
+    }
+
+    /// keep the dumps in a separate index file, so several pipelines can share the same bucket and prefix
+    pub fn set_index_file_name(&mut self, name: String) {
+        self.index_file_name = name;
+    }
+
+    /// return the object key with the prefix
+    fn key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
+    }
+
+    /// swap the committed dump with the dump `name`, which is deleted
+    fn replace_dump(&self, name: &str) -> Result<(), Error> {
+        let mut index_file = self.index_file()?;
+        let replaced_location = index_file
+            .dumps
+            .iter()
+            .find(|b| b.directory_name == name)
+            .map(|b| b.location().to_string());
+
+        // objects can't be renamed: the committed dump takes the name of the replaced one and keeps its objects, in
+        // a single write of the index file
+        index_file.dumps.retain(|b| b.directory_name != name);
+        if let Some(dump) = index_file
+            .dumps
+            .iter_mut()
+            .find(|b| b.directory_name == self.root_key)
+        {
+            dump.directory_name = name.to_string();
+            dump.location = Some(self.root_key.to_string());
+        }
+
+        self.write_index_file(&index_file)?;
+
+        // the objects of the replaced dump are deleted last, once the new dump is listed under its name
+        if let Some(replaced_location) = replaced_location {
+            let directory = format!("{}/", self.key(replaced_location.as_str()));
+            delete_directory(&self.client, self.bucket.as_str(), directory.as_str())?;
+        }
+
+        Ok(())
+    }
+
     fn create_index_file(&self) -> Result<IndexFile, Error> {
         match self.index_file() {
             Ok(index_file) => Ok(index_file),
@@ -174,13 +237,15 @@ impl Connector for S3 {
 
 impl Datastore for S3 {
     fn index_file(&self) -> Result<IndexFile, Error> {
-        let object = get_object(&self.client, self.bucket.as_str(), INDEX_FILE_NAME)?;
+        let index_file_key = self.key(self.index_file_name.as_str());
+        let object = get_object(&self.client, self.bucket.as_str(), index_file_key.as_str())?;
         let index_file: IndexFile = serde_json::from_slice(object.as_slice())?;
         Ok(index_file)
     }
 
     fn raw_index_file(&self) -> Result<Value, Error> {
-        let object = get_object(&self.client, self.bucket.as_str(), INDEX_FILE_NAME)?;
+        let index_file_key = self.key(self.index_file_name.as_str());
+        let object = get_object(&self.client, self.bucket.as_str(), index_file_key.as_str())?;
         let index_file = serde_json::from_slice(object.as_slice())?;
 
         Ok(index_file)
@@ -192,7 +257,7 @@ impl Datastore for S3 {
         create_object(
             &self.client,
             self.bucket.as_str(),
-            INDEX_FILE_NAME,
+            self.key(self.index_file_name.as_str()).as_str(),
             index_file_json,
         )
         .map_err(|err| Error::from(err))
@@ -204,23 +269,40 @@ impl Datastore for S3 {
         create_object(
             &self.client,
             self.bucket.as_str(),
-            INDEX_FILE_NAME,
+            self.key(self.index_file_name.as_str()).as_str(),
             index_file_json,
         )
         .map_err(|err| Error::from(err))
     }
 
-    fn write(&self, file_part: u16, data: Bytes) -> Result<(), Error> {
+    fn write(&self, file_part: u16, data: Bytes) -> Result<usize, Error> {
         write_objects(
             self,
             file_part,
             data,
             self.bucket.as_str(),
+            self.prefix.as_str(),
             self.root_key.as_str(),
             &self.client,
         )
     }
 
+    fn commit(&self, size: usize, checksum: Option<String>) -> Result<(), Error> {
+        add_dump_to_index_file(
+            self,
+            self.root_key.as_str(),
+            &self.dump_tables,
+            self.dump_started_at,
+            size,
+            checksum,
+        )?;
+
+        match &self.replaced_dump {
+            Some(name) => self.replace_dump(name),
+            None => Ok(()),
+        }
+    }
+
     fn read(
         &self,
         options: &ReadOptions,
@@ -228,12 +310,13 @@ impl Datastore for S3 {
     ) -> Result<(), Error> {
         let mut index_file = self.index_file()?;
         let dump = index_file.find_dump(options)?;
+        let dump_key = format!("{}/", self.key(dump.location()));
 
-        for object in list_objects(
-            &self.client,
-            self.bucket.as_str(),
-            Some(dump.directory_name.as_str()),
-        )? {
+        let mut objects =
+            list_objects(&self.client, self.bucket.as_str(), Some(dump_key.as_str()))?;
+        objects.sort_by_key(|object| part_number(object.key().unwrap_or_default()));
+
+        for object in objects {
             let data = get_object(&self.client, self.bucket.as_str(), object.key().unwrap())?;
 
             // decrypt data?
@@ -271,6 +354,32 @@ impl Datastore for S3 {
         self.root_key = name;
     }
 
+    fn set_dump_tables(&mut self, tables: Vec<DumpTable>) {
+        self.dump_tables = tables;
+    }
+
+    fn set_dump_started_at(&mut self, started_at: u128) {
+        self.dump_started_at = Some(started_at);
+    }
+
+    fn set_replaced_dump(&mut self, name: String) {
+        self.root_key = format!("{}.new-{}", name, epoch_millis());
+        self.replaced_dump = Some(name);
+    }
+
+    fn set_temporary_dump(&mut self) {
+        if self.replaced_dump.is_none() {
+            self.set_replaced_dump(self.root_key.to_string());
+        }
+    }
+
+    fn discard(&self) -> Result<(), Error> {
+        let directory = format!("{}/", self.key(self.root_key.as_str()));
+        delete_directory(&self.client, self.bucket.as_str(), directory.as_str())?;
+
+        Ok(())
+    }
+
     fn compression_enabled(&self) -> bool {
         self.enable_compression
     }
@@ -281,10 +390,15 @@ impl Datastore for S3 {
 
     fn delete_by_name(&self, name: String) -> Result<(), Error> {
         let mut index_file = self.index_file()?;
+        // the bucket may be shared with other index files, only their own dumps are deleted
+        let dump = index_file.find_dump(&ReadOptions::Dump { name: name.clone() })?;
 
         let bucket = &self.bucket;
 
-        let _ = delete_directory(&self.client, bucket, &name).map_err(|err| Error::from(err))?;
+        // the trailing `/` keeps the dumps whose name starts with this one (e.g. `prod.v2` or `prod.new-<ts>`)
+        let directory = format!("{}/", self.key(dump.location()));
+        let _ =
+            delete_directory(&self.client, bucket, &directory).map_err(|err| Error::from(err))?;
 
         index_file.dumps.retain(|b| b.directory_name != name);
 
@@ -297,9 +411,10 @@ fn write_objects<B: Datastore>(
     file_part: u16,
     data: Bytes,
     bucket: &str,
+    prefix: &str,
     root_key: &str,
     client: &Client,
-) -> Result<(), Error> {
+) -> Result<usize, Error> {
     // compress data?
     let data = if datastore.compression_enabled() {
         compress(data)?
@@ -314,21 +429,39 @@ fn write_objects<B: Datastore>(
     };
 
     let data_size = data.len();
-    let key = format!("{}/{}.dump", root_key, file_part);
+    let key = format!("{}{}/{}.dump", prefix, root_key, file_part);
 
     info!("upload object '{}' part {} on", key.as_str(), file_part);
 
     let _ = create_object(client, bucket, key.as_str(), data)?;
 
+    Ok(data_size)
+}
+
+fn add_dump_to_index_file<B: Datastore>(
+    datastore: &B,
+    root_key: &str,
+    tables: &[DumpTable],
+    started_at: Option<u128>,
+    size: usize,
+    checksum: Option<String>,
+) -> Result<(), Error> {
     // update index file
     let mut index_file = datastore.index_file()?;
 
     let mut new_dump = Dump {
         directory_name: root_key.to_string(),
+        location: None,
         size: 0,
         created_at: epoch_millis(),
+        started_at,
         compressed: datastore.compression_enabled(),
         encrypted: datastore.encryption_key().is_some(),
+        checksum,
+        tables: match tables.is_empty() {
+            true => None,
+            false => Some(tables.to_vec()),
+        },
     };
 
     // find or create dump
@@ -341,11 +474,13 @@ fn write_objects<B: Datastore>(
     if dump.size == 0 {
         // it means it's a new dump.
         // We need to add it into the index_file.dumps
-        new_dump.size = data_size;
+        new_dump.size = size;
         index_file.dumps.push(new_dump);
     } else {
-        // update total dump size
-        dump.size = dump.size + data_size;
+        // update total dump size - the checksum only covers the new parts
+        dump.size = dump.size + size;
+        dump.checksum = None;
+        dump.tables = None;
     }
 
     // save index file
@@ -611,14 +746,16 @@ mod tests {
     use chrono::{Duration, Utc};
     use fake::{Fake, Faker};
     use serde_json::json;
+    use sha2::{Digest, Sha256};
 
     use crate::cli::DumpDeleteArgs;
     use crate::config::{AwsCredentials, Endpoint};
     use crate::connector::Connector;
     use crate::datastore::s3::{
-        create_bucket, create_object, delete_bucket, delete_object, get_object, S3Error,
+        create_bucket, create_object, delete_bucket, delete_object, get_object, list_objects,
+        S3Error,
     };
-    use crate::datastore::{Datastore, Dump, INDEX_FILE_NAME};
+    use crate::datastore::{Datastore, Dump, ReadOptions, INDEX_FILE_NAME};
     use crate::migration::rename_backups_to_dumps::RenameBackupsToDump;
     use crate::migration::update_version_number::UpdateVersionNumber;
     use crate::migration::Migrator;
@@ -853,10 +990,14 @@ mod tests {
 
         index_file.dumps.push(Dump {
             directory_name: "dump-1".to_string(),
+            location: None,
             size: 0,
             created_at: epoch_millis(),
+            started_at: None,
             compressed: true,
             encrypted: false,
+            checksum: None,
+            tables: None,
         });
 
         assert!(s3.write_index_file(&index_file).is_ok());
@@ -876,6 +1017,77 @@ mod tests {
         assert_eq!(s3.root_key, "custom-dump-name".to_string())
     }
 
+    #[test]
+    fn test_prefix() {
+        let bucket = aws_bucket();
+        let mut s3 = aws_s3(bucket.as_str());
+
+        assert_eq!(s3.key(INDEX_FILE_NAME), INDEX_FILE_NAME.to_string());
+
+        s3.set_prefix("/replibyte/project-a/".to_string());
+        assert_eq!(
+            s3.key(INDEX_FILE_NAME),
+            format!("replibyte/project-a/{}", INDEX_FILE_NAME)
+        );
+
+        s3.set_prefix("replibyte/project-b".to_string());
+        assert_eq!(s3.key("dump-1"), "replibyte/project-b/dump-1".to_string());
+    }
+
+    #[test]
+    fn test_s3_dumps_with_prefixes() {
+        let bucket = aws_bucket();
+
+        let mut s3_a = aws_s3(bucket.as_str());
+        s3_a.set_prefix("replibyte/project-a".to_string());
+        s3_a.set_dump_name("dump-1".to_string());
+        let _ = s3_a.init().expect("s3 init failed");
+
+        let mut s3_b = aws_s3(bucket.as_str());
+        s3_b.set_prefix("replibyte/project-b".to_string());
+        let _ = s3_b.init().expect("s3 init failed");
+
+        let size = s3_a.write(1, b"hello w0rld".to_vec()).unwrap();
+        assert!(s3_a.commit(size, None).is_ok());
+
+        // the index file and the dump are prefixed
+        assert!(get_object(&s3_a.client, bucket.as_str(), INDEX_FILE_NAME).is_err());
+        assert!(get_object(
+            &s3_a.client,
+            bucket.as_str(),
+            format!("replibyte/project-a/{}", INDEX_FILE_NAME).as_str()
+        )
+        .is_ok());
+        assert!(get_object(
+            &s3_a.client,
+            bucket.as_str(),
+            "replibyte/project-a/dump-1/1.dump"
+        )
+        .is_ok());
+
+        // the dumps of a prefix are not visible from another one
+        assert_eq!(s3_a.index_file().unwrap().dumps.len(), 1);
+        assert!(s3_b.index_file().unwrap().dumps.is_empty());
+
+        assert!(s3_a
+            .delete(&DumpDeleteArgs {
+                dump: Some("dump-1".to_string()),
+                older_than: None,
+                keep_last: None,
+                yes: true,
+            })
+            .is_ok());
+        assert!(s3_a.index_file().unwrap().dumps.is_empty());
+        assert!(get_object(
+            &s3_a.client,
+            bucket.as_str(),
+            "replibyte/project-a/dump-1/1.dump"
+        )
+        .is_err());
+
+        assert!(delete_bucket(&s3_a.client, bucket.as_str(), true).is_ok());
+    }
+
     #[test]
     fn test_s3_dump_delete_by_name() {
         let bucket = aws_bucket();
@@ -892,18 +1104,26 @@ mod tests {
         // Add 2 dumps in the manifest
         index_file.dumps.push(Dump {
             directory_name: "dump-1".to_string(),
+            location: None,
             size: 0,
             created_at: epoch_millis(),
+            started_at: None,
             compressed: true,
             encrypted: false,
+            checksum: None,
+            tables: None,
         });
 
         index_file.dumps.push(Dump {
             directory_name: "dump-2".to_string(),
+            location: None,
             size: 0,
             created_at: epoch_millis(),
+            started_at: None,
             compressed: true,
             encrypted: false,
+            checksum: None,
+            tables: None,
         });
 
         assert!(s3.write_index_file(&index_file).is_ok());
@@ -930,6 +1150,7 @@ mod tests {
                 dump: Some("dump-1".to_string()),
                 older_than: None,
                 keep_last: None,
+                yes: true,
             })
             .is_ok());
 
@@ -942,12 +1163,97 @@ mod tests {
                 dump: Some("dump-2".to_string()),
                 older_than: None,
                 keep_last: None,
+                yes: true,
             })
             .is_ok());
         assert!(s3.index_file().unwrap().dumps.is_empty());
         assert!(get_object(&s3.client, bucket.as_str(), "dump-2/testing-key.dump").is_err());
     }
 
+    fn write_dump(s3: &S3, data: &[u8]) {
+        assert!(s3.write(1, data.to_vec()).is_ok());
+        assert!(s3.commit(data.len(), None).is_ok());
+    }
+
+    fn read_dump(s3: &S3, name: &str) -> Vec<u8> {
+        let mut dump = vec![];
+        let options = ReadOptions::Dump {
+            name: name.to_string(),
+        };
+        assert!(s3.read(&options, &mut |data| dump.extend(data)).is_ok());
+        dump
+    }
+
+    #[test]
+    fn test_s3_replace_dump() {
+        let bucket = aws_bucket();
+        let mut s3 = aws_s3(bucket.as_str());
+        let _ = s3.init().expect("s3 init failed");
+
+        s3.set_dump_name("prod".to_string());
+        write_dump(&s3, b"INSERT INTO customers VALUES (1);");
+
+        // a dump whose name starts with the name of the replaced dump
+        let mut other_s3 = aws_s3(bucket.as_str());
+        other_s3.set_dump_name("prod.v2".to_string());
+        write_dump(&other_s3, b"INSERT INTO customers VALUES (2);");
+
+        let mut s3 = aws_s3(bucket.as_str());
+        s3.set_replaced_dump("prod".to_string());
+        write_dump(&s3, b"INSERT INTO customers VALUES (3);");
+
+        let dumps = s3.index_file().unwrap().dumps;
+        assert_eq!(dumps.len(), 2);
+        assert_eq!(
+            read_dump(&s3, "prod"),
+            b"INSERT INTO customers VALUES (3);".to_vec()
+        );
+        assert_eq!(
+            read_dump(&s3, "prod.v2"),
+            b"INSERT INTO customers VALUES (2);".to_vec()
+        );
+
+        // the objects of the replaced dump are gone, the new dump keeps the objects it was written to
+        let objects = list_objects(&s3.client, bucket.as_str(), Some("prod/")).unwrap();
+        assert!(objects.is_empty());
+        assert!(dumps
+            .iter()
+            .any(|dump| dump.location().starts_with("prod.new-")));
+
+        assert!(s3.delete_by_name("prod".to_string()).is_ok());
+        assert_eq!(
+            read_dump(&s3, "prod.v2"),
+            b"INSERT INTO customers VALUES (2);".to_vec()
+        );
+
+        assert!(delete_bucket(&s3.client, bucket.as_str(), true).is_ok());
+    }
+
+    #[test]
+    fn test_s3_recompress_dump() {
+        let bucket = aws_bucket();
+        let mut s3 = aws_s3(bucket.as_str());
+        let _ = s3.init().expect("s3 init failed");
+
+        let data = b"INSERT INTO customers VALUES (1);".to_vec();
+        let checksum = format!("{:x}", Sha256::digest(&data));
+        s3.set_dump_name("prod".to_string());
+        assert!(s3.write(1, data.clone()).is_ok());
+        assert!(s3.commit(data.len(), Some(checksum)).is_ok());
+
+        let mut s3 = aws_s3(bucket.as_str());
+        assert!(s3.recompress("prod", false).is_ok());
+
+        let dumps = s3.index_file().unwrap().dumps;
+        assert_eq!(dumps.len(), 1);
+        assert_eq!(dumps[0].directory_name, "prod");
+        assert!(!dumps[0].compressed);
+        assert!(s3.verify("prod").is_ok());
+        assert_eq!(read_dump(&s3, "prod"), data);
+
+        assert!(delete_bucket(&s3.client, bucket.as_str(), true).is_ok());
+    }
+
     #[test]
     fn test_s3_dump_delete_older_than() {
         let bucket = aws_bucket();
@@ -964,19 +1270,27 @@ mod tests {
         // Add a dump from 5 days ago
         index_file.dumps.push(Dump {
             directory_name: "dump-1".to_string(),
+            location: None,
             size: 0,
             created_at: (Utc::now() - Duration::days(5)).timestamp_millis() as u128,
+            started_at: None,
             compressed: true,
             encrypted: false,
+            checksum: None,
+            tables: None,
         });
 
         // Add a dump from now
         index_file.dumps.push(Dump {
             directory_name: "dump-2".to_string(),
+            location: None,
             size: 0,
             created_at: epoch_millis(),
+            started_at: None,
             compressed: true,
             encrypted: false,
+            checksum: None,
+            tables: None,
         });
 
         assert!(s3.write_index_file(&index_file).is_ok());
@@ -1003,6 +1317,7 @@ mod tests {
                 dump: None,
                 older_than: Some("6d".to_string()),
                 keep_last: None,
+                yes: true,
             })
             .is_ok());
 
@@ -1015,6 +1330,7 @@ mod tests {
                 dump: None,
                 older_than: Some("5d".to_string()),
                 keep_last: None,
+                yes: true,
             })
             .is_ok());
 
@@ -1038,26 +1354,38 @@ mod tests {
 
         index_file.dumps.push(Dump {
             directory_name: "dump-1".to_string(),
+            location: None,
             size: 0,
             created_at: (Utc::now() - Duration::days(3)).timestamp_millis() as u128,
+            started_at: None,
             compressed: true,
             encrypted: false,
+            checksum: None,
+            tables: None,
         });
 
         index_file.dumps.push(Dump {
             directory_name: "dump-2".to_string(),
+            location: None,
             size: 0,
             created_at: (Utc::now() - Duration::days(5)).timestamp_millis() as u128,
+            started_at: None,
             compressed: true,
             encrypted: false,
+            checksum: None,
+            tables: None,
         });
 
         index_file.dumps.push(Dump {
             directory_name: "dump-3".to_string(),
+            location: None,
             size: 0,
             created_at: epoch_millis(),
+            started_at: None,
             compressed: true,
             encrypted: false,
+            checksum: None,
+            tables: None,
         });
 
         assert!(s3.write_index_file(&index_file).is_ok());
@@ -1092,6 +1420,7 @@ mod tests {
                 dump: None,
                 older_than: None,
                 keep_last: Some(2),
+                yes: true,
             })
             .is_ok());
 
@@ -1105,6 +1434,7 @@ mod tests {
                 dump: None,
                 older_than: None,
                 keep_last: Some(1),
+                yes: true,
             })
             .is_ok());
 
@@ -1172,20 +1502,28 @@ mod tests {
             s3.index_file().unwrap().dumps.get(0),
             Some(&Dump {
                 directory_name: "dump-1653170039392".to_string(),
+                location: None,
                 size: 62279,
                 created_at: 1234,
+                started_at: None,
                 compressed: true,
-                encrypted: false
+                encrypted: false,
+                checksum: None,
+                tables: None
             })
         );
         assert_eq!(
             s3.index_file().unwrap().dumps.get(1),
             Some(&Dump {
                 directory_name: "dump-1653170570014".to_string(),
+                location: None,
                 size: 62283,
                 created_at: 5678,
+                started_at: None,
                 compressed: true,
-                encrypted: false
+                encrypted: false,
+                checksum: None,
+                tables: None
             })
         );
     }
@@ -1,7 +1,7 @@
 #[macro_use]
 extern crate prettytable;
 
-use std::fs::File;
+use std::path::Path;
 use std::sync::mpsc;
 use std::sync::mpsc::Receiver;
 use std::thread::sleep;
@@ -13,15 +13,17 @@ use indicatif::{ProgressBar, ProgressStyle};
 use migration::{migrations, Migrator};
 use utils::get_replibyte_version;
 
-use crate::cli::{DumpCommand, RestoreCommand, SubCommand, TransformerCommand, CLI};
-use crate::config::{Config, DatabaseSubsetConfig, DatastoreConfig};
+use crate::cli::{ConfigCommand, DumpCommand, RestoreCommand, SubCommand, TransformerCommand, CLI};
+use crate::config::{load_env_file, Config, DatabaseSubsetConfig, DatastoreConfig};
 use crate::datastore::local_disk::LocalDisk;
+use crate::datastore::multi::MultiDatastore;
 use crate::datastore::s3::S3;
 use crate::datastore::Datastore;
 use crate::source::{Source, SourceOptions};
 use crate::tasks::{MaxBytes, TransferredBytes};
 use crate::telemetry::{ClientOptions, TelemetryClient, TELEMETRY_TOKEN};
-use crate::utils::epoch_millis;
+use crate::transformer::plugin::load_plugins;
+use crate::utils::{epoch_millis, redact_connection_uris};
 
 mod cli;
 mod commands;
@@ -32,9 +34,11 @@ mod destination;
 mod migration;
 mod runtime;
 mod source;
+mod ssh_tunnel;
 mod tasks;
 mod telemetry;
 mod transformer;
+mod translation;
 mod types;
 mod utils;
 
@@ -83,11 +87,27 @@ fn main() {
     let env_args = env::args().collect::<Vec<String>>();
     let args = CLI::parse();
 
-    let file = File::open(args.config).expect("missing config file");
-    let config: Config = serde_yaml::from_reader(file).expect("bad config file format");
+    if let Some(env_file) = &args.env_file {
+        load_env_file(env_file).expect("bad env file");
+    }
 
     let sub_commands: &SubCommand = &args.sub_commands;
 
+    if let SubCommand::Dump(DumpCommand::Create(create_args)) = sub_commands {
+        if create_args.input && args.config == Path::new("-") {
+            eprintln!("the configuration and the dump can't both be read from stdin");
+            std::process::exit(1);
+        }
+    }
+
+    let config = match Config::load(&args.config) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("{}", redact_connection_uris(err.to_string().as_str()));
+            std::process::exit(1);
+        }
+    };
+
     let telemetry_client = match args.no_telemetry {
         true => None,
         false => Some(TelemetryClient::new(ClientOptions::from(TELEMETRY_TOKEN))),
@@ -101,7 +121,7 @@ fn main() {
 
     let mut exit_code = 0;
     if let Err(err) = run(config, &sub_commands) {
-        eprintln!("{}", err);
+        eprintln!("{}", redact_connection_uris(err.to_string().as_str()));
         exit_code = 1;
     }
 
@@ -119,26 +139,53 @@ fn main() {
 }
 
 fn run(config: Config, sub_commands: &SubCommand) -> anyhow::Result<()> {
-    let mut datastore: Box<dyn Datastore> = match &config.datastore {
-        DatastoreConfig::AWS(config) => Box::new(S3::aws(
-            config.bucket()?,
-            config.region()?,
-            config.profile()?,
-            config.credentials()?,
-            config.endpoint()?,
-        )?),
-        DatastoreConfig::GCP(config) => Box::new(S3::gcp(
-            config.bucket()?,
-            config.region()?,
-            config.access_key()?,
-            config.secret()?,
-            config.endpoint()?,
-        )?),
-        DatastoreConfig::LocalDisk(config) => Box::new(LocalDisk::new(config.dir()?)),
+    // the transformers of the plugins are created with the source configuration
+    if let Some(plugins) = config.plugins()? {
+        load_plugins(&plugins)?;
+    }
+
+    // checking the configuration must not depend on the datastore being reachable
+    if let SubCommand::Config(ConfigCommand::Check) = sub_commands {
+        return commands::config::check(&config);
+    }
+
+    let mut datastores = config
+        .datastore
+        .datastores()
+        .iter()
+        .map(datastore)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    for datastore in datastores.iter() {
+        let migrator = Migrator::new(get_replibyte_version(), datastore, migrations());
+        let _ = migrator.migrate()?;
+    }
+
+    // a dump is restored from a single datastore, the other commands apply to all of them
+    let restore_datastore = match sub_commands {
+        SubCommand::Dump(DumpCommand::Restore(RestoreCommand::Local(args))) => &args.datastore,
+        SubCommand::Dump(DumpCommand::Restore(RestoreCommand::Remote(args))) => &args.datastore,
+        _ => &None,
     };
 
-    let migrator = Migrator::new(get_replibyte_version(), &datastore, migrations());
-    let _ = migrator.migrate()?;
+    let mut datastore: Box<dyn Datastore> = match restore_datastore {
+        Some(name) => match config
+            .datastore
+            .datastores()
+            .iter()
+            .position(|config| config.name() == name.as_str())
+        {
+            Some(index) => datastores.swap_remove(index),
+            None => {
+                return Err(anyhow::Error::from(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    format!("no '{}' datastore in the configuration", name),
+                )))
+            }
+        },
+        None if datastores.len() == 1 => datastores.remove(0),
+        None => Box::new(MultiDatastore::new(datastores)),
+    };
 
     let _ = datastore.init()?;
 
@@ -171,13 +218,13 @@ fn run(config: Config, sub_commands: &SubCommand) -> anyhow::Result<()> {
                 Ok(())
             }
             DumpCommand::Create(args) => {
-                if let Some(name) = &args.name {
-                    datastore.set_dump_name(name.to_string());
-                }
-
                 commands::dump::run(args, datastore, config, progress_callback)
             }
             DumpCommand::Delete(args) => commands::dump::delete(datastore, args),
+            DumpCommand::ExportIndex(args) => commands::dump::export_index(datastore, args),
+            DumpCommand::ImportIndex(args) => commands::dump::import_index(datastore, args),
+            DumpCommand::Verify(args) => commands::dump::verify(datastore, args),
+            DumpCommand::Recompress(args) => commands::dump::recompress(datastore, config, args),
             DumpCommand::Restore(restore_cmd) => match restore_cmd {
                 RestoreCommand::Local(args) => {
                     commands::dump::restore_local(args, datastore, config, progress_callback)
@@ -193,5 +240,59 @@ fn run(config: Config, sub_commands: &SubCommand) -> anyhow::Result<()> {
                 Ok(())
             }
         },
+        SubCommand::Config(cmd) => match cmd {
+            ConfigCommand::Check => commands::config::check(&config),
+        },
     }
 }
+
+/// create the datastore described by the configuration
+fn datastore(config: &DatastoreConfig) -> anyhow::Result<Box<dyn Datastore>> {
+    let datastore: Box<dyn Datastore> = match config {
+        DatastoreConfig::AWS(config) => {
+            let mut s3 = S3::aws(
+                config.bucket()?,
+                config.region()?,
+                config.profile()?,
+                config.credentials()?,
+                config.endpoint()?,
+            )?;
+
+            if let Some(prefix) = config.prefix()? {
+                s3.set_prefix(prefix);
+            }
+
+            if let Some(index_name) = config.index_name()? {
+                s3.set_index_file_name(index_name);
+            }
+
+            Box::new(s3)
+        }
+        DatastoreConfig::GCP(config) => {
+            let mut s3 = S3::gcp(
+                config.bucket()?,
+                config.region()?,
+                config.access_key()?,
+                config.secret()?,
+                config.endpoint()?,
+            )?;
+
+            if let Some(index_name) = config.index_name()? {
+                s3.set_index_file_name(index_name);
+            }
+
+            Box::new(s3)
+        }
+        DatastoreConfig::LocalDisk(config) => {
+            let mut local_disk = LocalDisk::new(config.dir()?);
+
+            if let Some(index_name) = config.index_name()? {
+                local_disk.set_index_file_name(index_name);
+            }
+
+            Box::new(local_disk)
+        }
+    };
+
+    Ok(datastore)
+}
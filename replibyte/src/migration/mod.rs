@@ -120,7 +120,7 @@ mod tests {
     use serde_json::json;
 
     use crate::connector::Connector;
-    use crate::datastore::{Datastore, IndexFile, ReadOptions};
+    use crate::datastore::{Datastore, DumpTable, IndexFile, ReadOptions};
 
     use super::{Migration, Migrator, Version};
 
@@ -167,7 +167,11 @@ mod tests {
             unimplemented!()
         }
 
-        fn write(&self, _file_part: u16, _data: crate::types::Bytes) -> Result<(), Error> {
+        fn write(&self, _file_part: u16, _data: crate::types::Bytes) -> Result<usize, Error> {
+            unimplemented!()
+        }
+
+        fn commit(&self, _size: usize, _checksum: Option<String>) -> Result<(), Error> {
             unimplemented!()
         }
 
@@ -199,6 +203,26 @@ mod tests {
             unimplemented!()
         }
 
+        fn set_dump_tables(&mut self, _tables: Vec<DumpTable>) {
+            unimplemented!()
+        }
+
+        fn set_dump_started_at(&mut self, _started_at: u128) {
+            unimplemented!()
+        }
+
+        fn set_replaced_dump(&mut self, _name: String) {
+            unimplemented!()
+        }
+
+        fn set_temporary_dump(&mut self) {
+            unimplemented!()
+        }
+
+        fn discard(&self) -> Result<(), Error> {
+            unimplemented!()
+        }
+
         fn delete_by_name(&self, _name: String) -> Result<(), Error> {
             unimplemented!()
         }
@@ -1,8 +1,13 @@
-use std::io::Error;
+use std::io::{BufRead, BufReader, Error, ErrorKind, Read};
+use std::path::Path;
 
-use crate::config::{DatabaseSubsetConfig, OnlyTablesConfig, SkipConfig};
+use flate2::read::MultiGzDecoder;
+
+use crate::config::{
+    AmplifyConfig, DatabaseSubsetConfig, OnlyTablesConfig, SchemaOnlyTablesConfig, SkipConfig,
+};
 use crate::connector::Connector;
-use crate::transformer::Transformer;
+use crate::transformer::{OnTransformError, Transformer};
 use crate::types::{OriginalQuery, Query};
 
 pub mod mongodb;
@@ -12,6 +17,9 @@ pub mod mysql_stdin;
 pub mod postgres;
 pub mod postgres_stdin;
 
+const GZIP_MAGIC_BYTES: [u8; 2] = [0x1f, 0x8b];
+const ZSTD_MAGIC_BYTES: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
 pub trait Source: Connector {
     fn read<F: FnMut(OriginalQuery, Query)>(
         &self,
@@ -25,4 +33,109 @@ pub struct SourceOptions<'a> {
     pub skip_config: &'a Vec<SkipConfig>,
     pub database_subset: &'a Option<DatabaseSubsetConfig>,
     pub only_tables: &'a Vec<OnlyTablesConfig>,
+    pub schema_only_tables: &'a Vec<SchemaOnlyTablesConfig>,
+    pub max_table_size: Option<u64>,
+    pub tmp_dir: Option<&'a Path>,
+    pub detect_pii_comments: bool,
+    /// append the current values of the dumped sequences (PostgreSQL only)
+    pub capture_sequences: bool,
+    /// what to do with a value a transformer can't handle - transformed anyway by default (PostgreSQL and MySQL only)
+    pub on_transform_error: Option<OnTransformError>,
+    pub amplify: &'a Vec<AmplifyConfig>,
+    /// seed of the transformers - the values picked by the source (e.g. enum labels) are derived from it too
+    pub seed: Option<u64>,
+}
+
+/// wrap a dump reader (e.g. STDIN) and transparently decompress it if it starts with the gzip magic bytes
+pub fn decompress_reader<'a, R: Read + 'a>(
+    reader: R,
+) -> Result<BufReader<Box<dyn Read + 'a>>, Error> {
+    let mut reader = BufReader::new(reader);
+    let header = reader.fill_buf()?;
+
+    if header.starts_with(&GZIP_MAGIC_BYTES) {
+        return Ok(BufReader::new(Box::new(MultiGzDecoder::new(reader))));
+    }
+
+    if header.starts_with(&ZSTD_MAGIC_BYTES) {
+        return Err(Error::new(
+            ErrorKind::Other,
+            "zstd compressed dumps are not supported - decompress it first (e.g. with 'zstd -dc')",
+        ));
+    }
+
+    Ok(BufReader::new(Box::new(reader)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    use crate::source::postgres::read_and_transform;
+    use crate::source::{decompress_reader, SourceOptions};
+    use crate::transformer::random::RandomTransformer;
+    use crate::transformer::Transformer;
+
+    const DUMP: &str = r#"
+INSERT INTO public.employees (employee_id, first_name, last_name) VALUES (1, 'Nancy', 'Davolio');
+"#;
+
+    fn read_dump(reader: Cursor<Vec<u8>>) -> Vec<String> {
+        let t1: Box<dyn Transformer> =
+            Box::new(RandomTransformer::new("public", "employees", "last_name"));
+        let transformers = vec![t1];
+
+        let source_options = SourceOptions {
+            transformers: &transformers,
+            skip_config: &vec![],
+            database_subset: &None,
+            only_tables: &vec![],
+            schema_only_tables: &vec![],
+            max_table_size: None,
+            tmp_dir: None,
+            detect_pii_comments: false,
+            capture_sequences: false,
+            on_transform_error: None,
+            amplify: &vec![],
+            seed: None,
+        };
+
+        let mut rows = vec![];
+        read_and_transform(
+            decompress_reader(reader).unwrap(),
+            source_options,
+            |_original_query, query| {
+                rows.push(String::from_utf8_lossy(query.data().as_slice()).to_string());
+            },
+        )
+        .unwrap();
+
+        rows
+    }
+
+    #[test]
+    fn read_gzip_compressed_dump() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(DUMP.as_bytes()).unwrap();
+        let compressed_dump = encoder.finish().unwrap();
+
+        for rows in [
+            read_dump(Cursor::new(compressed_dump)),
+            read_dump(Cursor::new(DUMP.as_bytes().to_vec())),
+        ] {
+            let insert_into = rows.iter().find(|x| x.starts_with("INSERT INTO")).unwrap();
+
+            assert!(insert_into.contains("'Nancy'"));
+            assert!(!insert_into.contains("'Davolio'"));
+        }
+    }
+
+    #[test]
+    fn reject_zstd_compressed_dump() {
+        let dump = vec![0x28, 0xb5, 0x2f, 0xfd, 0x00, 0x00];
+        assert!(decompress_reader(Cursor::new(dump)).is_err());
+    }
 }
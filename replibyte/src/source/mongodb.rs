@@ -92,6 +92,34 @@ fn check_connection_status(db: &MongoDB) -> Result<(), Error> {
     Ok(())
 }
 
+/// BSON value of a transformed column - a transformer can change the type of the column
+fn column_to_bson(column: Column) -> Bson {
+    if column.is_null() {
+        return Bson::Null;
+    }
+
+    if let Some(value) = column.number_value() {
+        return Bson::Int64(*value as i64);
+    }
+
+    if let Some(value) = column.float_value() {
+        return Bson::Double(*value);
+    }
+
+    if let Some(value) = column.bool_value() {
+        return Bson::Boolean(*value);
+    }
+
+    if let Some(value) = column.char_value() {
+        return Bson::String(value.to_string());
+    }
+
+    match column {
+        Column::StringValue(_, value) => Bson::String(value),
+        _ => Bson::Null,
+    }
+}
+
 pub fn recursively_transform_bson(
     key: String,
     bson: Bson,
@@ -106,7 +134,7 @@ pub fn recursively_transform_bson(
                 Some(transformer) => transformer.transform(column), // apply transformation on the column
                 None => column,
             };
-            Bson::String((*column.string_value().unwrap()).to_string())
+            column_to_bson(column)
         }
         Bson::Double(value) => {
             column = Column::FloatNumberValue(key.clone(), value);
@@ -114,7 +142,7 @@ pub fn recursively_transform_bson(
                 Some(transformer) => transformer.transform(column), // apply transformation on the column
                 None => column,
             };
-            Bson::Double(*column.float_number_value().unwrap())
+            column_to_bson(column)
         }
         Bson::Array(arr) => {
             let new_arr = arr
@@ -149,7 +177,10 @@ pub fn recursively_transform_bson(
                 Some(transformer) => transformer.transform(column), // apply transformation on the column
                 None => column,
             };
-            Bson::Int32(column.number_value().map(|&n| n as i32).unwrap())
+            match column.number_value() {
+                Some(value) => Bson::Int32(*value as i32),
+                None => column_to_bson(column),
+            }
         }
         Bson::Int64(value) => {
             column = Column::NumberValue(key.clone(), value as i128);
@@ -157,7 +188,7 @@ pub fn recursively_transform_bson(
                 Some(transformer) => transformer.transform(column), // apply transformation on the column
                 None => column,
             };
-            Bson::Int64(column.number_value().map(|&n| n as i64).unwrap())
+            column_to_bson(column)
         }
         // ALL OF THE NEXT TYPES ARE NOT TRANSFORMABLE (yet?)
         Bson::ObjectId(oid) => Bson::ObjectId(oid),
@@ -276,9 +307,10 @@ mod tests {
     use std::collections::{HashMap, HashSet};
     use std::vec;
 
-    use crate::source::mongodb::{find_all_keys_with_array_wildcard_op, MongoDB};
+    use crate::source::mongodb::{column_to_bson, find_all_keys_with_array_wildcard_op, MongoDB};
     use crate::transformer::transient::TransientTransformer;
     use crate::transformer::Transformer;
+    use crate::types::Column;
 
     use super::recursively_transform_document;
 
@@ -307,6 +339,14 @@ mod tests {
             skip_config: &vec![],
             database_subset: &None,
             only_tables: &vec![],
+            schema_only_tables: &vec![],
+            max_table_size: None,
+            tmp_dir: None,
+            detect_pii_comments: false,
+            capture_sequences: false,
+            on_transform_error: None,
+            amplify: &vec![],
+            seed: None,
         };
 
         assert!(p.read(source_options, |_, _| {}).is_ok());
@@ -319,6 +359,14 @@ mod tests {
             skip_config: &vec![],
             database_subset: &None,
             only_tables: &vec![],
+            schema_only_tables: &vec![],
+            max_table_size: None,
+            tmp_dir: None,
+            detect_pii_comments: false,
+            capture_sequences: false,
+            on_transform_error: None,
+            amplify: &vec![],
+            seed: None,
         };
 
         assert!(p.read(source_options, |_, _| {}).is_err());
@@ -334,6 +382,14 @@ mod tests {
             skip_config: &vec![],
             database_subset: &None,
             only_tables: &vec![],
+            schema_only_tables: &vec![],
+            max_table_size: None,
+            tmp_dir: None,
+            detect_pii_comments: false,
+            capture_sequences: false,
+            on_transform_error: None,
+            amplify: &vec![],
+            seed: None,
         };
 
         p.read(source_options, |original_query, query| {
@@ -343,6 +399,33 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn transformed_column_to_bson() {
+        let name = || "field".to_string();
+
+        assert_eq!(
+            column_to_bson(Column::NumberValue(name(), 42)),
+            Bson::Int64(42)
+        );
+        assert_eq!(
+            column_to_bson(Column::FloatNumberValue(name(), 9.99)),
+            Bson::Double(9.99)
+        );
+        assert_eq!(
+            column_to_bson(Column::StringValue(name(), "Nancy".to_string())),
+            Bson::String("Nancy".to_string())
+        );
+        assert_eq!(
+            column_to_bson(Column::CharValue(name(), 'A')),
+            Bson::String("A".to_string())
+        );
+        assert_eq!(
+            column_to_bson(Column::BooleanValue(name(), true)),
+            Bson::Boolean(true)
+        );
+        assert_eq!(column_to_bson(Column::None(name())), Bson::Null);
+    }
+
     #[test]
     fn recursive_document_transform() {
         let database_name = "test";
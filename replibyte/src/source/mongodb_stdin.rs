@@ -1,6 +1,7 @@
-use std::io::{stdin, BufReader, Error};
+use std::io::{stdin, Error};
 
 use crate::connector::Connector;
+use crate::source::decompress_reader;
 use crate::source::mongodb::read_and_transform;
 use crate::types::{OriginalQuery, Query};
 use crate::Source;
@@ -26,7 +27,7 @@ impl Source for MongoDBStdin {
         options: SourceOptions,
         query_callback: F,
     ) -> Result<(), Error> {
-        let reader = BufReader::new(stdin());
+        let reader = decompress_reader(stdin())?;
 
         if let Some(_database_subset) = &options.database_subset {
             todo!("database subset not supported yet for MongoDB source")
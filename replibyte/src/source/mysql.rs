@@ -1,5 +1,5 @@
 use std::borrow::BorrowMut;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io::{BufReader, Error, ErrorKind, Read};
 use std::process::{Command, Stdio};
 
@@ -13,7 +13,7 @@ use dump_parser::utils::{list_sql_queries_from_dump_reader, ListQueryResult};
 
 use crate::connector::Connector;
 use crate::source::Source;
-use crate::transformer::Transformer;
+use crate::transformer::{transform_column, transform_row_columns, OnTransformError, Transformer};
 use crate::types::{Column, InsertIntoQuery, OriginalQuery, Query};
 use crate::utils::{binary_exists, wait_for_command};
 
@@ -116,7 +116,7 @@ impl<'a> Source for Mysql<'a> {
 
         let reader = BufReader::new(stdout);
 
-        read_and_transform(reader, options, query_callback);
+        read_and_transform(reader, options, query_callback)?;
 
         wait_for_command(&mut process)
     }
@@ -126,7 +126,7 @@ pub fn read_and_transform<R: Read, F: FnMut(OriginalQuery, Query)>(
     reader: BufReader<R>,
     options: SourceOptions,
     mut query_callback: F,
-) {
+) -> Result<(), Error> {
     // create a map variable with Transformer by column_name
     let mut transformer_by_db_and_table_and_column_name: HashMap<String, &Box<dyn Transformer>> =
         HashMap::with_capacity(options.transformers.len());
@@ -136,16 +136,35 @@ pub fn read_and_transform<R: Read, F: FnMut(OriginalQuery, Query)>(
             .insert(transformer.table_and_column_name(), transformer);
     }
 
+    // the schema of these tables is kept, only their rows are skipped
+    let schema_only_tables = options
+        .schema_only_tables
+        .iter()
+        .map(|cfg| cfg.table.as_str())
+        .collect::<HashSet<_>>();
+
+    // error aborting the dump, the rest of it is not read
+    let mut transform_error = None;
+
     match list_sql_queries_from_dump_reader(reader, |query| {
         let tokens = get_tokens_from_query_str(query);
 
         match get_row_type(&tokens) {
+            RowType::InsertInto { table_name }
+                if schema_only_tables.contains(table_name.as_str()) => {}
             RowType::InsertInto { table_name } => {
-                let (original_columns, columns) = transform_columns(
+                let (original_columns, columns) = match transform_columns(
                     table_name.as_str(),
                     &tokens,
                     &transformer_by_db_and_table_and_column_name,
-                );
+                    options.on_transform_error,
+                ) {
+                    Ok(columns) => columns,
+                    Err(err) => {
+                        transform_error = Some(err);
+                        return ListQueryResult::Break;
+                    }
+                };
 
                 query_callback(
                     to_query(
@@ -178,6 +197,11 @@ pub fn read_and_transform<R: Read, F: FnMut(OriginalQuery, Query)>(
         Ok(_) => {}
         Err(err) => panic!("{:?}", err),
     }
+
+    match transform_error {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
 }
 
 fn no_change_query_callback<F: FnMut(OriginalQuery, Query)>(query_callback: &mut F, query: &str) {
@@ -192,7 +216,8 @@ fn transform_columns(
     table_name: &str,
     tokens: &Vec<Token>,
     transformer_by_db_and_table_and_column_name: &HashMap<String, &Box<dyn Transformer>>,
-) -> (Vec<Column>, Vec<Column>) {
+    on_transform_error: Option<OnTransformError>,
+) -> Result<(Vec<Column>, Vec<Column>), Error> {
     // find database name by filtering out all queries starting with
     // INSERT INTO `<table>` (...)
     // INSERT       -> position 0
@@ -205,7 +230,6 @@ fn transform_columns(
     assert_eq!(column_names.len(), column_values.len(), "Column names do not match values: got {} names and {} values", column_names.len(), column_values.len());
     
     let mut original_columns = vec![];
-    let mut columns = vec![];
 
     for (i, column_name) in column_names.iter().enumerate() {
         let value_token = column_values.get(i).unwrap();
@@ -246,22 +270,30 @@ fn transform_columns(
             _ => Column::None(column_name.to_string()),
         };
 
-        // get the right transformer for the right column name
-        let original_column = column.clone();
-
-        let table_and_column_name = format!("{}.{}", table_name, *column_name);
-
-        let column =
-            match transformer_by_db_and_table_and_column_name.get(table_and_column_name.as_str()) {
-                Some(transformer) => transformer.transform(column), // apply transformation on the column
-                None => column,
-            };
-
-        original_columns.push(original_column);
-        columns.push(column);
+        original_columns.push(column);
     }
 
-    (original_columns, columns)
+    // get the right transformer for the right column name
+    let transformers = original_columns
+        .iter()
+        .map(|column| {
+            let table_and_column_name = format!("{}.{}", table_name, column.name());
+            transformer_by_db_and_table_and_column_name
+                .get(table_and_column_name.as_str())
+                .map(|transformer| transformer.as_ref())
+        })
+        .collect::<Vec<_>>();
+
+    // apply transformation on the columns, with the original values of the row
+    let columns = transform_row_columns(
+        &original_columns,
+        &transformers,
+        |_, transformer, column, row| {
+            transform_column(transformer, column, row, on_transform_error)
+        },
+    )?;
+
+    Ok((original_columns, columns))
 }
 
 fn is_insert_into_statement(tokens: &Vec<Token>) -> bool {
@@ -380,6 +412,14 @@ mod tests {
             skip_config: &vec![],
             database_subset: &None,
             only_tables: &vec![],
+            schema_only_tables: &vec![],
+            max_table_size: None,
+            tmp_dir: None,
+            detect_pii_comments: false,
+            capture_sequences: false,
+            on_transform_error: None,
+            amplify: &vec![],
+            seed: None,
         };
 
         assert!(p.read(source_options, |_original_query, _query| {}).is_ok());
@@ -392,6 +432,14 @@ mod tests {
             skip_config: &vec![],
             database_subset: &None,
             only_tables: &vec![],
+            schema_only_tables: &vec![],
+            max_table_size: None,
+            tmp_dir: None,
+            detect_pii_comments: false,
+            capture_sequences: false,
+            on_transform_error: None,
+            amplify: &vec![],
+            seed: None,
         };
         assert!(p
             .read(source_options, |_original_query, _query| {})
@@ -408,6 +456,14 @@ mod tests {
             skip_config: &vec![],
             database_subset: &None,
             only_tables: &vec![],
+            schema_only_tables: &vec![],
+            max_table_size: None,
+            tmp_dir: None,
+            detect_pii_comments: false,
+            capture_sequences: false,
+            on_transform_error: None,
+            amplify: &vec![],
+            seed: None,
         };
         let _ = p.read(source_options, |original_query, query| {
             assert!(original_query.data().len() > 0);
@@ -1,6 +1,7 @@
-use std::io::{stdin, BufReader, Error};
+use std::io::{stdin, Error};
 
 use crate::connector::Connector;
+use crate::source::decompress_reader;
 use crate::source::mysql::read_and_transform;
 use crate::types::{OriginalQuery, Query};
 use crate::Source;
@@ -33,8 +34,8 @@ impl Source for MysqlStdin {
         options: SourceOptions,
         query_callback: F,
     ) -> Result<(), Error> {
-        let reader = BufReader::new(stdin());
-        read_and_transform(reader, options, query_callback);
+        let reader = decompress_reader(stdin())?;
+        read_and_transform(reader, options, query_callback)?;
 
         Ok(())
     }
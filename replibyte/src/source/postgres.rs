@@ -3,9 +3,11 @@ use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io;
 use std::io::{BufReader, Error, ErrorKind, Read, Write};
+use std::path::Path;
 use std::process::{Command, Stdio};
 
 use log::info;
+use rand::seq::SliceRandom;
 
 use dump_parser::postgres::Keyword::NoKeyword;
 use dump_parser::postgres::{
@@ -17,10 +19,15 @@ use dump_parser::utils::{list_sql_queries_from_dump_reader, ListQueryResult};
 use subset::postgres::{PostgresSubset, SubsetStrategy};
 use subset::{PassthroughTable, Subset, SubsetOptions};
 
-use crate::config::DatabaseSubsetConfigStrategy;
+use crate::config::{
+    AmplifyConfig, DatabaseSubsetConfigStrategy, OnlyTablesConfig, SchemaOnlyTablesConfig,
+    TransformerTypeConfig,
+};
 use crate::connector::Connector;
 use crate::source::Source;
-use crate::transformer::Transformer;
+use crate::transformer::number_distribution::NumberDistributionOptions;
+use crate::transformer::seeded::{rng, with_salt, with_seed};
+use crate::transformer::{transform_column, transform_row_columns, OnTransformError, Transformer};
 use crate::types::{Column, InsertIntoQuery, OriginalQuery, Query};
 use crate::utils::{binary_exists, wait_for_command};
 use crate::DatabaseSubsetConfig;
@@ -40,15 +47,68 @@ enum RowType {
         database_name: String,
         table_name: String,
     },
+    AttachPartition {
+        database_name: String,
+        table_name: String,
+        partition_database_name: String,
+        partition_table_name: String,
+    },
+    CommentOnColumn {
+        database_name: String,
+        table_name: String,
+        column_name: String,
+        comment: String,
+    },
+    CreateEnumType {
+        database_name: String,
+        type_name: String,
+        labels: Vec<String>,
+    },
     Others,
 }
 
+const TABLE_SIZES_QUERY: &str = "SELECT schemaname, relname, pg_total_relation_size(relid) \
+FROM pg_catalog.pg_statio_user_tables";
+
+const SEQUENCES_QUERY: &str =
+    "SELECT schemaname, sequencename, coalesce(last_value, start_value), \
+last_value IS NOT NULL FROM pg_catalog.pg_sequences";
+
+/// current value of a sequence of the source
+#[derive(Debug, PartialEq)]
+pub struct Sequence {
+    pub database: String,
+    pub name: String,
+    pub last_value: i64,
+    /// false if the sequence has never been used - the next value is `last_value` then
+    pub is_called: bool,
+}
+
+impl Sequence {
+    /// the `setval` query restoring the sequence to its current value
+    fn setval_query(&self) -> String {
+        let name = format!(
+            "{}.{}",
+            quote_identifier(self.database.as_str()),
+            quote_identifier(self.name.as_str())
+        );
+
+        format!(
+            "SELECT pg_catalog.setval('{}', {}, {});",
+            name.replace('\'', "''"),
+            self.last_value,
+            self.is_called
+        )
+    }
+}
+
 pub struct Postgres<'a> {
     host: &'a str,
     port: u16,
     database: &'a str,
     username: &'a str,
     password: &'a str,
+    ssl_mode: Option<&'a str>,
 }
 
 impl<'a> Postgres<'a> {
@@ -65,8 +125,26 @@ impl<'a> Postgres<'a> {
             database,
             username,
             password,
+            ssl_mode: None,
         }
     }
+
+    /// `sslmode` of pg_dump and psql (e.g. `require` for a managed database only accepting SSL connections)
+    pub fn set_ssl_mode(&mut self, ssl_mode: &'a str) {
+        self.ssl_mode = Some(ssl_mode);
+    }
+
+    /// pg_dump or psql command with the password and the SSL mode of the connection
+    fn command(&self, program: &str) -> Command {
+        let mut command = Command::new(program);
+        command.env("PGPASSWORD", self.password);
+
+        if let Some(ssl_mode) = self.ssl_mode {
+            command.env("PGSSLMODE", ssl_mode);
+        }
+
+        command
+    }
 }
 
 impl<'a> Connector for Postgres<'a> {
@@ -79,7 +157,7 @@ impl<'a> Source for Postgres<'a> {
     fn read<F: FnMut(OriginalQuery, Query)>(
         &self,
         options: SourceOptions,
-        query_callback: F,
+        mut query_callback: F,
     ) -> Result<(), Error> {
         let s_port = self.port.to_string();
 
@@ -94,11 +172,7 @@ impl<'a> Source for Postgres<'a> {
             self.username,
         ];
 
-        let only_tables_args: Vec<String> = options
-            .only_tables
-            .iter()
-            .map(|cfg| format!("--table={}.{}", cfg.database, cfg.table))
-            .collect();
+        let only_tables_args = get_only_tables_args(options.only_tables);
         let mut only_tables_args: Vec<&str> = only_tables_args
             .iter()
             .map(String::as_str)
@@ -106,11 +180,41 @@ impl<'a> Source for Postgres<'a> {
 
         dump_args.append(&mut only_tables_args);
 
+        let schema_only_tables_args = get_schema_only_tables_args(options.schema_only_tables);
+        let mut schema_only_tables_args: Vec<&str> =
+            schema_only_tables_args.iter().map(String::as_str).collect();
+
+        dump_args.append(&mut schema_only_tables_args);
+
+        let oversized_tables = match options.max_table_size {
+            Some(max_table_size) => {
+                get_oversized_tables(&self.table_sizes()?, max_table_size, options.only_tables)
+            }
+            None => vec![],
+        };
+
+        for table in &oversized_tables {
+            info!(
+                "skipping table {} bigger than the max table size ({} bytes)",
+                table,
+                options.max_table_size.unwrap_or_default()
+            );
+        }
+
+        let oversized_tables_args = oversized_tables
+            .iter()
+            .map(|table| format!("--exclude-table={}", table))
+            .collect::<Vec<_>>();
+        let mut oversized_tables_args: Vec<&str> =
+            oversized_tables_args.iter().map(String::as_str).collect();
+
+        dump_args.append(&mut oversized_tables_args);
+
         dump_args.push(self.database);
 
         // TODO: as for mysql we can exclude tables directly here so we can remove the skip_tables_map checks
-        let mut process = Command::new("pg_dump")
-            .env("PGPASSWORD", self.password)
+        let mut process = self
+            .command("pg_dump")
             .args(dump_args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -121,36 +225,323 @@ impl<'a> Source for Postgres<'a> {
             .take()
             .ok_or_else(|| Error::new(ErrorKind::Other, "Could not capture standard output."))?;
 
+        let capture_sequences = options.capture_sequences;
+
+        // the sequences of the other tables are not in the dump, they can't be set on restore - and pg_dump
+        // already sets the ones whose data is dumped
+        let mut dumped_sequences = HashSet::new();
+        let mut set_sequences = HashSet::new();
+        let dump_callback = |original_query: OriginalQuery, query: Query| {
+            if let Some(sequence) = create_sequence_name(query.data()) {
+                let _ = dumped_sequences.insert(sequence);
+            }
+
+            if let Some(sequence) = setval_sequence_name(query.data()) {
+                let _ = set_sequences.insert(sequence);
+            }
+
+            query_callback(original_query, query)
+        };
+
         match &options.database_subset {
             None => {
                 let reader = BufReader::new(stdout);
-                read_and_transform(reader, options, query_callback);
+                read_and_transform(reader, options, dump_callback)?;
             }
             Some(subset_config) => {
                 let dump_reader = BufReader::new(stdout);
-                let reader = subset(dump_reader, subset_config)?;
-                read_and_transform(reader, options, query_callback);
+                let reader = subset(dump_reader, subset_config, options.tmp_dir)?;
+                read_and_transform(reader, options, dump_callback)?;
             }
         };
 
-        wait_for_command(&mut process)
+        wait_for_command(&mut process)?;
+
+        // the values are captured once the rows are dumped, so they are never behind the ids of the rows
+        if capture_sequences {
+            for query in setval_queries(&self.sequences()?, &dumped_sequences, &set_sequences) {
+                query_callback(query.clone(), query);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> Postgres<'a> {
+    /// min, max, mean and standard deviation of a numeric column
+    pub fn column_stats(
+        &self,
+        database: &str,
+        table: &str,
+        column: &str,
+    ) -> Result<NumberDistributionOptions, Error> {
+        let query = column_stats_query(database, table, column);
+        let output = self.query(query.as_str())?;
+
+        parse_column_stats(output.as_str()).ok_or_else(|| {
+            Error::new(
+                ErrorKind::Other,
+                format!(
+                    "cannot read the statistics of column {}.{}.{}: {}",
+                    database, table, column, output
+                ),
+            )
+        })
+    }
+
+    /// current value of each sequence of the database
+    pub fn sequences(&self) -> Result<Vec<Sequence>, Error> {
+        Ok(parse_sequences(self.query(SEQUENCES_QUERY)?.as_str()))
+    }
+
+    /// size in bytes of each table of the database, TOAST and indexes included
+    fn table_sizes(&self) -> Result<Vec<(String, String, u64)>, Error> {
+        Ok(parse_table_sizes(self.query(TABLE_SIZES_QUERY)?.as_str()))
+    }
+
+    /// run a query with psql and return its rows, one per line with `,` separated fields
+    fn query(&self, query: &str) -> Result<String, Error> {
+        binary_exists("psql")?;
+
+        let s_port = self.port.to_string();
+        let output = self
+            .command("psql")
+            .args([
+                "-h",
+                self.host,
+                "-p",
+                s_port.as_str(),
+                "-d",
+                self.database,
+                "-U",
+                self.username,
+                "--no-align",
+                "--tuples-only",
+                "--field-separator=,",
+                "-c",
+                query,
+            ])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!(
+                    "command error: {}",
+                    String::from_utf8_lossy(output.stderr.as_slice())
+                ),
+            ));
+        }
+
+        Ok(String::from_utf8_lossy(output.stdout.as_slice()).to_string())
+    }
+}
+
+fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+fn column_stats_query(database: &str, table: &str, column: &str) -> String {
+    let column = quote_identifier(column);
+
+    format!(
+        "SELECT min({c})::float8, max({c})::float8, avg({c})::float8, stddev_pop({c})::float8 FROM {}.{}",
+        quote_identifier(database),
+        quote_identifier(table),
+        c = column
+    )
+}
+
+/// parse the `<min>,<max>,<mean>,<stddev>` line of the column stats query - all empty for an empty table
+fn parse_column_stats(output: &str) -> Option<NumberDistributionOptions> {
+    let fields = output
+        .trim()
+        .split(',')
+        .map(|field| match field {
+            "" => Ok(0.0),
+            field => field.parse::<f64>(),
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .ok()?;
+
+    match fields[..] {
+        [min, max, mean, stddev] => Some(NumberDistributionOptions {
+            min: Some(min),
+            max: Some(max),
+            mean: Some(mean),
+            stddev: Some(stddev),
+        }),
+        _ => None,
+    }
+}
+
+/// parse the `<schema>,<table>,<size>` lines of the table sizes query
+fn parse_table_sizes(output: &str) -> Vec<(String, String, u64)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.trim().splitn(3, ',');
+            let database = fields.next()?;
+            let table = fields.next()?;
+            let size = fields.next()?.parse::<u64>().ok()?;
+
+            Some((database.to_string(), table.to_string(), size))
+        })
+        .collect()
+}
+
+/// parse the `<schema>,<sequence>,<last value>,<is called>` lines of the sequences query
+fn parse_sequences(output: &str) -> Vec<Sequence> {
+    output
+        .lines()
+        .filter_map(|line| {
+            // a sequence name can contain a comma, the numbers can't
+            let mut fields = line.trim().rsplitn(3, ',');
+            let is_called = fields.next()? == "t";
+            let last_value = fields.next()?.parse::<i64>().ok()?;
+            let (database, name) = fields.next()?.split_once(',')?;
+
+            Some(Sequence {
+                database: database.to_string(),
+                name: name.to_string(),
+                last_value,
+                is_called,
+            })
+        })
+        .collect()
+}
+
+/// unquoted `<database>.<sequence>` of a `CREATE SEQUENCE` query
+fn create_sequence_name(query: &[u8]) -> Option<String> {
+    let query = std::str::from_utf8(query).ok()?;
+    let name = query
+        .trim_start()
+        .strip_prefix("CREATE SEQUENCE ")?
+        .split_whitespace()
+        .next()?;
+
+    Some(name.trim_end_matches(';').replace('"', ""))
+}
+
+/// unquoted `<database>.<sequence>` of a `SELECT pg_catalog.setval(...)` query
+fn setval_sequence_name(query: &[u8]) -> Option<String> {
+    let query = std::str::from_utf8(query).ok()?;
+    // a sequence name can contain a comma, the value and the is called flag can't
+    let name = query
+        .trim_start()
+        .strip_prefix("SELECT pg_catalog.setval('")?
+        .rsplitn(3, ',')
+        .nth(2)?
+        .strip_suffix('\'')?;
+
+    Some(name.replace("''", "'").replace('"', ""))
+}
+
+/// `setval` queries of the sequences in the dump that pg_dump does not set (e.g. the ones of the tables whose data is
+/// excluded)
+fn setval_queries(
+    sequences: &[Sequence],
+    dumped_sequences: &HashSet<String>,
+    set_sequences: &HashSet<String>,
+) -> Vec<Query> {
+    sequences
+        .iter()
+        .filter(|sequence| {
+            let name = format!("{}.{}", sequence.database, sequence.name);
+            dumped_sequences.contains(&name) && !set_sequences.contains(&name)
+        })
+        .map(|sequence| Query(sequence.setval_query().into_bytes()))
+        .collect()
+}
+
+/// `<database>.<table>` of the tables bigger than `max_table_size` - the tables in `only_tables` are always dumped
+fn get_oversized_tables(
+    table_sizes: &[(String, String, u64)],
+    max_table_size: u64,
+    only_tables: &[OnlyTablesConfig],
+) -> Vec<String> {
+    table_sizes
+        .iter()
+        .filter(|(database, table, size)| {
+            *size > max_table_size
+                && !only_tables
+                    .iter()
+                    .any(|cfg| &cfg.database == database && &cfg.table == table)
+        })
+        .map(|(database, table, _)| format!("{}.{}", database, table))
+        .collect()
+}
+
+/// pg_dump arguments to dump only the configured tables (all tables if empty)
+fn get_only_tables_args(only_tables: &[OnlyTablesConfig]) -> Vec<String> {
+    only_tables
+        .iter()
+        .map(|cfg| format!("--table={}.{}", cfg.database, cfg.table))
+        .collect()
+}
+
+/// pg_dump arguments to dump the schema of the configured tables without their rows
+fn get_schema_only_tables_args(schema_only_tables: &[SchemaOnlyTablesConfig]) -> Vec<String> {
+    schema_only_tables
+        .iter()
+        .map(|cfg| format!("--exclude-table-data={}.{}", cfg.database, cfg.table))
+        .collect()
+}
+
+/// intermediate file in `tmp_dir`, or in the system temp directory - deleted when dropped
+fn new_named_temp_file(tmp_dir: Option<&Path>) -> Result<tempfile::NamedTempFile, Error> {
+    match tmp_dir {
+        Some(tmp_dir) => tempfile::NamedTempFile::new_in(tmp_dir),
+        None => tempfile::NamedTempFile::new(),
     }
 }
 
 pub fn subset<R: Read>(
     mut dump_reader: BufReader<R>,
     subset_config: &DatabaseSubsetConfig,
+    tmp_dir: Option<&Path>,
 ) -> Result<BufReader<File>, Error> {
-    let mut named_temp_file = tempfile::NamedTempFile::new()?;
+    let mut named_temp_file = new_named_temp_file(tmp_dir)?;
     let mut temp_dump_file = named_temp_file.as_file_mut();
     let _ = io::copy(&mut dump_reader, &mut temp_dump_file)?;
 
-    let strategy = match subset_config.strategy {
+    let strategy = match &subset_config.strategy {
         DatabaseSubsetConfigStrategy::Random(opt) => SubsetStrategy::RandomPercent {
             database: subset_config.database.as_str(),
             table: subset_config.table.as_str(),
             percent: opt.percent,
         },
+        DatabaseSubsetConfigStrategy::Stratified(opt) => SubsetStrategy::StratifiedPercent {
+            database: subset_config.database.as_str(),
+            table: subset_config.table.as_str(),
+            column: opt.column.as_str(),
+            percent: opt.percent,
+        },
+        DatabaseSubsetConfigStrategy::Limit(opt) => SubsetStrategy::Limit {
+            database: subset_config.database.as_str(),
+            table: subset_config.table.as_str(),
+            rows: opt.rows as usize,
+        },
+        DatabaseSubsetConfigStrategy::Incremental(opt) => {
+            match opt.condition() {
+                Some(condition) => info!(
+                    "Database subset of {}.{} where {}",
+                    subset_config.database, subset_config.table, condition
+                ),
+                None => info!(
+                    "Database subset of {}.{}: no previous dump, all the rows are kept",
+                    subset_config.database, subset_config.table
+                ),
+            }
+
+            SubsetStrategy::Incremental {
+                database: subset_config.database.as_str(),
+                table: subset_config.table.as_str(),
+                column: opt.column.as_str(),
+                since: opt.since.as_deref(),
+            }
+        }
     };
 
     let empty_vec = Vec::new();
@@ -165,7 +556,7 @@ pub fn subset<R: Read>(
     let subset_options = SubsetOptions::new(&passthrough_tables);
     let subset = PostgresSubset::new(named_temp_file.path(), strategy, subset_options)?;
 
-    let named_subset_file = tempfile::NamedTempFile::new()?;
+    let named_subset_file = new_named_temp_file(tmp_dir)?;
     let mut subset_file = named_subset_file.as_file();
 
     let _ = subset.read(
@@ -192,7 +583,7 @@ pub fn read_and_transform<R: Read, F: FnMut(OriginalQuery, Query)>(
     reader: BufReader<R>,
     options: SourceOptions,
     mut query_callback: F,
-) {
+) -> Result<(), Error> {
     // create a map variable with Transformer by column_name
     let mut transformer_by_db_and_table_and_column_name: HashMap<String, &Box<dyn Transformer>> =
         HashMap::with_capacity(options.transformers.len());
@@ -204,12 +595,48 @@ pub fn read_and_transform<R: Read, F: FnMut(OriginalQuery, Query)>(
         );
     }
 
+    // transformers inferred from `COMMENT ON COLUMN ... IS 'pii:<tag>'` queries
+    let mut pii_transformer_by_db_and_table_and_column_name: HashMap<String, Box<dyn Transformer>> =
+        HashMap::new();
+
+    // labels of the `CREATE TYPE ... AS ENUM` types, then of the columns of these types
+    let mut enum_labels_by_db_and_type_name: HashMap<String, Vec<String>> = HashMap::new();
+    let mut enum_labels_by_db_and_table_and_column_name: HashMap<String, Vec<String>> =
+        HashMap::new();
+
+    // partitions inherit the transformers and the skip config of their parent table
+    let mut parent_table_by_db_and_partition_name: HashMap<String, (String, String)> =
+        HashMap::new();
+
     let mut skip_tables_map: HashMap<String, bool> =
         HashMap::with_capacity(options.skip_config.len());
+    let mut skip_data_tables_map: HashMap<String, bool> =
+        HashMap::with_capacity(options.schema_only_tables.len());
     for skip in options.skip_config {
         let _ = skip_tables_map.insert(format!("{}.{}", skip.database, skip.table), true);
     }
 
+    // the schema of these tables is kept, only their rows are skipped
+    for schema_only_table in options.schema_only_tables {
+        let _ = skip_data_tables_map.insert(
+            format!("{}.{}", schema_only_table.database, schema_only_table.table),
+            true,
+        );
+    }
+
+    let mut amplify_by_db_and_table: HashMap<String, &AmplifyConfig> =
+        HashMap::with_capacity(options.amplify.len());
+    for amplify in options.amplify {
+        let _ = amplify_by_db_and_table
+            .insert(format!("{}.{}", amplify.database, amplify.table), amplify);
+    }
+
+    // number of copies written for each amplified table
+    let mut copies_by_db_and_table: HashMap<String, i128> = HashMap::new();
+
+    // error aborting the dump, the rest of it is not read
+    let mut transform_error = None;
+
     match list_sql_queries_from_dump_reader(reader, |query| {
         let tokens = get_tokens_from_query_str(query);
 
@@ -218,13 +645,66 @@ pub fn read_and_transform<R: Read, F: FnMut(OriginalQuery, Query)>(
                 database_name,
                 table_name,
             } => {
-                if !skip_tables_map.contains_key(&format!("{}.{}", database_name, table_name)) {
-                    let (original_columns, columns) = transform_columns(
+                let parent_table = parent_table_by_db_and_partition_name
+                    .get(&format!("{}.{}", database_name, table_name))
+                    .map(|(db, table)| (db.as_str(), table.as_str()));
+
+                let is_data_skipped = |db: &str, table: &str| {
+                    let db_and_table_name = format!("{}.{}", db, table);
+                    skip_tables_map.contains_key(&db_and_table_name)
+                        || skip_data_tables_map.contains_key(&db_and_table_name)
+                };
+
+                let is_parent_table_skipped = match parent_table {
+                    Some((db, table)) => is_data_skipped(db, table),
+                    None => false,
+                };
+
+                if !is_parent_table_skipped
+                    && !is_data_skipped(database_name.as_str(), table_name.as_str())
+                {
+                    let (original_columns, columns) = match transform_columns(
                         database_name.as_str(),
                         table_name.as_str(),
+                        parent_table,
                         &tokens,
                         &transformer_by_db_and_table_and_column_name,
-                    );
+                        &pii_transformer_by_db_and_table_and_column_name,
+                        &enum_labels_by_db_and_table_and_column_name,
+                        options.on_transform_error,
+                        options.seed,
+                    ) {
+                        Ok(columns) => columns,
+                        Err(err) => {
+                            transform_error = Some(err);
+                            return ListQueryResult::Break;
+                        }
+                    };
+
+                    let db_and_table_name = format!("{}.{}", database_name, table_name);
+                    let copies = match amplify_by_db_and_table.get(&db_and_table_name) {
+                        Some(amplify) => {
+                            let copy_count =
+                                copies_by_db_and_table.entry(db_and_table_name).or_insert(0);
+
+                            let copies = (1..amplify.factor)
+                                .map(|copy_index| {
+                                    *copy_count += 1;
+                                    copy_row(&original_columns, amplify, *copy_count)
+                                        .map(|copy| (copy_index, copy))
+                                })
+                                .collect::<Result<Vec<_>, Error>>();
+
+                            match copies {
+                                Ok(copies) => copies,
+                                Err(err) => {
+                                    transform_error = Some(err);
+                                    return ListQueryResult::Break;
+                                }
+                            }
+                        }
+                        None => vec![],
+                    };
 
                     query_callback(
                         to_query(
@@ -241,13 +721,64 @@ pub fn read_and_transform<R: Read, F: FnMut(OriginalQuery, Query)>(
                                 columns,
                             },
                         ),
-                    )
+                    );
+
+                    // the copies are transformed again to get other values than the original row - salted with
+                    // the index of the copy, so the seeded transformers give each copy other values too
+                    for (copy_index, copy) in copies {
+                        let columns = match with_salt(copy_index as u64, || {
+                            transform_row(
+                                database_name.as_str(),
+                                table_name.as_str(),
+                                parent_table,
+                                &copy,
+                                &transformer_by_db_and_table_and_column_name,
+                                &pii_transformer_by_db_and_table_and_column_name,
+                                &enum_labels_by_db_and_table_and_column_name,
+                                options.on_transform_error,
+                                options.seed,
+                            )
+                        }) {
+                            Ok(columns) => columns,
+                            Err(err) => {
+                                transform_error = Some(err);
+                                return ListQueryResult::Break;
+                            }
+                        };
+
+                        query_callback(
+                            to_query(
+                                Some(database_name.as_str()),
+                                InsertIntoQuery {
+                                    table_name: table_name.to_string(),
+                                    columns: copy,
+                                },
+                            ),
+                            to_query(
+                                Some(database_name.as_str()),
+                                InsertIntoQuery {
+                                    table_name: table_name.to_string(),
+                                    columns,
+                                },
+                            ),
+                        );
+                    }
                 }
             }
             RowType::CreateTable {
                 database_name,
                 table_name,
             } => {
+                // the transformed values of these columns must be labels of their enum type
+                for (column_name, type_name) in get_column_types(&tokens) {
+                    if let Some(labels) = enum_labels_by_db_and_type_name.get(&type_name) {
+                        let _ = enum_labels_by_db_and_table_and_column_name.insert(
+                            format!("{}.{}.{}", database_name, table_name, column_name),
+                            labels.clone(),
+                        );
+                    }
+                }
+
                 if !skip_tables_map.contains_key(&format!("{}.{}", database_name, table_name)) {
                     no_change_query_callback(query_callback.borrow_mut(), query);
                 }
@@ -260,6 +791,67 @@ pub fn read_and_transform<R: Read, F: FnMut(OriginalQuery, Query)>(
                     no_change_query_callback(query_callback.borrow_mut(), query);
                 }
             }
+            RowType::AttachPartition {
+                database_name,
+                table_name,
+                partition_database_name,
+                partition_table_name,
+            } => {
+                if !skip_tables_map.contains_key(&format!("{}.{}", database_name, table_name)) {
+                    no_change_query_callback(query_callback.borrow_mut(), query);
+                }
+
+                let _ = parent_table_by_db_and_partition_name.insert(
+                    format!("{}.{}", partition_database_name, partition_table_name),
+                    (database_name, table_name),
+                );
+            }
+            RowType::CommentOnColumn {
+                database_name,
+                table_name,
+                column_name,
+                comment,
+            } => {
+                let db_and_table_and_column_name =
+                    format!("{}.{}.{}", database_name, table_name, column_name);
+
+                // a transformer explicitly set in the configuration always takes precedence
+                if options.detect_pii_comments
+                    && !transformer_by_db_and_table_and_column_name
+                        .contains_key(db_and_table_and_column_name.as_str())
+                {
+                    if let Some(transformer_type) =
+                        TransformerTypeConfig::from_pii_comment(comment.as_str())
+                    {
+                        let transformer = transformer_type.transformer(
+                            database_name.as_str(),
+                            table_name.as_str(),
+                            column_name.as_str(),
+                        );
+
+                        info!(
+                            "column comment detected: apply '{}' transformer on {}",
+                            transformer.id(),
+                            db_and_table_and_column_name
+                        );
+
+                        let _ = pii_transformer_by_db_and_table_and_column_name
+                            .insert(db_and_table_and_column_name, transformer);
+                    }
+                }
+
+                no_change_query_callback(query_callback.borrow_mut(), query);
+            }
+            RowType::CreateEnumType {
+                database_name,
+                type_name,
+                labels,
+            } => {
+                let _ = enum_labels_by_db_and_type_name
+                    .insert(format!("{}.{}", database_name, type_name), labels);
+
+                no_change_query_callback(query_callback.borrow_mut(), query);
+            }
             RowType::Others => {
                 // other rows than `INSERT INTO ...` and `CREATE TABLE ...`
                 no_change_query_callback(query_callback.borrow_mut(), query);
@@ -271,6 +863,11 @@ pub fn read_and_transform<R: Read, F: FnMut(OriginalQuery, Query)>(
         Ok(_) => {}
         Err(err) => panic!("{:?}", err),
     }
+
+    match transform_error {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
 }
 
 fn no_change_query_callback<F: FnMut(OriginalQuery, Query)>(query_callback: &mut F, query: &str) {
@@ -281,12 +878,18 @@ fn no_change_query_callback<F: FnMut(OriginalQuery, Query)>(query_callback: &mut
     );
 }
 
+#[allow(clippy::too_many_arguments)]
 fn transform_columns(
     database_name: &str,
     table_name: &str,
+    parent_table: Option<(&str, &str)>,
     tokens: &Vec<Token>,
     transformer_by_db_and_table_and_column_name: &HashMap<String, &Box<dyn Transformer>>,
-) -> (Vec<Column>, Vec<Column>) {
+    pii_transformer_by_db_and_table_and_column_name: &HashMap<String, Box<dyn Transformer>>,
+    enum_labels_by_db_and_table_and_column_name: &HashMap<String, Vec<String>>,
+    on_transform_error: Option<OnTransformError>,
+    seed: Option<u64>,
+) -> Result<(Vec<Column>, Vec<Column>), Error> {
     // find database name by filtering out all queries starting with
     // INSERT INTO <database>.<table> (...)
     // INSERT       -> position 0
@@ -299,7 +902,6 @@ fn transform_columns(
     assert_eq!(column_names.len(), column_values.len(), "Column names do not match values: got {} names and {} values", column_names.len(), column_values.len());
 
     let mut original_columns = vec![];
-    let mut columns = vec![];
 
     for (i, column_name) in column_names.iter().enumerate() {
         let value_token = column_values.get(i).unwrap();
@@ -340,23 +942,140 @@ fn transform_columns(
             _ => Column::None(column_name.to_string()),
         };
 
-        // get the right transformer for the right column name
-        let original_column = column.clone();
+        original_columns.push(column);
+    }
 
-        let db_and_table_and_column_name =
-            format!("{}.{}.{}", database_name, table_name, *column_name);
-        let column = match transformer_by_db_and_table_and_column_name
-            .get(db_and_table_and_column_name.as_str())
-        {
-            Some(transformer) => transformer.transform(column), // apply transformation on the column
+    let columns = transform_row(
+        database_name,
+        table_name,
+        parent_table,
+        &original_columns,
+        transformer_by_db_and_table_and_column_name,
+        pii_transformer_by_db_and_table_and_column_name,
+        enum_labels_by_db_and_table_and_column_name,
+        on_transform_error,
+        seed,
+    )?;
+
+    Ok((original_columns, columns))
+}
+
+/// apply the transformers on the columns of a row
+#[allow(clippy::too_many_arguments)]
+fn transform_row(
+    database_name: &str,
+    table_name: &str,
+    parent_table: Option<(&str, &str)>,
+    original_columns: &[Column],
+    transformer_by_db_and_table_and_column_name: &HashMap<String, &Box<dyn Transformer>>,
+    pii_transformer_by_db_and_table_and_column_name: &HashMap<String, Box<dyn Transformer>>,
+    enum_labels_by_db_and_table_and_column_name: &HashMap<String, Vec<String>>,
+    on_transform_error: Option<OnTransformError>,
+    seed: Option<u64>,
+) -> Result<Vec<Column>, Error> {
+    let db_and_table_and_column_names = |column: &Column| {
+        [Some((database_name, table_name)), parent_table]
+            .iter()
+            .flatten()
+            .map(|(database_name, table_name)| {
+                format!("{}.{}.{}", database_name, table_name, column.name())
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // get the right transformer for the right column name, then from the parent table for partitions
+    let transformers = original_columns
+        .iter()
+        .map(|column| {
+            db_and_table_and_column_names(column)
+                .iter()
+                .find_map(|db_and_table_and_column_name| {
+                    transformer_by_db_and_table_and_column_name
+                        .get(db_and_table_and_column_name.as_str())
+                        .copied()
+                        .or_else(|| {
+                            pii_transformer_by_db_and_table_and_column_name
+                                .get(db_and_table_and_column_name.as_str())
+                        })
+                })
+                .map(|transformer| transformer.as_ref())
+        })
+        .collect::<Vec<_>>();
+
+    // apply transformation on the columns, with the original values of the row
+    transform_row_columns(
+        original_columns,
+        &transformers,
+        |idx, transformer, column, row| {
+            let column = transform_column(transformer, column, row, on_transform_error)?;
+
+            let enum_labels = db_and_table_and_column_names(&original_columns[idx])
+                .iter()
+                .find_map(|db_and_table_and_column_name| {
+                    enum_labels_by_db_and_table_and_column_name
+                        .get(db_and_table_and_column_name.as_str())
+                });
+
+            match enum_labels {
+                // picked from the original value, like the values of the seeded transformers
+                Some(labels) => Ok(with_seed(seed, &original_columns[idx], || {
+                    to_enum_label(column, labels)
+                })),
+                None => Ok(column),
+            }
+        },
+    )
+}
+
+/// the column itself if its value is one of the labels of its enum type, a random label otherwise
+fn to_enum_label(column: Column, labels: &[String]) -> Column {
+    match &column {
+        Column::None(_) => column,
+        Column::StringValue(_, value) if labels.contains(value) => column,
+        _ => match labels.choose(&mut rng()) {
+            Some(label) => Column::StringValue(column.name().to_string(), label.clone()),
             None => column,
-        };
+        },
+    }
+}
 
-        original_columns.push(original_column);
-        columns.push(column);
+/// copy of the row with a new primary key - negative numbers never collide with the ids of the table
+/// or the ones generated later by a sequence, and strings get the copy number as suffix. The foreign keys are not
+/// remapped: the copy references the same rows as the original one
+fn copy_row(
+    original_columns: &[Column],
+    amplify: &AmplifyConfig,
+    copy_number: i128,
+) -> Result<Vec<Column>, Error> {
+    let mut has_primary_key = false;
+
+    let columns = original_columns
+        .iter()
+        .map(|column| match column {
+            Column::NumberValue(name, _) if *name == amplify.primary_key => {
+                has_primary_key = true;
+                Column::NumberValue(name.clone(), -copy_number)
+            }
+            Column::StringValue(name, value) if *name == amplify.primary_key => {
+                has_primary_key = true;
+                Column::StringValue(name.clone(), format!("{}-{}", value, copy_number))
+            }
+            column => column.clone(),
+        })
+        .collect::<Vec<_>>();
+
+    if !has_primary_key {
+        // copies would have the same primary key as the original row
+        return Err(Error::new(
+            ErrorKind::Other,
+            format!(
+                "Failed to amplify {}.{}: no number or string column '{}' to use as primary key",
+                amplify.database, amplify.table, amplify.primary_key
+            ),
+        ));
     }
 
-    (original_columns, columns)
+    Ok(columns)
 }
 
 fn is_insert_into_statement(tokens: &Vec<Token>) -> bool {
@@ -374,6 +1093,111 @@ fn is_alter_table_statement(tokens: &Vec<Token>) -> bool {
         && match_keyword_at_position(Keyword::Table, &tokens, 2)
 }
 
+fn is_attach_partition_statement(tokens: &Vec<Token>) -> bool {
+    // ALTER TABLE ONLY <database>.<table> ATTACH PARTITION <database>.<partition> ...
+    let attach_pos = if match_keyword_at_position(Keyword::Only, &tokens, 4) {
+        10
+    } else {
+        8
+    };
+
+    is_alter_table_statement(&tokens)
+        && match_keyword_at_position(Keyword::Attach, &tokens, attach_pos)
+        && match_keyword_at_position(Keyword::Partition, &tokens, attach_pos + 2)
+}
+
+fn is_comment_on_column_statement(tokens: &Vec<Token>) -> bool {
+    match_keyword_at_position(Keyword::Comment, &tokens, 0)
+        && match_keyword_at_position(Keyword::On, &tokens, 2)
+        && match_keyword_at_position(Keyword::Column, &tokens, 4)
+}
+
+fn is_create_enum_type_statement(tokens: &Vec<Token>) -> bool {
+    // CREATE TYPE <database>.<type> AS ENUM (...)
+    let is_word_at_position = |word: &str, pos: usize| {
+        get_word_value_at_position(tokens, pos)
+            .map(|value| value.eq_ignore_ascii_case(word))
+            .unwrap_or(false)
+    };
+
+    match_keyword_at_position(Keyword::Create, tokens, 0)
+        && is_word_at_position("TYPE", 2)
+        && is_word_at_position("AS", 8)
+        && is_word_at_position("ENUM", 10)
+}
+
+/// (column, type) of the columns of a `CREATE TABLE` query, with the column name escaped as for INSERT INTO column
+/// names and the `<database>.<type>` of a qualified type - an array type and the table constraints are not returned
+fn get_column_types(tokens: &[Token]) -> Vec<(String, String)> {
+    let table_constraints = [
+        "CONSTRAINT",
+        "CHECK",
+        "UNIQUE",
+        "PRIMARY",
+        "EXCLUDE",
+        "FOREIGN",
+    ];
+
+    let mut column_types = vec![];
+    let mut definition: Vec<&Token> = vec![];
+    let mut depth = 0;
+
+    for token in tokens {
+        match token {
+            Token::LParen => depth += 1,
+            Token::RParen => depth -= 1,
+            _ => {}
+        }
+
+        let is_end_of_definition = match token {
+            Token::Comma => depth == 1,
+            Token::RParen => depth == 0,
+            _ => false,
+        };
+
+        if is_end_of_definition {
+            let column_type = match definition.as_slice() {
+                [Token::Word(column), Token::Word(database), Token::Period, Token::Word(type_name), rest @ ..]
+                    if !matches!(rest.first(), Some(Token::LBracket)) =>
+                {
+                    Some((column, format!("{}.{}", database.value, type_name.value)))
+                }
+                [Token::Word(column), Token::Word(type_name), rest @ ..]
+                    if !matches!(rest.first(), Some(Token::LBracket | Token::Period)) =>
+                {
+                    Some((column, type_name.value.clone()))
+                }
+                _ => None,
+            };
+
+            let column_type = column_type.filter(|(column, _)| {
+                column.quote_style.is_some()
+                    || !table_constraints
+                        .iter()
+                        .any(|keyword| column.value.eq_ignore_ascii_case(keyword))
+            });
+
+            if let Some((column, type_name)) = column_type {
+                let column_name = match column.quote_style {
+                    Some(quote) => format!("{}{}{}", quote, column.value, quote),
+                    None => column.value.clone(),
+                };
+
+                column_types.push((column_name, type_name));
+            }
+
+            definition.clear();
+        } else if depth >= 1
+            && !(depth == 1 && *token == Token::LParen)
+            && !matches!(token, Token::Whitespace(_))
+        {
+            definition.push(token);
+        }
+    }
+
+    column_types
+}
+
 fn get_row_type(tokens: &Vec<Token>) -> RowType {
     let mut row_type = RowType::Others;
 
@@ -422,6 +1246,81 @@ fn get_row_type(tokens: &Vec<Token>) -> RowType {
         }
     }
 
+    if is_attach_partition_statement(&tokens) {
+        let database_name_pos = if match_keyword_at_position(Keyword::Only, &tokens, 4) {
+            6
+        } else {
+            4
+        };
+
+        if let (
+            Some(database_name),
+            Some(table_name),
+            Some(partition_database_name),
+            Some(partition_table_name),
+        ) = (
+            get_word_value_at_position(&tokens, database_name_pos),
+            get_word_value_at_position(&tokens, database_name_pos + 2),
+            get_word_value_at_position(&tokens, database_name_pos + 8),
+            get_word_value_at_position(&tokens, database_name_pos + 10),
+        ) {
+            row_type = RowType::AttachPartition {
+                database_name: database_name.to_string(),
+                table_name: table_name.to_string(),
+                partition_database_name: partition_database_name.to_string(),
+                partition_table_name: partition_table_name.to_string(),
+            };
+        }
+    }
+
+    if is_create_enum_type_statement(tokens) {
+        if let (Some(database_name), Some(type_name)) = (
+            get_word_value_at_position(tokens, 4),
+            get_word_value_at_position(tokens, 6),
+        ) {
+            let labels = tokens
+                .iter()
+                .skip(11)
+                .filter_map(|token| match token {
+                    Token::SingleQuotedString(label) => Some(label.clone()),
+                    _ => None,
+                })
+                .collect();
+
+            row_type = RowType::CreateEnumType {
+                database_name: database_name.to_string(),
+                type_name: type_name.to_string(),
+                labels,
+            };
+        }
+    }
+
+    if is_comment_on_column_statement(&tokens) {
+        // COMMENT ON COLUMN <database>.<table>.<column> IS '<comment>';
+        if let (
+            Some(database_name),
+            Some(table_name),
+            Some(Token::Word(column)),
+            Some(Token::SingleQuotedString(comment)),
+        ) = (
+            get_word_value_at_position(&tokens, 6),
+            get_word_value_at_position(&tokens, 8),
+            tokens.get(10),
+            tokens.get(14),
+        ) {
+            row_type = RowType::CommentOnColumn {
+                database_name: database_name.to_string(),
+                table_name: table_name.to_string(),
+                // column name with escaping, as for INSERT INTO column names
+                column_name: match column.quote_style {
+                    Some(quote) => format!("{}{}{}", quote, column.value, quote),
+                    None => column.value.clone(),
+                },
+                comment: comment.to_string(),
+            };
+        }
+    }
+
     row_type
 }
 
@@ -477,18 +1376,37 @@ fn to_query(database: Option<&str>, query: InsertIntoQuery) -> Query {
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
+    use std::io::{BufReader, Error};
     use std::str;
     use std::vec;
 
+    use dump_parser::postgres::get_tokens_from_query_str;
+    use tempfile::tempdir;
+
     use crate::config::{
-        DatabaseSubsetConfig, DatabaseSubsetConfigStrategy, DatabaseSubsetConfigStrategyRandom,
-        SkipConfig,
+        AmplifyConfig, DatabaseSubsetConfig, DatabaseSubsetConfigStrategy,
+        DatabaseSubsetConfigStrategyRandom, ForeignKeyConfig, OnlyTablesConfig,
+        SchemaOnlyTablesConfig, SkipConfig,
+    };
+    use crate::source::postgres::{
+        column_stats_query, create_sequence_name, get_column_types, get_only_tables_args,
+        get_oversized_tables, get_schema_only_tables_args, new_named_temp_file, parse_column_stats,
+        parse_sequences, parse_table_sizes, read_and_transform, setval_queries,
+        setval_sequence_name, to_query, Postgres, Sequence,
     };
-    use crate::source::postgres::{to_query, Postgres};
     use crate::source::SourceOptions;
+    use crate::transformer::corporate_email::{CorporateEmailOptions, CorporateEmailTransformer};
+    use crate::transformer::credit_card::CreditCardTransformer;
+    use crate::transformer::email::EmailTransformer;
+    use crate::transformer::first_name::FirstNameTransformer;
+    use crate::transformer::foreign_key::link_foreign_keys;
+    use crate::transformer::keep_first_char::{KeepFirstCharOptions, KeepFirstCharTransformer};
     use crate::transformer::random::RandomTransformer;
+    use crate::transformer::redacted::RedactedTransformer;
+    use crate::transformer::seeded::seed_transformers;
     use crate::transformer::transient::TransientTransformer;
-    use crate::transformer::Transformer;
+    use crate::transformer::unique::UniqueTransformer;
+    use crate::transformer::{OnTransformError, Transformer};
     use crate::types::{Column, InsertIntoQuery};
     use crate::Source;
 
@@ -500,6 +1418,24 @@ mod tests {
         Postgres::new("localhost", 5432, "root", "root", "wrongpassword")
     }
 
+    #[test]
+    fn set_the_ssl_mode_of_the_commands() {
+        let ssl_mode = |postgres: &Postgres| {
+            postgres
+                .command("pg_dump")
+                .get_envs()
+                .find(|(name, _)| *name == "PGSSLMODE")
+                .and_then(|(_, value)| value.map(|value| value.to_string_lossy().to_string()))
+        };
+
+        // unchanged without SSL mode
+        let mut postgres = get_postgres();
+        assert_eq!(ssl_mode(&postgres), None);
+
+        postgres.set_ssl_mode("require");
+        assert_eq!(ssl_mode(&postgres), Some("require".to_string()));
+    }
+
     #[test]
     fn connect() {
         let p = get_postgres();
@@ -510,6 +1446,14 @@ mod tests {
             skip_config: &vec![],
             database_subset: &None,
             only_tables: &vec![],
+            schema_only_tables: &vec![],
+            max_table_size: None,
+            tmp_dir: None,
+            detect_pii_comments: false,
+            capture_sequences: false,
+            on_transform_error: None,
+            amplify: &vec![],
+            seed: None,
         };
 
         assert!(p.read(source_options, |original_query, query| {}).is_ok());
@@ -522,6 +1466,14 @@ mod tests {
             skip_config: &vec![],
             database_subset: &None,
             only_tables: &vec![],
+            schema_only_tables: &vec![],
+            max_table_size: None,
+            tmp_dir: None,
+            detect_pii_comments: false,
+            capture_sequences: false,
+            on_transform_error: None,
+            amplify: &vec![],
+            seed: None,
         };
 
         assert!(p.read(source_options, |original_query, query| {}).is_err());
@@ -537,6 +1489,14 @@ mod tests {
             skip_config: &vec![],
             database_subset: &None,
             only_tables: &vec![],
+            schema_only_tables: &vec![],
+            max_table_size: None,
+            tmp_dir: None,
+            detect_pii_comments: false,
+            capture_sequences: false,
+            on_transform_error: None,
+            amplify: &vec![],
+            seed: None,
         };
 
         let _ = p.read(source_options, |original_query, query| {
@@ -663,6 +1623,14 @@ mod tests {
             skip_config: &vec![],
             database_subset: &None,
             only_tables: &vec![],
+            schema_only_tables: &vec![],
+            max_table_size: None,
+            tmp_dir: None,
+            detect_pii_comments: false,
+            capture_sequences: false,
+            on_transform_error: None,
+            amplify: &vec![],
+            seed: None,
         };
 
         let _ = p.read(source_options, |original_query, query| {
@@ -704,6 +1672,14 @@ mod tests {
             skip_config: &skip_config,
             database_subset: &None,
             only_tables: &vec![],
+            schema_only_tables: &vec![],
+            max_table_size: None,
+            tmp_dir: None,
+            detect_pii_comments: false,
+            capture_sequences: false,
+            on_transform_error: None,
+            amplify: &vec![],
+            seed: None,
         };
 
         let _ = p.read(source_options, |_original_query, query| {
@@ -755,6 +1731,14 @@ mod tests {
                 passthrough_tables: None,
             }),
             only_tables: &vec![],
+            schema_only_tables: &vec![],
+            max_table_size: None,
+            tmp_dir: None,
+            detect_pii_comments: false,
+            capture_sequences: false,
+            on_transform_error: None,
+            amplify: &vec![],
+            seed: None,
         };
 
         let mut rows_percent_50 = vec![];
@@ -792,6 +1776,14 @@ mod tests {
                 passthrough_tables: None,
             }),
             only_tables: &vec![],
+            schema_only_tables: &vec![],
+            max_table_size: None,
+            tmp_dir: None,
+            detect_pii_comments: false,
+            capture_sequences: false,
+            on_transform_error: None,
+            amplify: &vec![],
+            seed: None,
         };
 
         let mut rows_percent_30 = vec![];
@@ -816,4 +1808,1032 @@ mod tests {
 
         assert!(rows_percent_30.len() < rows_percent_50.len());
     }
+
+    #[test]
+    fn only_tables_args() {
+        assert!(get_only_tables_args(&[]).is_empty());
+
+        let only_tables = vec![OnlyTablesConfig {
+            database: "public".to_string(),
+            table: "orders".to_string(),
+        }];
+
+        assert_eq!(
+            get_only_tables_args(&only_tables),
+            vec!["--table=public.orders".to_string()]
+        );
+    }
+
+    #[test]
+    fn schema_only_tables_args() {
+        assert!(get_schema_only_tables_args(&[]).is_empty());
+
+        let schema_only_tables = vec![SchemaOnlyTablesConfig {
+            database: "public".to_string(),
+            table: "audit_logs".to_string(),
+        }];
+
+        assert_eq!(
+            get_schema_only_tables_args(&schema_only_tables),
+            vec!["--exclude-table-data=public.audit_logs".to_string()]
+        );
+    }
+
+    #[test]
+    fn skip_oversized_tables() {
+        let table_sizes = parse_table_sizes(
+            "public,orders,8192\npublic,audit_logs,1073741824\npublic,events,2147483648\n",
+        );
+
+        assert_eq!(table_sizes.len(), 3);
+        assert_eq!(
+            get_oversized_tables(&table_sizes, 1_000_000, &[]),
+            vec!["public.audit_logs".to_string(), "public.events".to_string()]
+        );
+
+        // a table in `only_tables` is dumped whatever its size
+        let only_tables = vec![OnlyTablesConfig {
+            database: "public".to_string(),
+            table: "events".to_string(),
+        }];
+
+        assert_eq!(
+            get_oversized_tables(&table_sizes, 1_000_000, &only_tables),
+            vec!["public.audit_logs".to_string()]
+        );
+    }
+
+    #[test]
+    fn restore_the_values_of_the_dumped_sequences() {
+        let sequences = parse_sequences(
+            "public,orders_id_seq,42,t\npublic,order,lines_id_seq,7,t\naudit,events_id_seq,1,f\n",
+        );
+
+        assert_eq!(
+            sequences[1],
+            Sequence {
+                database: "public".to_string(),
+                name: "order,lines_id_seq".to_string(),
+                last_value: 7,
+                is_called: true,
+            }
+        );
+
+        // the sequences missing from the dump (e.g. of a table not in `only_tables`) are not restored
+        let dumped_sequences = [
+            create_sequence_name(
+                b"CREATE SEQUENCE public.orders_id_seq\n    AS integer\n    START WITH 1;",
+            ),
+            create_sequence_name(b"CREATE SEQUENCE audit.\"events_id_seq\"\n    START WITH 1;"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<HashSet<_>>();
+
+        let queries = setval_queries(&sequences, &dumped_sequences, &HashSet::new())
+            .into_iter()
+            .map(|query| String::from_utf8(query.0).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            queries,
+            vec![
+                "SELECT pg_catalog.setval('\"public\".\"orders_id_seq\"', 42, true);",
+                "SELECT pg_catalog.setval('\"audit\".\"events_id_seq\"', 1, false);",
+            ]
+        );
+
+        // the sequences already set by pg_dump are not set twice
+        let set_sequences = [setval_sequence_name(
+            b"SELECT pg_catalog.setval('public.orders_id_seq', 42, true);",
+        )]
+        .into_iter()
+        .flatten()
+        .collect::<HashSet<_>>();
+
+        let queries = setval_queries(&sequences, &dumped_sequences, &set_sequences)
+            .into_iter()
+            .map(|query| String::from_utf8(query.0).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            queries,
+            vec!["SELECT pg_catalog.setval('\"audit\".\"events_id_seq\"', 1, false);"]
+        );
+    }
+
+    #[test]
+    fn read_the_sequence_name_of_setval_queries() {
+        assert_eq!(
+            setval_sequence_name(b"SELECT pg_catalog.setval('public.orders_id_seq', 42, true);"),
+            Some("public.orders_id_seq".to_string())
+        );
+        assert_eq!(
+            setval_sequence_name(
+                b"SELECT pg_catalog.setval('public.\"order,line''s_id_seq\"', 1, false);"
+            ),
+            Some("public.order,line's_id_seq".to_string())
+        );
+        assert_eq!(setval_sequence_name(b"SELECT 1;"), None);
+    }
+
+    #[test]
+    fn read_column_stats() {
+        assert_eq!(
+            column_stats_query("public", "orders", "unit \"price\""),
+            "SELECT min(\"unit \"\"price\"\"\")::float8, max(\"unit \"\"price\"\"\")::float8, \
+avg(\"unit \"\"price\"\"\")::float8, stddev_pop(\"unit \"\"price\"\"\")::float8 FROM \"public\".\"orders\""
+        );
+
+        let stats = parse_column_stats("1.5,99,42.25,12.5\n").unwrap();
+        assert_eq!(stats.min, Some(1.5));
+        assert_eq!(stats.max, Some(99.0));
+        assert_eq!(stats.mean, Some(42.25));
+        assert_eq!(stats.stddev, Some(12.5));
+
+        // all the statistics of an empty table are NULL
+        assert_eq!(parse_column_stats(",,,\n").unwrap().mean, Some(0.0));
+        assert!(parse_column_stats("ERROR").is_none());
+    }
+
+    #[test]
+    fn write_intermediate_files_in_tmp_dir() {
+        let tmp_dir = tempdir().expect("cannot create tempdir");
+
+        let file = new_named_temp_file(Some(tmp_dir.path())).unwrap();
+        assert!(file.path().starts_with(tmp_dir.path()));
+
+        let file = new_named_temp_file(None).unwrap();
+        assert!(file.path().starts_with(std::env::temp_dir()));
+    }
+
+    #[test]
+    fn keep_the_schema_of_schema_only_tables() {
+        let dump = r#"
+CREATE TABLE public.audit_logs (log_id integer NOT NULL, message text);
+CREATE TABLE public.employees (employee_id integer NOT NULL, last_name character varying(20));
+INSERT INTO public.audit_logs (log_id, message) VALUES (1, 'Nancy logged in');
+INSERT INTO public.employees (employee_id, last_name) VALUES (1, 'Davolio');
+"#;
+
+        let schema_only_tables = vec![SchemaOnlyTablesConfig {
+            database: "public".to_string(),
+            table: "audit_logs".to_string(),
+        }];
+
+        let source_options = SourceOptions {
+            transformers: &vec![],
+            skip_config: &vec![],
+            database_subset: &None,
+            only_tables: &vec![],
+            schema_only_tables: &schema_only_tables,
+            max_table_size: None,
+            tmp_dir: None,
+            detect_pii_comments: false,
+            capture_sequences: false,
+            on_transform_error: None,
+            amplify: &vec![],
+            seed: None,
+        };
+
+        let mut rows = vec![];
+        read_and_transform(
+            BufReader::new(dump.as_bytes()),
+            source_options,
+            |_original_query, query| {
+                rows.push(String::from_utf8_lossy(query.data().as_slice()).to_string());
+            },
+        )
+        .unwrap();
+
+        assert!(rows
+            .iter()
+            .any(|x| x.starts_with("CREATE TABLE public.audit_logs")));
+        assert!(!rows
+            .iter()
+            .any(|x| x.starts_with("INSERT INTO public.audit_logs")));
+        assert!(rows
+            .iter()
+            .any(|x| x.starts_with("INSERT INTO public.employees")));
+    }
+
+    #[test]
+    fn transform_partitions_with_the_parent_table_transformers() {
+        let dump = r#"
+CREATE TABLE public.events (event_id integer NOT NULL, email character varying(255), created_at date NOT NULL) PARTITION BY RANGE (created_at);
+CREATE TABLE public.events_2023 (event_id integer NOT NULL, email character varying(255), created_at date NOT NULL);
+CREATE TABLE public.events_2024 (event_id integer NOT NULL, email character varying(255), created_at date NOT NULL);
+ALTER TABLE ONLY public.events ATTACH PARTITION public.events_2023 FOR VALUES FROM ('2023-01-01') TO ('2024-01-01');
+ALTER TABLE ONLY public.events ATTACH PARTITION public.events_2024 FOR VALUES FROM ('2024-01-01') TO ('2025-01-01');
+INSERT INTO public.events_2023 (event_id, email, created_at) VALUES (1, 'nancy.davolio@northwind.com', '2023-05-01');
+INSERT INTO public.events_2024 (event_id, email, created_at) VALUES (2, 'andrew.fuller@northwind.com', '2024-05-01');
+"#;
+
+        let t1: Box<dyn Transformer> =
+            Box::new(RandomTransformer::new("public", "events", "email"));
+        let transformers = vec![t1];
+
+        let source_options = SourceOptions {
+            transformers: &transformers,
+            skip_config: &vec![],
+            database_subset: &None,
+            only_tables: &vec![],
+            schema_only_tables: &vec![],
+            max_table_size: None,
+            tmp_dir: None,
+            detect_pii_comments: false,
+            capture_sequences: false,
+            on_transform_error: None,
+            amplify: &vec![],
+            seed: None,
+        };
+
+        let mut rows = vec![];
+        read_and_transform(
+            BufReader::new(dump.as_bytes()),
+            source_options,
+            |_original_query, query| {
+                rows.push(String::from_utf8_lossy(query.data().as_slice()).to_string());
+            },
+        )
+        .unwrap();
+
+        let insert_into_rows = rows
+            .iter()
+            .filter(|x| x.starts_with("INSERT INTO"))
+            .collect::<Vec<_>>();
+
+        assert_eq!(insert_into_rows.len(), 2);
+        assert!(insert_into_rows[0].starts_with("INSERT INTO public.events_2023"));
+        assert!(!insert_into_rows[0].contains("nancy.davolio@northwind.com"));
+        assert!(insert_into_rows[1].starts_with("INSERT INTO public.events_2024"));
+        assert!(!insert_into_rows[1].contains("andrew.fuller@northwind.com"));
+
+        // skipping the parent table skips its partitions
+        let skip_config = vec![SkipConfig {
+            database: "public".to_string(),
+            table: "events".to_string(),
+        }];
+
+        let source_options = SourceOptions {
+            transformers: &transformers,
+            skip_config: &skip_config,
+            database_subset: &None,
+            only_tables: &vec![],
+            schema_only_tables: &vec![],
+            max_table_size: None,
+            tmp_dir: None,
+            detect_pii_comments: false,
+            capture_sequences: false,
+            on_transform_error: None,
+            amplify: &vec![],
+            seed: None,
+        };
+
+        let mut rows = vec![];
+        read_and_transform(
+            BufReader::new(dump.as_bytes()),
+            source_options,
+            |_original_query, query| {
+                rows.push(String::from_utf8_lossy(query.data().as_slice()).to_string());
+            },
+        )
+        .unwrap();
+
+        assert!(!rows.iter().any(|x| x.starts_with("INSERT INTO")));
+    }
+
+    #[test]
+    fn detect_pii_from_column_comments() {
+        let dump = r#"
+COMMENT ON COLUMN public.employees.email IS 'pii:email';
+COMMENT ON COLUMN public.employees.last_name IS 'pii:redacted';
+COMMENT ON COLUMN public.employees.first_name IS 'first name of the employee';
+INSERT INTO public.employees (employee_id, first_name, last_name, email) VALUES (1, 'Nancy', 'Davolio', 'nancy.davolio@northwind.com');
+"#;
+
+        // the last_name transformer from the configuration must take precedence over the comment
+        let t1: Box<dyn Transformer> = Box::new(TransientTransformer::new(
+            "public",
+            "employees",
+            "last_name",
+        ));
+        let transformers = vec![t1];
+
+        let source_options = SourceOptions {
+            transformers: &transformers,
+            skip_config: &vec![],
+            database_subset: &None,
+            only_tables: &vec![],
+            schema_only_tables: &vec![],
+            max_table_size: None,
+            tmp_dir: None,
+            detect_pii_comments: true,
+            capture_sequences: false,
+            on_transform_error: None,
+            amplify: &vec![],
+            seed: None,
+        };
+
+        let mut rows = vec![];
+        read_and_transform(
+            BufReader::new(dump.as_bytes()),
+            source_options,
+            |_original_query, query| {
+                rows.push(String::from_utf8_lossy(query.data().as_slice()).to_string());
+            },
+        )
+        .unwrap();
+
+        let insert_into = rows.iter().find(|x| x.starts_with("INSERT INTO")).unwrap();
+
+        assert!(insert_into.contains("'Nancy'"));
+        assert!(insert_into.contains("'Davolio'"));
+        assert!(!insert_into.contains("nancy.davolio@northwind.com"));
+
+        // without the option, column comments are ignored
+        let source_options = SourceOptions {
+            transformers: &transformers,
+            skip_config: &vec![],
+            database_subset: &None,
+            only_tables: &vec![],
+            schema_only_tables: &vec![],
+            max_table_size: None,
+            tmp_dir: None,
+            detect_pii_comments: false,
+            capture_sequences: false,
+            on_transform_error: None,
+            amplify: &vec![],
+            seed: None,
+        };
+
+        let mut rows = vec![];
+        read_and_transform(
+            BufReader::new(dump.as_bytes()),
+            source_options,
+            |_original_query, query| {
+                rows.push(String::from_utf8_lossy(query.data().as_slice()).to_string());
+            },
+        )
+        .unwrap();
+
+        let insert_into = rows.iter().find(|x| x.starts_with("INSERT INTO")).unwrap();
+
+        assert!(insert_into.contains("nancy.davolio@northwind.com"));
+    }
+
+    #[test]
+    fn keep_the_values_of_a_unique_transformer_unique_across_the_whole_table() {
+        // the rows are far apart in the dump, with the rows of another table in between
+        let mut dump = String::new();
+        for table_name in ["users", "orders", "users"] {
+            for idx in 0..500 {
+                dump.push_str(
+                    format!(
+                        "INSERT INTO public.{} (id, username) VALUES ({}, 'john.doe');\n",
+                        table_name, idx
+                    )
+                    .as_str(),
+                );
+            }
+        }
+
+        // the transformers are created once for the dump, so they keep their state from a row to another
+        let t1: Box<dyn Transformer> = Box::new(UniqueTransformer::new(Box::new(
+            RedactedTransformer::new("public", "users", "username", Default::default()),
+        )));
+        let transformers = vec![t1];
+
+        let source_options = SourceOptions {
+            transformers: &transformers,
+            skip_config: &vec![],
+            database_subset: &None,
+            only_tables: &vec![],
+            schema_only_tables: &vec![],
+            max_table_size: None,
+            tmp_dir: None,
+            detect_pii_comments: false,
+            capture_sequences: false,
+            on_transform_error: None,
+            amplify: &vec![],
+            seed: None,
+        };
+
+        let mut rows = vec![];
+        read_and_transform(
+            BufReader::new(dump.as_bytes()),
+            source_options,
+            |_original_query, query| {
+                rows.push(String::from_utf8_lossy(query.data().as_slice()).to_string());
+            },
+        )
+        .unwrap();
+
+        let usernames = rows
+            .iter()
+            .filter(|x| x.starts_with("INSERT INTO public.users"))
+            .map(|x| x.rsplit_once(", ").unwrap().1)
+            .collect::<Vec<_>>();
+
+        assert_eq!(usernames.len(), 1000);
+        assert_eq!(usernames.iter().collect::<HashSet<_>>().len(), 1000);
+        assert!(!usernames.contains(&"'john.doe');"));
+    }
+
+    #[test]
+    fn keep_the_foreign_keys_aligned_with_the_transformed_primary_keys() {
+        // the orders are read before the users they reference
+        let mut dump = String::new();
+        for idx in 0..100 {
+            dump.push_str(
+                format!(
+                    "INSERT INTO public.orders (id, user_id) VALUES ({}, {});\n",
+                    idx + 1000,
+                    idx % 10
+                )
+                .as_str(),
+            );
+        }
+        for idx in 0..10 {
+            dump.push_str(
+                format!(
+                    "INSERT INTO public.users (id, username) VALUES ({}, 'user-{}');\n",
+                    idx, idx
+                )
+                .as_str(),
+            );
+        }
+
+        let t1: Box<dyn Transformer> = Box::new(RandomTransformer::new("public", "users", "id"));
+        let foreign_keys = vec![ForeignKeyConfig {
+            database: "public".to_string(),
+            table: "orders".to_string(),
+            column: "user_id".to_string(),
+            referenced_table: "users".to_string(),
+            referenced_column: "id".to_string(),
+        }];
+        let transformers = link_foreign_keys(vec![t1], &foreign_keys).unwrap();
+
+        let source_options = SourceOptions {
+            transformers: &transformers,
+            skip_config: &vec![],
+            database_subset: &None,
+            only_tables: &vec![],
+            schema_only_tables: &vec![],
+            max_table_size: None,
+            tmp_dir: None,
+            detect_pii_comments: false,
+            capture_sequences: false,
+            on_transform_error: None,
+            amplify: &vec![],
+            seed: None,
+        };
+
+        let mut rows = vec![];
+        read_and_transform(
+            BufReader::new(dump.as_bytes()),
+            source_options,
+            |_original_query, query| {
+                rows.push(String::from_utf8_lossy(query.data().as_slice()).to_string());
+            },
+        )
+        .unwrap();
+
+        // transformed value of `column` in the INSERT INTO queries of `table`, by original value
+        let values = |table: &str, column: usize, original_value: &dyn Fn(usize) -> String| {
+            rows.iter()
+                .filter(|row| row.starts_with(format!("INSERT INTO public.{} ", table).as_str()))
+                .enumerate()
+                .map(|(idx, row)| {
+                    let values = row.split("VALUES (").nth(1).unwrap();
+                    let value = values.split(", ").nth(column).unwrap();
+                    (
+                        original_value(idx),
+                        value.trim_end_matches(");").to_string(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let user_ids = values("users", 0, &|idx| idx.to_string());
+        let order_user_ids = values("orders", 1, &|idx| (idx % 10).to_string());
+        assert_eq!(user_ids.len(), 10);
+        assert_eq!(order_user_ids.len(), 100);
+
+        for (user_id, transformed_user_id) in &user_ids {
+            assert_ne!(user_id, transformed_user_id);
+
+            for (order_user_id, transformed_order_user_id) in &order_user_ids {
+                if order_user_id == user_id {
+                    assert_eq!(transformed_order_user_id, transformed_user_id);
+                }
+            }
+        }
+    }
+
+    /// dump with the transformers seeded with `seed`
+    fn seeded_dump(dump: &str, seed: u64) -> Vec<u8> {
+        let transformers: Vec<Box<dyn Transformer>> = vec![
+            Box::new(FirstNameTransformer::new(
+                "public",
+                "customers",
+                "first_name",
+            )),
+            Box::new(RandomTransformer::new("public", "customers", "last_name")),
+            Box::new(EmailTransformer::new(
+                "public",
+                "customers",
+                "contact_email",
+                Default::default(),
+            )),
+        ];
+        let transformers = seed_transformers(transformers, seed);
+
+        let source_options = SourceOptions {
+            transformers: &transformers,
+            skip_config: &vec![],
+            database_subset: &None,
+            only_tables: &vec![],
+            schema_only_tables: &vec![],
+            max_table_size: None,
+            tmp_dir: None,
+            detect_pii_comments: false,
+            capture_sequences: false,
+            on_transform_error: None,
+            amplify: &vec![],
+            seed: None,
+        };
+
+        let mut output = vec![];
+        read_and_transform(
+            BufReader::new(dump.as_bytes()),
+            source_options,
+            |_original_query, query| {
+                output.extend_from_slice(query.data().as_slice());
+            },
+        )
+        .unwrap();
+
+        output
+    }
+
+    #[test]
+    fn dump_the_same_values_with_the_same_seed() {
+        let dump = r#"
+INSERT INTO public.customers (id, first_name, last_name, contact_email) VALUES ('ALFKI', 'Maria', 'Anders', 'maria.anders@gmail.com');
+INSERT INTO public.customers (id, first_name, last_name, contact_email) VALUES ('ANATR', 'Ana', 'Trujillo', 'ana@factchecker.com');
+INSERT INTO public.customers (id, first_name, last_name, contact_email) VALUES ('ANTON', 'Antonio', 'Moreno', NULL);
+"#;
+
+        let first_dump = seeded_dump(dump, 42);
+        assert_eq!(seeded_dump(dump, 42), first_dump);
+
+        let first_dump = String::from_utf8(first_dump).unwrap();
+        assert!(!first_dump.contains("maria.anders@gmail.com"));
+        assert!(!first_dump.contains("'Trujillo'"));
+    }
+
+    /// INSERT INTO query of a payment whose card number is not a credit card number, with the `on_transform_error` policy
+    fn transform_invalid_credit_card(
+        on_transform_error: Option<OnTransformError>,
+    ) -> Result<String, Error> {
+        let dump = r#"
+INSERT INTO public.payments (payment_id, card_number) VALUES (1, 'see the invoice');
+"#;
+
+        let t1: Box<dyn Transformer> = Box::new(CreditCardTransformer::new(
+            "public",
+            "payments",
+            "card_number",
+        ));
+        let transformers = vec![t1];
+
+        let source_options = SourceOptions {
+            transformers: &transformers,
+            skip_config: &vec![],
+            database_subset: &None,
+            only_tables: &vec![],
+            schema_only_tables: &vec![],
+            max_table_size: None,
+            tmp_dir: None,
+            detect_pii_comments: false,
+            capture_sequences: false,
+            on_transform_error,
+            amplify: &vec![],
+            seed: None,
+        };
+
+        let mut rows = vec![];
+        read_and_transform(
+            BufReader::new(dump.as_bytes()),
+            source_options,
+            |_original_query, query| {
+                rows.push(String::from_utf8_lossy(query.data().as_slice()).to_string());
+            },
+        )?;
+
+        Ok(rows
+            .into_iter()
+            .find(|x| x.starts_with("INSERT INTO"))
+            .unwrap())
+    }
+
+    #[test]
+    fn transform_an_invalid_value_without_policy() {
+        let insert_into = transform_invalid_credit_card(None).unwrap();
+        assert!(!insert_into.contains("'see the invoice'"));
+    }
+
+    #[test]
+    fn passthrough_an_invalid_value() {
+        let insert_into =
+            transform_invalid_credit_card(Some(OnTransformError::Passthrough)).unwrap();
+        assert!(insert_into.contains("(1, 'see the invoice')"));
+    }
+
+    #[test]
+    fn blank_an_invalid_value() {
+        let insert_into = transform_invalid_credit_card(Some(OnTransformError::Blank)).unwrap();
+        assert!(insert_into.contains("(1, '')"));
+    }
+
+    #[test]
+    fn abort_on_an_invalid_value() {
+        let err = transform_invalid_credit_card(Some(OnTransformError::Abort)).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("credit-card transformer cannot transform public.payments.card_number"));
+    }
+
+    #[test]
+    fn keep_the_transformed_values_of_enum_columns_within_their_labels() {
+        let dump = r#"
+CREATE TYPE public.mood AS ENUM (
+    'sad',
+    'ok',
+    'happy'
+);
+CREATE TABLE public.people (
+    id integer NOT NULL,
+    name character varying(30),
+    mood public.mood,
+    previous_moods public.mood[],
+    backup_mood public.mood
+);
+INSERT INTO public.people (id, name, mood, previous_moods, backup_mood) VALUES (1, 'Nancy', 'happy', '{sad,ok}', 'sad');
+"#;
+
+        let t1: Box<dyn Transformer> = Box::new(RandomTransformer::new("public", "people", "mood"));
+        let t2: Box<dyn Transformer> =
+            Box::new(TransientTransformer::new("public", "people", "backup_mood"));
+        let t3: Box<dyn Transformer> = Box::new(RandomTransformer::new("public", "people", "name"));
+        let transformers = vec![t1, t2, t3];
+
+        for _ in 0..10 {
+            let source_options = SourceOptions {
+                transformers: &transformers,
+                skip_config: &vec![],
+                database_subset: &None,
+                only_tables: &vec![],
+                schema_only_tables: &vec![],
+                max_table_size: None,
+                tmp_dir: None,
+                detect_pii_comments: false,
+                capture_sequences: false,
+                on_transform_error: None,
+                amplify: &vec![],
+                seed: None,
+            };
+
+            let mut rows = vec![];
+            read_and_transform(
+                BufReader::new(dump.as_bytes()),
+                source_options,
+                |_original_query, query| {
+                    rows.push(String::from_utf8_lossy(query.data().as_slice()).to_string());
+                },
+            )
+            .unwrap();
+
+            assert!(rows
+                .iter()
+                .any(|x| x.starts_with("CREATE TYPE public.mood")));
+
+            let insert_into = rows.iter().find(|x| x.starts_with("INSERT INTO")).unwrap();
+
+            // the random value of the mood is remapped to one of the labels, the name is not an enum
+            assert!(!insert_into.contains("'Nancy'"));
+            assert!(["sad", "ok", "happy"].iter().any(|label| {
+                insert_into.ends_with(&format!(", '{}', '{{sad,ok}}', 'sad');", label))
+            }));
+        }
+    }
+
+    #[test]
+    fn pick_the_same_enum_label_with_a_seed() {
+        let dump = r#"
+CREATE TYPE public.mood AS ENUM (
+    'sad',
+    'ok',
+    'happy'
+);
+CREATE TABLE public.people (
+    id integer NOT NULL,
+    mood public.mood
+);
+INSERT INTO public.people (id, mood) VALUES (1, 'happy');
+"#;
+
+        let t1: Box<dyn Transformer> = Box::new(RandomTransformer::new("public", "people", "mood"));
+        let transformers = seed_transformers(vec![t1], 42);
+
+        let insert_into = || {
+            let source_options = SourceOptions {
+                transformers: &transformers,
+                skip_config: &vec![],
+                database_subset: &None,
+                only_tables: &vec![],
+                schema_only_tables: &vec![],
+                max_table_size: None,
+                tmp_dir: None,
+                detect_pii_comments: false,
+                capture_sequences: false,
+                on_transform_error: None,
+                amplify: &vec![],
+                seed: Some(42),
+            };
+
+            let mut rows = vec![];
+            read_and_transform(
+                BufReader::new(dump.as_bytes()),
+                source_options,
+                |_original_query, query| {
+                    rows.push(String::from_utf8_lossy(query.data().as_slice()).to_string());
+                },
+            )
+            .unwrap();
+
+            rows.into_iter()
+                .find(|x| x.starts_with("INSERT INTO"))
+                .unwrap()
+        };
+
+        // the random value of the mood is remapped to the same label on every dump
+        let first_insert_into = insert_into();
+        for _ in 0..10 {
+            assert_eq!(insert_into(), first_insert_into);
+        }
+    }
+
+    #[test]
+    fn build_the_corporate_email_from_the_transformed_names() {
+        let dump = r#"
+INSERT INTO public.employees (employee_id, email, first_name, last_name) VALUES (1, 'nancy.davolio@northwind.com', 'Nancy', 'Davolio');
+"#;
+
+        let corporate_email_options = CorporateEmailOptions {
+            first_name_column: "first_name".to_string(),
+            last_name_column: "last_name".to_string(),
+            domain: "acme.example".to_string(),
+        };
+        let t1: Box<dyn Transformer> = Box::new(CorporateEmailTransformer::new(
+            "public",
+            "employees",
+            "email",
+            corporate_email_options,
+        ));
+        let t2: Box<dyn Transformer> = Box::new(KeepFirstCharTransformer::new(
+            "public",
+            "employees",
+            "first_name",
+            KeepFirstCharOptions::default(),
+        ));
+        let t3: Box<dyn Transformer> = Box::new(KeepFirstCharTransformer::new(
+            "public",
+            "employees",
+            "last_name",
+            KeepFirstCharOptions::default(),
+        ));
+        // the wrapping transformers keep building the email from the transformed names
+        let transformers = seed_transformers(vec![t1, t2, t3], 42);
+
+        let source_options = SourceOptions {
+            transformers: &transformers,
+            skip_config: &vec![],
+            database_subset: &None,
+            only_tables: &vec![],
+            schema_only_tables: &vec![],
+            max_table_size: None,
+            tmp_dir: None,
+            detect_pii_comments: false,
+            capture_sequences: false,
+            on_transform_error: None,
+            amplify: &vec![],
+            seed: None,
+        };
+
+        let mut rows = vec![];
+        read_and_transform(
+            BufReader::new(dump.as_bytes()),
+            source_options,
+            |_original_query, query| {
+                rows.push(String::from_utf8_lossy(query.data().as_slice()).to_string());
+            },
+        )
+        .unwrap();
+
+        let insert_into = rows.iter().find(|x| x.starts_with("INSERT INTO")).unwrap();
+        assert!(insert_into.contains("'n.d@acme.example'"));
+        assert!(!insert_into.to_lowercase().contains("nancy"));
+    }
+
+    #[test]
+    fn get_the_column_types_of_a_create_table_query() {
+        let q = "CREATE TABLE public.people (id integer NOT NULL, \"Mood\" public.mood DEFAULT 'ok'::public.mood, moods public.mood[], tags text, CONSTRAINT people_pkey PRIMARY KEY (id));";
+        let tokens = get_tokens_from_query_str(q);
+
+        assert_eq!(
+            get_column_types(&tokens),
+            vec![
+                ("id".to_string(), "integer".to_string()),
+                ("\"Mood\"".to_string(), "public.mood".to_string()),
+                ("tags".to_string(), "text".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn amplify_table_rows() {
+        let dump = r#"
+INSERT INTO public.employees (employee_id, first_name, last_name) VALUES (1, 'Nancy', 'Davolio');
+INSERT INTO public.employees (employee_id, first_name, last_name) VALUES (2, 'Andrew', 'Fuller');
+INSERT INTO public.orders (order_id, employee_id) VALUES (10248, 1);
+"#;
+
+        let t1: Box<dyn Transformer> =
+            Box::new(RandomTransformer::new("public", "employees", "last_name"));
+        let transformers = vec![t1];
+
+        let amplify = vec![AmplifyConfig {
+            database: "public".to_string(),
+            table: "employees".to_string(),
+            factor: 3,
+            primary_key: "employee_id".to_string(),
+        }];
+
+        let source_options = SourceOptions {
+            transformers: &transformers,
+            skip_config: &vec![],
+            database_subset: &None,
+            only_tables: &vec![],
+            schema_only_tables: &vec![],
+            max_table_size: None,
+            tmp_dir: None,
+            detect_pii_comments: false,
+            capture_sequences: false,
+            on_transform_error: None,
+            amplify: &amplify,
+            seed: None,
+        };
+
+        let mut rows = vec![];
+        read_and_transform(
+            BufReader::new(dump.as_bytes()),
+            source_options,
+            |_original_query, query| {
+                rows.push(String::from_utf8_lossy(query.data().as_slice()).to_string());
+            },
+        )
+        .unwrap();
+
+        let employees = rows
+            .iter()
+            .filter(|x| x.starts_with("INSERT INTO public.employees"))
+            .collect::<Vec<_>>();
+
+        assert_eq!(employees.len(), 6);
+
+        // ids of the rows are unique, the original ones are kept
+        let ids = employees
+            .iter()
+            .map(|x| {
+                x.split("VALUES (")
+                    .nth(1)
+                    .unwrap()
+                    .split(',')
+                    .next()
+                    .unwrap()
+            })
+            .collect::<HashSet<_>>();
+
+        assert_eq!(ids.len(), 6);
+        assert!(ids.contains("1"));
+        assert!(ids.contains("2"));
+
+        // the other tables are not amplified
+        assert_eq!(
+            rows.iter()
+                .filter(|x| x.starts_with("INSERT INTO public.orders"))
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn fail_to_amplify_a_table_without_the_primary_key_column() {
+        let dump = r#"
+INSERT INTO public.employees (employee_id, first_name, last_name) VALUES (1, 'Nancy', 'Davolio');
+"#;
+
+        let amplify = vec![AmplifyConfig {
+            database: "public".to_string(),
+            table: "employees".to_string(),
+            factor: 3,
+            primary_key: "id".to_string(),
+        }];
+
+        let source_options = SourceOptions {
+            transformers: &vec![],
+            skip_config: &vec![],
+            database_subset: &None,
+            only_tables: &vec![],
+            schema_only_tables: &vec![],
+            max_table_size: None,
+            tmp_dir: None,
+            detect_pii_comments: false,
+            capture_sequences: false,
+            on_transform_error: None,
+            amplify: &amplify,
+            seed: None,
+        };
+
+        let mut rows = vec![];
+        let result = read_and_transform(
+            BufReader::new(dump.as_bytes()),
+            source_options,
+            |_original_query, query| {
+                rows.push(String::from_utf8_lossy(query.data().as_slice()).to_string());
+            },
+        );
+
+        // the copies would have the same primary key as the original row
+        assert!(result.unwrap_err().to_string().contains("'id'"));
+        assert!(!rows.iter().any(|x| x.starts_with("INSERT INTO")));
+    }
+
+    #[test]
+    fn amplify_table_rows_with_a_seed() {
+        let dump = r#"
+INSERT INTO public.employees (employee_id, first_name, last_name) VALUES (1, 'Nancy', 'Davolio');
+"#;
+
+        let t1: Box<dyn Transformer> =
+            Box::new(RandomTransformer::new("public", "employees", "last_name"));
+        let transformers = seed_transformers(vec![t1], 42);
+
+        let amplify = vec![AmplifyConfig {
+            database: "public".to_string(),
+            table: "employees".to_string(),
+            factor: 3,
+            primary_key: "employee_id".to_string(),
+        }];
+
+        let last_names = || {
+            let source_options = SourceOptions {
+                transformers: &transformers,
+                skip_config: &vec![],
+                database_subset: &None,
+                only_tables: &vec![],
+                schema_only_tables: &vec![],
+                max_table_size: None,
+                tmp_dir: None,
+                detect_pii_comments: false,
+                capture_sequences: false,
+                on_transform_error: None,
+                amplify: &amplify,
+                seed: Some(42),
+            };
+
+            let mut rows = vec![];
+            read_and_transform(
+                BufReader::new(dump.as_bytes()),
+                source_options,
+                |_original_query, query| {
+                    rows.push(String::from_utf8_lossy(query.data().as_slice()).to_string());
+                },
+            )
+            .unwrap();
+
+            rows.iter()
+                .filter(|x| x.starts_with("INSERT INTO public.employees"))
+                .map(|x| x.rsplit('\'').nth(1).unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        // each copy gets other values than the original row, the same ones on every dump
+        let first_last_names = last_names();
+        assert_eq!(first_last_names.len(), 3);
+        assert_eq!(first_last_names.iter().collect::<HashSet<_>>().len(), 3);
+        assert_eq!(last_names(), first_last_names);
+    }
 }
@@ -1,6 +1,7 @@
-use std::io::{stdin, BufReader, Error};
+use std::io::{stdin, Error};
 
 use crate::connector::Connector;
+use crate::source::decompress_reader;
 use crate::source::postgres::{read_and_transform, subset};
 use crate::types::{OriginalQuery, Query};
 use crate::Source;
@@ -35,13 +36,13 @@ impl Source for PostgresStdin {
     ) -> Result<(), Error> {
         match &options.database_subset {
             None => {
-                let reader = BufReader::new(stdin());
-                read_and_transform(reader, options, query_callback);
+                let reader = decompress_reader(stdin())?;
+                read_and_transform(reader, options, query_callback)?;
             }
             Some(subset_config) => {
-                let dump_reader = BufReader::new(stdin());
-                let reader = subset(dump_reader, subset_config)?;
-                read_and_transform(reader, options, query_callback);
+                let dump_reader = decompress_reader(stdin())?;
+                let reader = subset(dump_reader, subset_config, options.tmp_dir)?;
+                read_and_transform(reader, options, query_callback)?;
             }
         };
 
@@ -0,0 +1,172 @@
+use std::io::{Error, Write};
+
+use serde::Serialize;
+
+use crate::utils::epoch_millis;
+
+/// number of rows of a table between two `rows_processed` events
+const ROWS_PROCESSED_INTERVAL: usize = 1000;
+
+/// Event of the lifecycle of a dump, for the tools tracking its progress. The rows are the `INSERT INTO` statements of
+/// the dump - one by row for PostgreSQL and MySQL.
+#[derive(Debug, Serialize, PartialEq, Clone)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    DumpStarted,
+    TableStarted {
+        table: String,
+    },
+    /// rows of the table processed so far
+    RowsProcessed {
+        table: String,
+        rows: usize,
+    },
+    TableFinished {
+        table: String,
+        rows: usize,
+    },
+    DumpFinished {
+        rows: usize,
+        bytes: usize,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// event with the time it was emitted at, as written in the NDJSON output
+#[derive(Serialize)]
+struct TimedEvent<'a> {
+    timestamp: u64,
+    #[serde(flatten)]
+    event: &'a Event,
+}
+
+/// Emit the events of a dump from the queries it reads, if a callback is set.
+#[derive(Default)]
+pub struct DumpEvents<'a> {
+    callback: Option<Box<dyn FnMut(Event) + 'a>>,
+    /// table of the last `INSERT INTO` statement and its number of rows
+    table: Option<(String, usize)>,
+    rows: usize,
+}
+
+impl<'a> DumpEvents<'a> {
+    pub fn new<F: FnMut(Event) + 'a>(callback: F) -> Self {
+        DumpEvents {
+            callback: Some(Box::new(callback)),
+            table: None,
+            rows: 0,
+        }
+    }
+
+    /// write the events as JSON, one per line - each line is flushed so the events can be followed live
+    pub fn ndjson<W: Write + 'a>(mut writer: W) -> Self {
+        DumpEvents::new(move |event| {
+            let event = TimedEvent {
+                timestamp: epoch_millis() as u64,
+                event: &event,
+            };
+
+            if let Ok(line) = serde_json::to_string(&event) {
+                let _ = writeln!(writer, "{}", line);
+                let _ = writer.flush();
+            }
+        })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.callback.is_some()
+    }
+
+    fn emit(&mut self, event: Event) {
+        if let Some(callback) = self.callback.as_mut() {
+            callback(event);
+        }
+    }
+
+    pub fn dump_started(&mut self) {
+        self.emit(Event::DumpStarted);
+    }
+
+    /// a query of the dump is read - `table` is the table of an `INSERT INTO` statement, none for the other statements
+    pub fn query_read(&mut self, table: Option<&str>) {
+        let table = match table {
+            Some(table) => table,
+            // a table is finished when the rows of another table start, the statements in between are not rows
+            None => return,
+        };
+
+        match self.table.as_mut() {
+            Some((current_table, rows)) if current_table == table => *rows += 1,
+            _ => {
+                self.table_finished();
+                self.emit(Event::TableStarted {
+                    table: table.to_string(),
+                });
+                self.table = Some((table.to_string(), 1));
+            }
+        }
+
+        self.rows += 1;
+
+        if let Some((table, rows)) = self.table.clone() {
+            if rows % ROWS_PROCESSED_INTERVAL == 0 {
+                self.emit(Event::RowsProcessed { table, rows });
+            }
+        }
+    }
+
+    fn table_finished(&mut self) {
+        if let Some((table, rows)) = self.table.take() {
+            self.emit(Event::TableFinished { table, rows });
+        }
+    }
+
+    pub fn dump_finished(&mut self, bytes: usize) {
+        self.table_finished();
+        let rows = self.rows;
+        self.emit(Event::DumpFinished { rows, bytes });
+    }
+
+    pub fn error(&mut self, err: &Error) {
+        self.emit(Event::Error {
+            message: err.to_string(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Error, ErrorKind};
+
+    use serde_json::Value;
+
+    use crate::tasks::events::DumpEvents;
+
+    #[test]
+    fn write_one_json_event_per_line() {
+        let mut output = vec![];
+
+        {
+            let mut events = DumpEvents::ndjson(&mut output);
+            events.dump_started();
+            events.query_read(Some("public.orders"));
+            events.error(&Error::new(ErrorKind::Other, "connection lost"));
+        }
+
+        let events = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0]["event"], "dump_started");
+        assert_eq!(events[1]["event"], "table_started");
+        assert_eq!(events[1]["table"], "public.orders");
+        assert_eq!(events[2]["event"], "error");
+        assert_eq!(events[2]["message"], "connection lost");
+        assert!(events.iter().all(|event| event["timestamp"].is_u64()));
+    }
+}
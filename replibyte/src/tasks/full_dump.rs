@@ -2,13 +2,18 @@ use std::io::{Error, ErrorKind};
 use std::sync::mpsc;
 use std::thread;
 
-use crate::datastore::Datastore;
+use sha2::{Digest, Sha256};
+
+use crate::datastore::{Datastore, DumpTable};
 use crate::source::SourceOptions;
+use crate::tasks::events::DumpEvents;
 use crate::tasks::{MaxBytes, Message, Task, TransferredBytes};
 use crate::types::{to_bytes, Queries};
+use crate::utils::epoch_millis;
 use crate::Source;
 
-type DataMessage = (u16, Queries);
+/// part number, table of the part when the dump is split per table, and queries of the part
+type DataMessage = (u16, Option<String>, Queries);
 
 /// FullDumpTask is a wrapping struct to execute the synchronization between a *Source* and a *Datastore*
 pub struct FullDumpTask<'a, S>
@@ -18,6 +23,8 @@ where
     source: S,
     datastore: Box<dyn Datastore>,
     options: SourceOptions<'a>,
+    split_by_table: bool,
+    events: DumpEvents<'a>,
 }
 
 impl<'a, S> FullDumpTask<'a, S>
@@ -29,8 +36,38 @@ where
             source,
             datastore,
             options,
+            split_by_table: false,
+            events: DumpEvents::default(),
         }
     }
+
+    /// write the rows of each table in their own parts, listed in the index file, instead of one stream of parts
+    pub fn set_split_by_table(&mut self, split_by_table: bool) {
+        self.split_by_table = split_by_table;
+    }
+
+    /// emit the events of the lifecycle of the dump (tables started and finished, rows processed, errors)
+    pub fn set_events(&mut self, events: DumpEvents<'a>) {
+        self.events = events;
+    }
+}
+
+/// table of an `INSERT INTO` statement (e.g. `public.orders`), none for the other statements
+fn insert_into_table(query: &[u8]) -> Option<String> {
+    // the table name is at the start of the statement, no need to decode a large insert
+    let query = String::from_utf8_lossy(&query[..query.len().min(1024)]);
+    let query = query.trim_start();
+
+    if !query.get(..12)?.eq_ignore_ascii_case("insert into ") {
+        return None;
+    }
+
+    let table = query[12..]
+        .trim_start()
+        .split(|c: char| c.is_whitespace() || c == '(')
+        .next()?;
+
+    Some(table.replace(['`', '"'], ""))
 }
 
 impl<'a, S> Task for FullDumpTask<'a, S>
@@ -41,32 +78,69 @@ where
         mut self,
         mut progress_callback: F,
     ) -> Result<(), Error> {
+        let mut events = self.events;
+        // the next incremental dump starts from here - the rows changed while the source is read are in it too
+        let started_at = epoch_millis();
+
         // initialize the source
-        let _ = self.source.init()?;
+        if let Err(err) = self.source.init() {
+            events.error(&err);
+            return Err(err);
+        }
 
         let (tx, rx) = mpsc::sync_channel::<Message<DataMessage>>(1);
-        let datastore = self.datastore;
+        let mut datastore = self.datastore;
+        // the parts are written under a temporary name, and deleted if the dump fails before being committed
+        datastore.set_temporary_dump();
 
         let join_handle = thread::spawn(move || -> Result<(), Error> {
             // managing Datastore (S3) upload here
-            let datastore = datastore;
+            let mut datastore = datastore;
+            let mut dump_tables: Vec<DumpTable> = vec![];
+            let mut dump_size = 0usize;
+            // hash the dump part by part, it is never held in memory as a whole
+            let mut hasher = Sha256::new();
 
             loop {
-                let result = match rx.recv() {
-                    Ok(Message::Data((chunk_part, queries))) => Ok((chunk_part, queries)),
+                let (chunk_part, table, queries) = match rx.recv() {
+                    Ok(Message::Data((chunk_part, table, queries))) => (chunk_part, table, queries),
                     Ok(Message::EOF) => break,
-                    Err(err) => Err(Error::new(ErrorKind::Other, format!("{}", err))),
+                    // the source stopped before the end of the dump - it must not be committed
+                    Err(err) => {
+                        let _ = datastore.discard();
+                        return Err(Error::new(ErrorKind::Other, format!("{}", err)));
+                    }
                 };
 
-                if let Ok((chunk_part, queries)) = result {
-                    let _ = match datastore.write(chunk_part, to_bytes(queries)) {
-                        Ok(_) => {}
-                        Err(err) => return Err(Error::new(ErrorKind::Other, format!("{}", err))),
-                    };
+                let data = to_bytes(queries);
+                hasher.update(&data);
+
+                dump_size += match datastore.write(chunk_part, data) {
+                    Ok(size) => size,
+                    Err(err) => {
+                        let _ = datastore.discard();
+                        return Err(Error::new(ErrorKind::Other, format!("{}", err)));
+                    }
+                };
+
+                if let Some(table) = table {
+                    match dump_tables.iter_mut().find(|t| t.name == table) {
+                        Some(dump_table) => dump_table.parts.push(chunk_part),
+                        None => dump_tables.push(DumpTable {
+                            name: table,
+                            parts: vec![chunk_part],
+                        }),
+                    }
                 }
             }
 
-            Ok(())
+            if !dump_tables.is_empty() {
+                datastore.set_dump_tables(dump_tables);
+            }
+            datastore.set_dump_started_at(started_at);
+
+            // all the parts are written, the dump can be listed and restored
+            datastore.commit(dump_size, Some(format!("{:x}", hasher.finalize())))
         });
 
         // buffer of 100MB in memory to use and re-use to upload data into datastore
@@ -75,6 +149,12 @@ where
         let mut consumed_buffer_size = 0usize;
         let mut total_transferred_bytes = 0usize;
         let mut chunk_part = 0u16;
+        // table of the queries in the buffer when the dump is split per table
+        let mut table = None;
+        let split_by_table = self.split_by_table;
+        let events_enabled = events.is_enabled();
+
+        events.dump_started();
 
         // init progress
         progress_callback(
@@ -82,18 +162,34 @@ where
             buffer_size * (chunk_part as usize + 1),
         );
 
-        let _ = self.source.read(self.options, |_original_query, query| {
-            if consumed_buffer_size + query.data().len() > buffer_size {
+        let read_result = self.source.read(self.options, |_original_query, query| {
+            let insert_table = match split_by_table || events_enabled {
+                true => insert_into_table(query.data()),
+                false => None,
+            };
+            events.query_read(insert_table.as_deref());
+
+            let query_table = match split_by_table {
+                true => insert_table,
+                false => None,
+            };
+
+            // a new part starts with each table when the dump is split per table
+            let is_new_table = !queries.is_empty() && query_table != table;
+
+            if is_new_table || consumed_buffer_size + query.data().len() > buffer_size {
                 chunk_part += 1;
                 consumed_buffer_size = 0;
                 // TODO .clone() - look if we do not consume more mem
 
-                let message = Message::Data((chunk_part, queries.clone()));
+                let message = Message::Data((chunk_part, table.clone(), queries.clone()));
 
                 let _ = tx.send(message); // FIXME catch SendError?
                 let _ = queries.clear();
             }
 
+            table = query_table;
+
             consumed_buffer_size += query.data().len();
             total_transferred_bytes += query.data().len();
             progress_callback(
@@ -101,16 +197,440 @@ where
                 buffer_size * (chunk_part as usize + 1),
             );
             queries.push(query);
-        })?;
+        });
+
+        if let Err(err) = read_result {
+            // stop the upload and wait for the parts being written - the dump is not committed
+            drop(tx);
+            let _ = join_handle.join();
+            events.error(&err);
+            return Err(err);
+        }
 
         progress_callback(total_transferred_bytes, total_transferred_bytes);
 
         chunk_part += 1;
-        let _ = tx.send(Message::Data((chunk_part, queries)));
+        let _ = tx.send(Message::Data((chunk_part, table, queries)));
         let _ = tx.send(Message::EOF);
         // wait for end of upload execution
-        join_handle.join().unwrap()?;
+        if let Err(err) = join_handle.join().unwrap() {
+            events.error(&err);
+            return Err(err);
+        }
+
+        events.dump_finished(total_transferred_bytes);
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::fs::{read, read_dir, write};
+    use std::io::{Error, ErrorKind};
+    use std::path::Path;
+
+    use tempfile::tempdir;
+
+    use crate::connector::Connector;
+    use crate::datastore::local_disk::LocalDisk;
+    use crate::datastore::multi::MultiDatastore;
+    use crate::datastore::{Datastore, DumpTable};
+    use crate::source::{Source, SourceOptions};
+    use crate::tasks::events::{DumpEvents, Event};
+    use crate::tasks::full_dump::{insert_into_table, FullDumpTask};
+    use crate::tasks::Task;
+    use crate::types::{OriginalQuery, Query};
+
+    /// source sending two queries of 51MB (so the first one is written on its own), then failing if `fail` is set
+    struct TestSource {
+        fail: bool,
+    }
+
+    impl Connector for TestSource {
+        fn init(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+
+    impl Source for TestSource {
+        fn read<F: FnMut(OriginalQuery, Query)>(
+            &self,
+            _options: SourceOptions,
+            mut query_callback: F,
+        ) -> Result<(), Error> {
+            for _ in 0..2 {
+                let query = Query(vec![b'a'; 51 * 1024 * 1024]);
+                query_callback(query.clone(), query);
+            }
+
+            match self.fail {
+                true => Err(Error::new(ErrorKind::Other, "connection lost")),
+                false => Ok(()),
+            }
+        }
+    }
+
+    /// source sending the schema then the rows of two tables
+    struct TwoTablesSource;
+
+    impl Connector for TwoTablesSource {
+        fn init(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+
+    impl Source for TwoTablesSource {
+        fn read<F: FnMut(OriginalQuery, Query)>(
+            &self,
+            _options: SourceOptions,
+            mut query_callback: F,
+        ) -> Result<(), Error> {
+            for query in [
+                "CREATE TABLE public.customers (id integer);",
+                "CREATE TABLE public.orders (id integer);",
+                "INSERT INTO public.customers (id) VALUES (1);",
+                "INSERT INTO public.customers (id) VALUES (2);",
+                "INSERT INTO public.orders (id) VALUES (1);",
+            ] {
+                let query = Query(query.as_bytes().to_vec());
+                query_callback(query.clone(), query);
+            }
+
+            Ok(())
+        }
+    }
+
+    /// source sending 1500 rows of a table then a row of another one, with other statements in between
+    struct ManyRowsSource;
+
+    impl Connector for ManyRowsSource {
+        fn init(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+
+    impl Source for ManyRowsSource {
+        fn read<F: FnMut(OriginalQuery, Query)>(
+            &self,
+            _options: SourceOptions,
+            mut query_callback: F,
+        ) -> Result<(), Error> {
+            let mut queries = vec!["CREATE TABLE public.customers (id integer);".to_string()];
+            for id in 1..=1500 {
+                queries.push(format!(
+                    "INSERT INTO public.customers (id) VALUES ({});",
+                    id
+                ));
+            }
+            queries.push("CREATE TABLE public.orders (id integer);".to_string());
+            queries.push("INSERT INTO public.orders (id) VALUES (1);".to_string());
+            queries.push("CREATE INDEX orders_id ON public.orders (id);".to_string());
+
+            for query in queries {
+                let query = Query(query.into_bytes());
+                query_callback(query.clone(), query);
+            }
+
+            Ok(())
+        }
+    }
+
+    fn run_dump<S: Source>(
+        source: S,
+        datastore: Box<dyn Datastore>,
+        split_by_table: bool,
+    ) -> Result<(), Error> {
+        run_dump_with_events(source, datastore, split_by_table, DumpEvents::default())
+    }
+
+    fn run_dump_with_events<S: Source>(
+        source: S,
+        mut datastore: Box<dyn Datastore>,
+        split_by_table: bool,
+        events: DumpEvents,
+    ) -> Result<(), Error> {
+        let _ = datastore.init().expect("datastore init failed");
+
+        let source_options = SourceOptions {
+            transformers: &vec![],
+            skip_config: &vec![],
+            database_subset: &None,
+            only_tables: &vec![],
+            schema_only_tables: &vec![],
+            max_table_size: None,
+            tmp_dir: None,
+            detect_pii_comments: false,
+            capture_sequences: false,
+            on_transform_error: None,
+            amplify: &vec![],
+            seed: None,
+        };
+
+        let mut task = FullDumpTask::new(source, datastore, source_options);
+        task.set_split_by_table(split_by_table);
+        task.set_events(events);
+        task.run(|_, _| {})
+    }
+
+    #[test]
+    fn aborted_dump_is_not_committed() {
+        let dir = tempdir().expect("cannot create tempdir");
+        let dir = dir.path().to_str().unwrap();
+
+        let written_dumps = || {
+            read_dir(dir)
+                .unwrap()
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().is_dir())
+                .map(|entry| entry.file_name().to_str().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let source = TestSource { fail: true };
+        assert!(run_dump(source, Box::new(LocalDisk::new(dir)), false).is_err());
+        assert!(LocalDisk::new(dir).index_file().unwrap().dumps.is_empty());
+        // the first part was written, it is deleted with the dump
+        assert!(written_dumps().is_empty());
+
+        let mut local_disk = LocalDisk::new(dir);
+        local_disk.set_dump_name("nightly".to_string());
+        let source = TestSource { fail: false };
+        assert!(run_dump(source, Box::new(local_disk), false).is_ok());
+        let dumps = LocalDisk::new(dir).index_file().unwrap().dumps;
+        assert_eq!(dumps.len(), 1);
+        assert!(dumps[0].size > 0);
+        // the dump is renamed once committed
+        assert_eq!(dumps[0].directory_name, "nightly");
+        assert_eq!(written_dumps(), vec!["nightly".to_string()]);
+    }
+
+    #[test]
+    fn aborted_dump_keeps_the_dump_it_replaces() {
+        let dir = tempdir().expect("cannot create tempdir");
+        let dir = dir.path().to_str().unwrap();
+
+        let mut local_disk = LocalDisk::new(dir);
+        local_disk.set_dump_name("nightly".to_string());
+        assert!(run_dump(TwoTablesSource, Box::new(local_disk), false).is_ok());
+        let checksum = LocalDisk::new(dir).index_file().unwrap().dumps[0]
+            .checksum
+            .clone();
+
+        let mut local_disk = LocalDisk::new(dir);
+        local_disk.set_replaced_dump("nightly".to_string());
+        let source = TestSource { fail: true };
+        assert!(run_dump(source, Box::new(local_disk), false).is_err());
+
+        // no parts of the aborted dump are left, the replaced dump is untouched
+        let dumps = LocalDisk::new(dir).index_file().unwrap().dumps;
+        assert_eq!(dumps.len(), 1);
+        assert_eq!(dumps[0].checksum, checksum);
+        let written_dumps = read_dir(dir)
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_dir())
+            .count();
+        assert_eq!(written_dumps, 1);
+        assert!(LocalDisk::new(dir).verify("nightly").is_ok());
+    }
+
+    #[test]
+    fn dump_records_when_the_source_started_to_be_read() {
+        let dir = tempdir().expect("cannot create tempdir");
+        let dir = dir.path().to_str().unwrap();
+
+        let source = TestSource { fail: false };
+        assert!(run_dump(source, Box::new(LocalDisk::new(dir)), false).is_ok());
+        let dumps = LocalDisk::new(dir).index_file().unwrap().dumps;
+
+        // the dump is committed once its parts are written, after the source started to be read
+        let started_at = dumps[0].started_at.unwrap();
+        assert!(started_at < dumps[0].created_at);
+    }
+
+    #[test]
+    fn tampered_dump_fails_verification() {
+        let dir = tempdir().expect("cannot create tempdir");
+        let dir = dir.path().to_str().unwrap();
+
+        let source = TestSource { fail: false };
+        assert!(run_dump(source, Box::new(LocalDisk::new(dir)), false).is_ok());
+        let local_disk = LocalDisk::new(dir);
+        let dump_name = local_disk.index_file().unwrap().dumps[0]
+            .directory_name
+            .clone();
+        assert!(local_disk.verify(dump_name.as_str()).is_ok());
+
+        // alter one byte of the last part
+        let part_path = format!("{}/{}/2.dump", dir, dump_name);
+        let mut data = read(&part_path).unwrap();
+        let last_byte = data.len() - 1;
+        data[last_byte] ^= 1;
+        write(&part_path, data).unwrap();
+
+        assert!(local_disk.verify(dump_name.as_str()).is_err());
+    }
+
+    #[test]
+    fn dump_is_written_to_all_the_datastores() {
+        let primary = tempdir().expect("cannot create tempdir");
+        let primary = primary.path().to_str().unwrap();
+        let mirror = tempdir().expect("cannot create tempdir");
+        let mirror = mirror.path().to_str().unwrap();
+
+        let datastore = MultiDatastore::new(vec![
+            Box::new(LocalDisk::new(primary)),
+            Box::new(LocalDisk::new(mirror)),
+        ]);
+        let source = TestSource { fail: false };
+        assert!(run_dump(source, Box::new(datastore), false).is_ok());
+
+        // each datastore has its own index file, with the same dump
+        let primary_dumps = LocalDisk::new(primary).index_file().unwrap().dumps;
+        let mirror_dumps = LocalDisk::new(mirror).index_file().unwrap().dumps;
+        assert_eq!(primary_dumps.len(), 1);
+        assert_eq!(
+            primary_dumps[0].directory_name,
+            mirror_dumps[0].directory_name
+        );
+        assert_eq!(primary_dumps[0].checksum, mirror_dumps[0].checksum);
+
+        let dump_name = primary_dumps[0].directory_name.as_str();
+        assert!(LocalDisk::new(primary).verify(dump_name).is_ok());
+        assert!(LocalDisk::new(mirror).verify(dump_name).is_ok());
+    }
+
+    #[test]
+    fn split_dump_writes_each_table_in_its_own_part() {
+        let dir = tempdir().expect("cannot create tempdir");
+        let dir = dir.path().to_str().unwrap();
+
+        assert!(run_dump(TwoTablesSource, Box::new(LocalDisk::new(dir)), true).is_ok());
+        let dumps = LocalDisk::new(dir).index_file().unwrap().dumps;
+        assert_eq!(dumps.len(), 1);
+
+        // the schema is in the first part, then each table has its own part
+        let tables = dumps[0].tables.as_ref().unwrap();
+        assert_eq!(
+            tables,
+            &vec![
+                DumpTable {
+                    name: "public.customers".to_string(),
+                    parts: vec![2],
+                },
+                DumpTable {
+                    name: "public.orders".to_string(),
+                    parts: vec![3],
+                },
+            ]
+        );
+
+        for part in ["1.dump", "2.dump", "3.dump"] {
+            let part_path = format!("{}/{}/{}", dir, dumps[0].directory_name, part);
+            assert!(Path::new(&part_path).exists());
+        }
+        assert!(LocalDisk::new(dir)
+            .verify(dumps[0].directory_name.as_str())
+            .is_ok());
+    }
+
+    #[test]
+    fn dump_is_not_split_by_default() {
+        let dir = tempdir().expect("cannot create tempdir");
+        let dir = dir.path().to_str().unwrap();
+
+        assert!(run_dump(TwoTablesSource, Box::new(LocalDisk::new(dir)), false).is_ok());
+        let dumps = LocalDisk::new(dir).index_file().unwrap().dumps;
+        assert_eq!(dumps[0].tables, None);
+    }
+
+    #[test]
+    fn dump_emits_the_lifecycle_of_each_table() {
+        let dir = tempdir().expect("cannot create tempdir");
+        let dir = dir.path().to_str().unwrap();
+
+        let emitted = RefCell::new(vec![]);
+        let events = DumpEvents::new(|event| emitted.borrow_mut().push(event));
+        assert!(
+            run_dump_with_events(ManyRowsSource, Box::new(LocalDisk::new(dir)), false, events)
+                .is_ok()
+        );
+        let emitted = emitted.into_inner();
+
+        let customers = "public.customers".to_string();
+        let orders = "public.orders".to_string();
+        assert_eq!(
+            emitted[..emitted.len() - 1],
+            [
+                Event::DumpStarted,
+                Event::TableStarted {
+                    table: customers.clone(),
+                },
+                Event::RowsProcessed {
+                    table: customers.clone(),
+                    rows: 1000,
+                },
+                Event::TableFinished {
+                    table: customers,
+                    rows: 1500,
+                },
+                Event::TableStarted {
+                    table: orders.clone(),
+                },
+                Event::TableFinished {
+                    table: orders,
+                    rows: 1,
+                },
+            ]
+        );
+        assert!(matches!(
+            emitted.last(),
+            Some(Event::DumpFinished { rows: 1501, bytes }) if *bytes > 0
+        ));
+    }
+
+    #[test]
+    fn failed_dump_emits_an_error() {
+        let dir = tempdir().expect("cannot create tempdir");
+        let dir = dir.path().to_str().unwrap();
+
+        let emitted = RefCell::new(vec![]);
+        let events = DumpEvents::new(|event| emitted.borrow_mut().push(event));
+        let source = TestSource { fail: true };
+        assert!(
+            run_dump_with_events(source, Box::new(LocalDisk::new(dir)), false, events).is_err()
+        );
+
+        assert_eq!(
+            emitted.into_inner(),
+            vec![
+                Event::DumpStarted,
+                Event::Error {
+                    message: "connection lost".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn table_of_insert_into_statement() {
+        assert_eq!(
+            insert_into_table(b"INSERT INTO public.orders (id) VALUES (1);"),
+            Some("public.orders".to_string())
+        );
+        assert_eq!(
+            insert_into_table(b"INSERT INTO `orders` VALUES (1);"),
+            Some("orders".to_string())
+        );
+        assert_eq!(
+            insert_into_table(b"insert into \"Orders\"(id) values (1);"),
+            Some("Orders".to_string())
+        );
+        assert_eq!(
+            insert_into_table(b"CREATE TABLE public.orders (id integer);"),
+            None
+        );
+    }
+}
@@ -1,12 +1,26 @@
-use std::io::Error;
-use std::sync::mpsc;
+use std::collections::HashMap;
+use std::io::{BufReader, Error, ErrorKind};
+use std::sync::{mpsc, Mutex};
 use std::thread;
 
+use dump_parser::utils::{list_sql_queries_from_dump_reader, ListQueryResult};
+
 use crate::datastore::{Datastore, ReadOptions};
 use crate::destination::Destination;
 use crate::tasks::{MaxBytes, Message, Task, TransferredBytes};
+use crate::translation::copy_to_insert::CopyToInsert;
+use crate::translation::postgres_to_mysql::PostgresToMysql;
 use crate::types::Bytes;
 
+/// part of the dump statements to restore
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RestoreFilter {
+    /// the statements creating the database objects (e.g. tables, indexes, constraints)
+    SchemaOnly,
+    /// the statements writing the data (e.g. INSERT)
+    DataOnly,
+}
+
 /// FullRestoreTask is a wrapping struct to execute the synchronization between a *Datastore* and a *Source*.
 pub struct FullRestoreTask<'a, D>
 where
@@ -15,6 +29,11 @@ where
     destination: &'a mut D,
     datastore: Box<dyn Datastore>,
     read_options: ReadOptions,
+    translation: Option<PostgresToMysql>,
+    copy_to_insert: Option<CopyToInsert>,
+    workers: usize,
+    filter: Option<RestoreFilter>,
+    prerequisites_first: bool,
 }
 
 impl<'a, D> FullRestoreTask<'a, D>
@@ -30,13 +49,44 @@ where
             destination,
             datastore,
             read_options,
+            translation: None,
+            copy_to_insert: None,
+            workers: 1,
+            filter: None,
+            prerequisites_first: false,
         }
     }
+
+    /// translate the dump statements before writing them into the destination
+    pub fn set_translation(&mut self, translation: PostgresToMysql) {
+        self.translation = Some(translation);
+    }
+
+    /// write the `COPY ... FROM stdin` blocks of the dump as INSERT statements
+    pub fn set_copy_to_insert(&mut self, copy_to_insert: CopyToInsert) {
+        self.copy_to_insert = Some(copy_to_insert);
+    }
+
+    /// write the data of the tables with up to `workers` concurrent destination connections
+    pub fn set_workers(&mut self, workers: usize) {
+        self.workers = workers.max(1);
+    }
+
+    /// restore only the schema or only the data of the dump
+    pub fn set_filter(&mut self, filter: RestoreFilter) {
+        self.filter = Some(filter);
+    }
+
+    /// create the schemas, the extensions and the types of the dump before its other statements, and stop at the first
+    /// extension failing - for the psql destinations only
+    pub fn set_prerequisites_first(&mut self, prerequisites_first: bool) {
+        self.prerequisites_first = prerequisites_first;
+    }
 }
 
 impl<'a, D> Task for FullRestoreTask<'a, D>
 where
-    D: Destination,
+    D: Destination + Sync,
 {
     fn run<F: FnMut(TransferredBytes, MaxBytes)>(
         mut self,
@@ -81,7 +131,32 @@ where
 
             progress_callback(data.len(), dump.size);
 
-            let _ = self.destination.write(data)?;
+            let data = match self.copy_to_insert.as_mut() {
+                Some(copy_to_insert) => copy_to_insert.translate(data)?,
+                None => data,
+            };
+
+            let data = match self.translation.as_mut() {
+                Some(translation) => translation.translate(data)?,
+                None => data,
+            };
+
+            let data = match self.filter {
+                Some(filter) => filter_statements(data, filter)?,
+                None => data,
+            };
+
+            let data = if self.prerequisites_first {
+                write_prerequisite_statements(self.destination, data)?
+            } else {
+                data
+            };
+
+            if self.workers > 1 {
+                write_tables_concurrently(self.destination, data, self.workers)?;
+            } else {
+                self.destination.write(data)?;
+            }
         }
 
         // wait for end of download execution
@@ -92,3 +167,608 @@ where
         Ok(())
     }
 }
+
+/// Keep the statements of a dump part matching `filter` - the session statements (e.g. SET) are kept by both filters.
+/// The rows of a `COPY ... FROM stdin;` statement are kept with it.
+fn filter_statements(data: Bytes, filter: RestoreFilter) -> Result<Bytes, Error> {
+    let mut statements = String::new();
+    // rows read so far of the COPY block being read, and whether they are kept - the parser returns them as queries
+    let mut copy_rows: Option<(String, bool)> = None;
+
+    list_sql_queries_from_dump_reader(BufReader::new(data.as_slice()), |query| {
+        let (rows, keep) = match copy_rows.as_mut() {
+            Some(copy_rows) => copy_rows,
+            None => {
+                copy_rows = filter_statement(&mut statements, query, filter);
+                return ListQueryResult::Continue;
+            }
+        };
+
+        // the new lines between two queries are also part of the next query
+        if query != "\n" {
+            rows.push_str(query);
+        }
+
+        let end = match end_of_copy_rows(rows) {
+            Some(end) => end,
+            None => return ListQueryResult::Continue,
+        };
+
+        if *keep {
+            statements.push_str(rows[..end].trim_start_matches('\n'));
+            if !statements.ends_with('\n') {
+                statements.push('\n');
+            }
+        }
+
+        // the statement following the rows can be in the same query
+        let next_query = rows[end..].to_string();
+        copy_rows = filter_statement(&mut statements, next_query.as_str(), filter);
+
+        ListQueryResult::Continue
+    })
+    .map_err(|err| Error::new(ErrorKind::Other, format!("{:?}", err)))?;
+
+    Ok(statements.into_bytes())
+}
+
+/// Push `query` to `statements` if it matches `filter`. Return the rows of the COPY block it starts, if any.
+fn filter_statement(
+    statements: &mut String,
+    query: &str,
+    filter: RestoreFilter,
+) -> Option<(String, bool)> {
+    let query = query.trim();
+
+    let keep = if query.is_empty() || query.starts_with("--") {
+        false
+    } else if is_session_statement(query) || query.starts_with("/*!") {
+        // MySQL version comments (e.g. /*!40101 SET NAMES utf8mb4 */) are session statements too
+        true
+    } else {
+        is_data_statement(query) == (filter == RestoreFilter::DataOnly)
+    };
+
+    if keep {
+        statements.push_str(query);
+        statements.push('\n');
+    }
+
+    match query.starts_with("COPY ") && query.ends_with("FROM stdin;") {
+        true => Some((String::new(), keep)),
+        false => None,
+    }
+}
+
+/// Index right after the `\.` line ending the rows of a COPY block, if it is read
+fn end_of_copy_rows(rows: &str) -> Option<usize> {
+    let mut end = 0;
+    for line in rows.split_inclusive('\n') {
+        end += line.len();
+        if line.trim() == "\\." {
+            return Some(end);
+        }
+    }
+
+    None
+}
+
+/// Write the statements creating the schemas, the extensions and the types of a dump part (e.g. CREATE EXTENSION postgis)
+/// before its other statements, in their original order: the tables using them can be created, and a missing extension
+/// or privilege is reported before anything else is restored. Return the other statements of the dump part.
+fn write_prerequisite_statements<D: Destination>(
+    destination: &D,
+    data: Bytes,
+) -> Result<Bytes, Error> {
+    let mut session_statements = String::new();
+    let mut prerequisite_statements = vec![];
+    let mut statements = String::new();
+    list_sql_queries_from_dump_reader(BufReader::new(data.as_slice()), |query| {
+        let query = query.trim();
+
+        if query.is_empty() || query.starts_with("--") {
+            // skip the blank and comment lines
+        } else if created_prerequisite(query).is_some() {
+            prerequisite_statements.push(query.to_string());
+        } else {
+            if is_session_statement(query) {
+                session_statements.push_str(query);
+                session_statements.push('\n');
+            }
+
+            statements.push_str(query);
+            statements.push('\n');
+        }
+
+        ListQueryResult::Continue
+    })
+    .map_err(|err| Error::new(ErrorKind::Other, format!("{:?}", err)))?;
+
+    if prerequisite_statements.is_empty() {
+        return Ok(data);
+    }
+
+    // the schemas and the types are written together, and psql carries on when one fails (e.g. it already exists)
+    let mut other_statements = String::new();
+    for statement in prerequisite_statements {
+        let name = match created_prerequisite(statement.as_str()) {
+            Some(("extension", name)) => name,
+            _ => {
+                other_statements.push_str(statement.as_str());
+                other_statements.push('\n');
+                continue;
+            }
+        };
+
+        if !other_statements.is_empty() {
+            let data = format!("{}{}", session_statements, other_statements);
+            destination.write(data.into_bytes())?;
+            other_statements.clear();
+        }
+
+        // an extension is created on its own, and psql ignores the failing statements unless ON_ERROR_STOP is set
+        let data = format!(
+            "\\set ON_ERROR_STOP on\n{}{}\n",
+            session_statements, statement
+        );
+
+        destination.write(data.into_bytes()).map_err(|err| {
+            Error::new(
+                ErrorKind::Other,
+                format!(
+                    "extension {} not available on destination - install it on the destination server or create it with a superuser before the restore: {}",
+                    name, err
+                ),
+            )
+        })?;
+    }
+
+    if !other_statements.is_empty() {
+        let data = format!("{}{}", session_statements, other_statements);
+        destination.write(data.into_bytes())?;
+    }
+
+    Ok(statements.into_bytes())
+}
+
+/// Write the statements of a dump part in order, except the INSERT statements between two other statements:
+/// they are grouped by table and the tables are written concurrently by up to `workers` destination connections.
+/// pg_dump adds the constraints (e.g. foreign keys) after the data, so the tables can be loaded in any order.
+fn write_tables_concurrently<D: Destination + Sync>(
+    destination: &D,
+    data: Bytes,
+    workers: usize,
+) -> Result<(), Error> {
+    let mut queries = vec![];
+    list_sql_queries_from_dump_reader(BufReader::new(data.as_slice()), |query| {
+        let query = query.trim();
+        // skip the blank and comment lines
+        if !query.is_empty() && !query.starts_with("--") {
+            queries.push(query.to_string());
+        }
+        ListQueryResult::Continue
+    })
+    .map_err(|err| Error::new(ErrorKind::Other, format!("{:?}", err)))?;
+
+    // SET statements are replayed on each connection
+    let mut session_statements = String::new();
+    let mut statements = String::new();
+    let mut tables: Vec<String> = vec![];
+    let mut table_idx_by_name: HashMap<String, usize> = HashMap::new();
+
+    for query in queries {
+        match insert_into_table_name(query.as_str()) {
+            Some(table_name) => {
+                if !statements.is_empty() {
+                    destination.write(statements.into_bytes())?;
+                    statements = String::new();
+                }
+
+                let idx = *table_idx_by_name
+                    .entry(table_name.to_string())
+                    .or_insert_with(|| {
+                        tables.push(session_statements.clone());
+                        tables.len() - 1
+                    });
+
+                tables[idx].push_str(query.as_str());
+                tables[idx].push('\n');
+            }
+            None => {
+                if !tables.is_empty() {
+                    write_tables(destination, tables, workers)?;
+                    tables = vec![];
+                    table_idx_by_name.clear();
+                }
+
+                if is_session_statement(query.as_str()) {
+                    session_statements.push_str(query.as_str());
+                    session_statements.push('\n');
+                }
+
+                statements.push_str(query.as_str());
+                statements.push('\n');
+            }
+        }
+    }
+
+    if !tables.is_empty() {
+        write_tables(destination, tables, workers)?;
+    }
+
+    if !statements.is_empty() {
+        destination.write(statements.into_bytes())?;
+    }
+
+    Ok(())
+}
+
+fn write_tables<D: Destination + Sync>(
+    destination: &D,
+    tables: Vec<String>,
+    workers: usize,
+) -> Result<(), Error> {
+    let workers = workers.min(tables.len());
+    let tables = Mutex::new(tables.into_iter());
+
+    thread::scope(|scope| {
+        let handles = (0..workers)
+            .map(|_| {
+                scope.spawn(|| -> Result<(), Error> {
+                    loop {
+                        let table = match tables.lock().unwrap().next() {
+                            Some(table) => table,
+                            None => return Ok(()),
+                        };
+
+                        destination.write(table.into_bytes())?;
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .try_for_each(|handle| handle.join().unwrap())
+    })
+}
+
+fn insert_into_table_name(query: &str) -> Option<&str> {
+    query
+        .strip_prefix("INSERT INTO ")?
+        .split(|c: char| c.is_whitespace() || c == '(')
+        .next()
+}
+
+/// kind and name of the schema, extension or type created by the statement - e.g. ("extension", "postgis")
+fn created_prerequisite(query: &str) -> Option<(&str, &str)> {
+    let (kind, name) = [
+        ("CREATE SCHEMA ", "schema"),
+        ("CREATE EXTENSION ", "extension"),
+        ("CREATE TYPE ", "type"),
+    ]
+    .iter()
+    .find_map(|(prefix, kind)| Some((*kind, query.strip_prefix(prefix)?)))?;
+
+    let name = name.strip_prefix("IF NOT EXISTS ").unwrap_or(name);
+    name.split(|c: char| c.is_whitespace() || c == ';' || c == '(')
+        .next()
+        .map(|name| (kind, name))
+}
+
+fn is_session_statement(query: &str) -> bool {
+    query.starts_with("SET ") || query.starts_with("SELECT pg_catalog.set_config")
+}
+
+fn is_data_statement(query: &str) -> bool {
+    [
+        "INSERT INTO ",
+        "COPY ",
+        "SELECT pg_catalog.setval",
+        "LOCK TABLES ",
+        "UNLOCK TABLES",
+    ]
+    .iter()
+    .any(|prefix| query.starts_with(prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Error, ErrorKind};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+    use std::thread::sleep;
+    use std::time::Duration;
+
+    use tempfile::tempdir;
+
+    use crate::connector::Connector;
+    use crate::datastore::local_disk::LocalDisk;
+    use crate::datastore::{Datastore, ReadOptions};
+    use crate::destination::Destination;
+    use crate::tasks::full_restore::{FullRestoreTask, RestoreFilter};
+    use crate::tasks::Task;
+    use crate::types::Bytes;
+
+    /// destination recording the writes and the maximum number of concurrent writes - the writes containing
+    /// `failing_statement` fail
+    #[derive(Default)]
+    struct TestDestination {
+        writes: Mutex<Vec<String>>,
+        concurrent_writes: AtomicUsize,
+        max_concurrent_writes: AtomicUsize,
+        failing_statement: Option<&'static str>,
+    }
+
+    impl Connector for TestDestination {
+        fn init(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+
+    impl Destination for TestDestination {
+        fn write(&self, data: Bytes) -> Result<(), Error> {
+            let data = String::from_utf8(data).unwrap();
+            if let Some(failing_statement) = self.failing_statement {
+                if data.contains(failing_statement) {
+                    return Err(Error::new(
+                        ErrorKind::Other,
+                        "command error: exit status: 3",
+                    ));
+                }
+            }
+
+            let concurrent_writes = self.concurrent_writes.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_concurrent_writes
+                .fetch_max(concurrent_writes, Ordering::SeqCst);
+
+            sleep(Duration::from_millis(100));
+            self.writes.lock().unwrap().push(data);
+
+            self.concurrent_writes.fetch_sub(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    fn run_task<F>(destination: &mut TestDestination, dump: &str, configure: F) -> Result<(), Error>
+    where
+        F: FnOnce(&mut FullRestoreTask<TestDestination>),
+    {
+        let dir = tempdir().expect("cannot create tempdir");
+        let mut local_disk = LocalDisk::new(dir.path().to_str().unwrap());
+        let _ = local_disk.init().expect("local_disk init failed");
+        local_disk.set_dump_name("dump-1".to_string());
+        let size = local_disk.write(1, dump.as_bytes().to_vec()).unwrap();
+        assert!(local_disk.commit(size, None).is_ok());
+
+        let mut task = FullRestoreTask::new(destination, Box::new(local_disk), ReadOptions::Latest);
+        configure(&mut task);
+        task.run(|_, _| {})
+    }
+
+    fn restore(workers: usize, filter: Option<RestoreFilter>) -> TestDestination {
+        let dump = r#"SET client_encoding = 'UTF8';
+CREATE TABLE public.customers (id integer, name text);
+CREATE TABLE public.orders (id integer, customer_id integer);
+CREATE TABLE public.products (id integer, name text);
+INSERT INTO public.customers (id, name) VALUES (1, 'Nancy');
+INSERT INTO public.orders (id, customer_id) VALUES (1, 1);
+INSERT INTO public.products (id, name) VALUES (1, 'Chai; tea');
+INSERT INTO public.customers (id, name) VALUES (2, 'Andrew');
+ALTER TABLE ONLY public.orders ADD CONSTRAINT orders_customer_id_fkey FOREIGN KEY (customer_id) REFERENCES public.customers(id);
+SELECT pg_catalog.setval('public.orders_id_seq', 1, true);
+"#;
+
+        let mut destination = TestDestination::default();
+        let result = run_task(&mut destination, dump, |task| {
+            task.set_workers(workers);
+            if let Some(filter) = filter {
+                task.set_filter(filter);
+            }
+        });
+        assert!(result.is_ok());
+
+        destination
+    }
+
+    const DUMP_WITH_PREREQUISITES: &str = r#"SET client_encoding = 'UTF8';
+CREATE TABLE public.customers (id integer, name text);
+CREATE EXTENSION IF NOT EXISTS postgis WITH SCHEMA public;
+CREATE TYPE public.mood AS ENUM ('sad', 'ok', 'happy');
+CREATE TABLE public.stores (id integer, location public.geography(Point,4326), mood public.mood);
+INSERT INTO public.stores (id, location, mood) VALUES (1, '0101000020E6100000000000000000F03F0000000000000040', 'happy');
+"#;
+
+    #[test]
+    fn restore_tables_concurrently() {
+        let destination = restore(2, None);
+        let writes = destination.writes.into_inner().unwrap();
+
+        // the tables are created first, then each table is written on its own, then the constraints are added
+        assert_eq!(writes.len(), 5);
+        assert!(writes[0].contains("CREATE TABLE public.products"));
+        assert!(writes[4].starts_with("ALTER TABLE ONLY public.orders"));
+
+        let customers = writes
+            .iter()
+            .find(|x| x.contains("INSERT INTO public.customers"))
+            .unwrap();
+        assert!(customers.starts_with("SET client_encoding = 'UTF8';"));
+        assert!(customers.contains("'Nancy'") && customers.contains("'Andrew'"));
+        assert!(writes.iter().any(|x| x.contains("'Chai; tea'")));
+
+        // 3 tables, but no more than 2 at the same time
+        assert_eq!(destination.max_concurrent_writes.into_inner(), 2);
+    }
+
+    #[test]
+    fn restore_with_a_single_worker() {
+        let destination = restore(1, None);
+        let writes = destination.writes.into_inner().unwrap();
+
+        assert_eq!(writes.len(), 1);
+        assert_eq!(destination.max_concurrent_writes.into_inner(), 1);
+    }
+
+    #[test]
+    fn restore_schema_only() {
+        let destination = restore(1, Some(RestoreFilter::SchemaOnly));
+        let writes = destination.writes.into_inner().unwrap();
+
+        assert_eq!(writes.len(), 1);
+        assert!(writes[0].starts_with("SET client_encoding = 'UTF8';"));
+        assert_eq!(writes[0].matches("CREATE TABLE").count(), 3);
+        assert!(writes[0].contains("ALTER TABLE ONLY public.orders"));
+        assert!(!writes[0].contains("INSERT INTO"));
+        assert!(!writes[0].contains("setval"));
+    }
+
+    #[test]
+    fn restore_data_only() {
+        let destination = restore(2, Some(RestoreFilter::DataOnly));
+        let writes = destination.writes.into_inner().unwrap();
+        let statements = writes.join("\n");
+
+        assert!(writes[0].starts_with("SET client_encoding = 'UTF8';"));
+        assert_eq!(statements.matches("INSERT INTO").count(), 4);
+        assert!(statements.contains("SELECT pg_catalog.setval"));
+        assert!(!statements.contains("CREATE TABLE"));
+        assert!(!statements.contains("ALTER TABLE"));
+    }
+
+    const DUMP_WITH_COPY: &str = "SET client_encoding = 'UTF8';
+CREATE TABLE public.customers (id integer, name text);
+CREATE TABLE public.products (id integer, name text);
+COPY public.customers (id, name) FROM stdin;
+1\tNancy
+2\tAndrew;
+3\tJanet; Jr
+
+\\.
+CREATE INDEX customers_name_idx ON public.customers (name);
+COPY public.products (id, name) FROM stdin;
+1\tChai
+\\.
+
+INSERT INTO public.products (id, name) VALUES (2, 'Chang');
+SELECT pg_catalog.setval('public.customers_id_seq', 3, true);
+";
+
+    fn restore_dump_with_copy(filter: RestoreFilter) -> String {
+        let mut destination = TestDestination::default();
+        let result = run_task(&mut destination, DUMP_WITH_COPY, |task| {
+            task.set_filter(filter)
+        });
+        assert!(result.is_ok());
+
+        destination.writes.into_inner().unwrap().join("\n")
+    }
+
+    #[test]
+    fn restore_the_rows_of_copy_statements_with_data_only() {
+        let statements = restore_dump_with_copy(RestoreFilter::DataOnly);
+
+        // the rows are kept as they are, up to the end of the COPY block
+        assert!(statements.contains(
+            "COPY public.customers (id, name) FROM stdin;\n1\tNancy\n2\tAndrew;\n3\tJanet; Jr\n\n\\.\n"
+        ));
+        assert!(statements.contains("COPY public.products (id, name) FROM stdin;\n1\tChai\n\\.\n"));
+        assert!(statements.contains("INSERT INTO public.products"));
+        assert!(statements.contains("SELECT pg_catalog.setval"));
+        assert!(!statements.contains("CREATE TABLE"));
+        assert!(!statements.contains("CREATE INDEX"));
+    }
+
+    #[test]
+    fn drop_the_rows_of_copy_statements_with_schema_only() {
+        let statements = restore_dump_with_copy(RestoreFilter::SchemaOnly);
+
+        assert!(statements.starts_with("SET client_encoding = 'UTF8';"));
+        assert_eq!(statements.matches("CREATE TABLE").count(), 2);
+        assert!(statements.contains("CREATE INDEX customers_name_idx"));
+        assert!(!statements.contains("COPY"));
+        assert!(!statements.contains("Nancy"));
+        assert!(!statements.contains("Chai"));
+        assert!(!statements.contains("\\."));
+        assert!(!statements.contains("INSERT INTO"));
+    }
+
+    #[test]
+    fn restore_the_extensions_and_the_types_first() {
+        let mut destination = TestDestination::default();
+        let result = run_task(&mut destination, DUMP_WITH_PREREQUISITES, |task| {
+            task.set_prerequisites_first(true)
+        });
+        assert!(result.is_ok());
+
+        let writes = destination.writes.into_inner().unwrap();
+        assert_eq!(writes.len(), 3);
+
+        // each extension and type is created on its own, before the tables using them
+        assert!(writes[0].starts_with("\\set ON_ERROR_STOP on\nSET client_encoding = 'UTF8';"));
+        assert!(writes[0].ends_with("CREATE EXTENSION IF NOT EXISTS postgis WITH SCHEMA public;\n"));
+        assert!(!writes[0].contains("CREATE TABLE"));
+        assert!(writes[1].starts_with("SET client_encoding = 'UTF8';"));
+        assert!(writes[1].contains("CREATE TYPE public.mood AS ENUM"));
+        assert!(!writes[1].contains("CREATE TABLE"));
+        // psql carries on when a type can't be created, e.g. when it already exists
+        assert!(!writes[1].contains("ON_ERROR_STOP"));
+
+        assert!(writes[2].starts_with("SET client_encoding = 'UTF8';"));
+        assert_eq!(writes[2].matches("CREATE TABLE").count(), 2);
+        assert!(writes[2].contains("INSERT INTO public.stores"));
+        assert!(!writes[2].contains("CREATE EXTENSION") && !writes[2].contains("CREATE TYPE"));
+    }
+
+    #[test]
+    fn restore_fails_on_a_missing_extension() {
+        let mut destination = TestDestination {
+            failing_statement: Some("CREATE EXTENSION"),
+            ..Default::default()
+        };
+        let err = run_task(&mut destination, DUMP_WITH_PREREQUISITES, |task| {
+            task.set_prerequisites_first(true)
+        })
+        .unwrap_err();
+
+        assert!(err
+            .to_string()
+            .starts_with("extension postgis not available on destination"));
+        // nothing else is restored
+        assert!(destination.writes.into_inner().unwrap().is_empty());
+    }
+
+    #[test]
+    fn restore_the_schemas_with_the_extensions_and_the_types() {
+        let dump = r#"SET client_encoding = 'UTF8';
+CREATE SCHEMA app;
+CREATE EXTENSION IF NOT EXISTS citext WITH SCHEMA app;
+CREATE TYPE app.mood AS ENUM ('sad', 'ok', 'happy');
+CREATE TABLE app.users (id integer, email app.citext, mood app.mood);
+INSERT INTO app.users (id, email, mood) VALUES (1, 'nancy@example.com', 'happy');
+"#;
+
+        let mut destination = TestDestination::default();
+        let result = run_task(&mut destination, dump, |task| {
+            task.set_prerequisites_first(true)
+        });
+        assert!(result.is_ok());
+
+        // the schema is created before the extension and the type created in it
+        let writes = destination.writes.into_inner().unwrap();
+        assert_eq!(writes.len(), 4);
+        assert_eq!(
+            writes[0],
+            "SET client_encoding = 'UTF8';\nCREATE SCHEMA app;\n"
+        );
+        assert!(writes[1].starts_with("\\set ON_ERROR_STOP on\n"));
+        assert!(writes[1].ends_with("CREATE EXTENSION IF NOT EXISTS citext WITH SCHEMA app;\n"));
+        assert_eq!(
+            writes[2],
+            "SET client_encoding = 'UTF8';\nCREATE TYPE app.mood AS ENUM ('sad', 'ok', 'happy');\n"
+        );
+        assert!(writes[3].contains("CREATE TABLE app.users"));
+        assert!(writes[3].contains("INSERT INTO app.users"));
+        assert!(!writes[3].contains("CREATE SCHEMA"));
+    }
+}
@@ -1,5 +1,6 @@
 use std::io::Error;
 
+pub mod events;
 pub mod full_dump;
 pub mod full_restore;
 
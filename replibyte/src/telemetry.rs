@@ -1,5 +1,5 @@
 use crate::config::{ConnectionUri, TransformerTypeConfig};
-use crate::{Config, DumpCommand, RestoreCommand, SubCommand, TransformerCommand};
+use crate::{Config, ConfigCommand, DumpCommand, RestoreCommand, SubCommand, TransformerCommand};
 use chrono::{NaiveDateTime, Utc};
 use reqwest::blocking::Client as HttpClient;
 use reqwest::header::CONTENT_TYPE;
@@ -110,13 +110,51 @@ impl TelemetryClient {
                         for column in &transformer.columns {
                             transformers.insert(match column.transformer {
                                 TransformerTypeConfig::Random => "random",
-                                TransformerTypeConfig::RandomDate => "random-date",
+                                TransformerTypeConfig::RandomDate(_) => "random-date",
                                 TransformerTypeConfig::FirstName => "first-name",
-                                TransformerTypeConfig::Email => "email",
-                                TransformerTypeConfig::KeepFirstChar => "keep-first-char",
-                                TransformerTypeConfig::PhoneNumber => "phone-number",
+                                TransformerTypeConfig::Email(_) => "email",
+                                TransformerTypeConfig::KeepFirstChar(_) => "keep-first-char",
+                                TransformerTypeConfig::PhoneNumber(_) => "phone-number",
                                 TransformerTypeConfig::CreditCard => "credit-card",
                                 TransformerTypeConfig::Redacted(_) => "redacted",
+                                TransformerTypeConfig::Scramble(_) => "scramble",
+                                TransformerTypeConfig::KeepMatching(_) => "keep-matching",
+                                TransformerTypeConfig::CorporateEmail(_) => "corporate-email",
+                                TransformerTypeConfig::Script(_) => "script",
+                                TransformerTypeConfig::FilePath(_) => "file-path",
+                                TransformerTypeConfig::Place(_) => "place",
+                                TransformerTypeConfig::BirthDate(_) => "birth-date",
+                                TransformerTypeConfig::IsoCode(_) => "iso-code",
+                                TransformerTypeConfig::TruncateDate(_) => "truncate-date",
+                                TransformerTypeConfig::DomainMap(_) => "domain-map",
+                                TransformerTypeConfig::XmlAttr(_) => "xml-attr",
+                                TransformerTypeConfig::PatternPreserving => "pattern-preserving",
+                                TransformerTypeConfig::FakeJson(_) => "fake-json",
+                                TransformerTypeConfig::MappingFile(_) => "mapping-file",
+                                TransformerTypeConfig::Url(_) => "url",
+                                TransformerTypeConfig::BooleanString => "boolean-string",
+                                TransformerTypeConfig::NumberDistribution(_) => {
+                                    "number-distribution"
+                                }
+                                TransformerTypeConfig::Lorem(_) => "lorem",
+                                TransformerTypeConfig::NameInitials(_) => "name-initials",
+                                TransformerTypeConfig::Plugin { .. } => "plugin",
+                                TransformerTypeConfig::Round(_) => "round",
+                                TransformerTypeConfig::Vat(_) => "vat",
+                                TransformerTypeConfig::Discriminator(_) => "discriminator",
+                                TransformerTypeConfig::IdList(_) => "id-list",
+                                TransformerTypeConfig::ShiftDate(_) => "shift-date",
+                                TransformerTypeConfig::UuidToInt(_) => "uuid-to-int",
+                                TransformerTypeConfig::UserAgent(_) => "user-agent",
+                                TransformerTypeConfig::PrefixPreservingFake(_) => {
+                                    "prefix-preserving-fake"
+                                }
+                                TransformerTypeConfig::ReferenceCode(_) => "reference-code",
+                                TransformerTypeConfig::Fake(_) => "fake",
+                                TransformerTypeConfig::Jitter(_) => "jitter",
+                                TransformerTypeConfig::Nearby(_) => "nearby",
+                                TransformerTypeConfig::Consistent(_) => "consistent",
+                                TransformerTypeConfig::RegexReplace(_) => "regex-replace",
                                 TransformerTypeConfig::Transient => "transient",
                                 TransformerTypeConfig::CustomWasm(_) => "custom-wasm",
                             });
@@ -136,6 +174,10 @@ impl TelemetryClient {
                 DumpCommand::List => "dump-list",
                 DumpCommand::Create(_) => "dump-create",
                 DumpCommand::Delete(_) => "dump-delete",
+                DumpCommand::ExportIndex(_) => "dump-export-index",
+                DumpCommand::ImportIndex(_) => "dump-import-index",
+                DumpCommand::Verify(_) => "dump-verify",
+                DumpCommand::Recompress(_) => "dump-recompress",
                 DumpCommand::Restore(restore_cmd) => match restore_cmd {
                     RestoreCommand::Local(_) => "dump-restore-local",
                     RestoreCommand::Remote(_) => "dump-restore-remote",
@@ -144,6 +186,9 @@ impl TelemetryClient {
             SubCommand::Transformer(cmd) => match cmd {
                 TransformerCommand::List => "transformer-list",
             },
+            SubCommand::Config(cmd) => match cmd {
+                ConfigCommand::Check => "config-check",
+            },
         };
 
         self.capture(Event {
@@ -0,0 +1,232 @@
+use std::io::{Error, ErrorKind};
+
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::transformer::{hash_row, Transformer};
+use crate::types::Column;
+
+const MIN_AGE_IN_DAYS: u64 = 18 * 365;
+const AGE_RANGE_IN_DAYS: u64 = 62 * 365;
+
+/// This struct is dedicated to replacing a birth date, or an age, by a fake one.
+/// It is set with the same options on both the birth date and the age columns of a table: they pick the same fake
+/// birth date for the row, so the age always matches the birth date.
+pub struct BirthDateTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    is_age_column: bool,
+    today: NaiveDate,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct BirthDateOptions {
+    pub birth_date_column: String,
+    pub age_column: String,
+}
+
+impl BirthDateTransformer {
+    pub fn new<S>(
+        database_name: S,
+        table_name: S,
+        column_name: S,
+        options: BirthDateOptions,
+    ) -> Result<Self, Error>
+    where
+        S: Into<String>,
+    {
+        let column_name = column_name.into();
+
+        let is_age_column = match column_name.as_str() {
+            name if name == options.age_column => true,
+            name if name == options.birth_date_column => false,
+            name => {
+                return Err(Error::new(
+                    ErrorKind::Other,
+                    format!(
+                        "column '{}' is neither the birth date column '{}' nor the age column '{}'",
+                        name, options.birth_date_column, options.age_column
+                    ),
+                ))
+            }
+        };
+
+        Ok(BirthDateTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name,
+            is_age_column,
+            today: Utc::now().naive_utc().date(),
+        })
+    }
+
+    fn birth_date(&self, seed: u64) -> NaiveDate {
+        let days = MIN_AGE_IN_DAYS + seed % AGE_RANGE_IN_DAYS;
+        self.today - Duration::days(days as i64)
+    }
+
+    fn age(&self, birth_date: NaiveDate) -> i32 {
+        let age = self.today.year() - birth_date.year();
+
+        // the birthday of this year is not passed yet
+        match (self.today.month(), self.today.day()) < (birth_date.month(), birth_date.day()) {
+            true => age - 1,
+            false => age,
+        }
+    }
+
+    fn transform_with_birth_date(&self, column: Column, birth_date: NaiveDate) -> Column {
+        match column {
+            Column::NumberValue(column_name, _) if self.is_age_column => {
+                Column::NumberValue(column_name, self.age(birth_date) as i128)
+            }
+            Column::StringValue(column_name, value) if self.is_age_column => {
+                match value.is_empty() {
+                    true => Column::StringValue(column_name, value),
+                    false => Column::StringValue(column_name, self.age(birth_date).to_string()),
+                }
+            }
+            Column::StringValue(column_name, value) if !value.is_empty() => {
+                // keep the time part of a timestamp (e.g. 1987-05-12 00:00:00)
+                let time = match value
+                    .get(..10)
+                    .map(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d"))
+                {
+                    Some(Ok(_)) => &value[10..],
+                    _ => "",
+                };
+
+                Column::StringValue(
+                    column_name,
+                    format!("{}{}", birth_date.format("%Y-%m-%d"), time),
+                )
+            }
+            column => column,
+        }
+    }
+}
+
+impl Default for BirthDateTransformer {
+    fn default() -> Self {
+        BirthDateTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            is_age_column: false,
+            today: Utc::now().naive_utc().date(),
+        }
+    }
+}
+
+impl Transformer for BirthDateTransformer {
+    fn id(&self) -> &str {
+        "birth-date"
+    }
+
+    fn description(&self) -> &str {
+        "Generate a birth date and the matching age for the whole row. [1987-05-12, 35]->[1969-11-03, 53]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        // without the row, the birth date can't be shared with the partner column
+        let birth_date = self.birth_date(rand::thread_rng().gen());
+        self.transform_with_birth_date(column, birth_date)
+    }
+
+    fn transform_with_row(&self, column: Column, row: &[Column]) -> Column {
+        let birth_date = self.birth_date(hash_row(row, None));
+        self.transform_with_birth_date(column, birth_date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Datelike, NaiveDate, Utc};
+
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{BirthDateOptions, BirthDateTransformer};
+
+    #[test]
+    fn transform_doesnt_change_with_float_value() {
+        let transformer = get_transformer("birth_date");
+        let column = Column::FloatNumberValue("birth_date".to_string(), 1.5);
+        let transformed_column = transformer.transform_with_row(column, &get_row(1));
+        let transformed_value = transformed_column.float_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 1.5)
+    }
+
+    #[test]
+    fn new_fails_with_another_column() {
+        assert!(BirthDateTransformer::new(
+            "github",
+            "users",
+            "email",
+            BirthDateOptions {
+                birth_date_column: "birth_date".to_string(),
+                age_column: "age".to_string(),
+            },
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn transform_keeps_the_age_consistent_with_the_birth_date() {
+        let now = Utc::now().naive_utc().date();
+
+        for id in 0..50 {
+            let row = get_row(id);
+
+            let birth_date = get_transformer("birth_date").transform_with_row(row[1].clone(), &row);
+            let age = get_transformer("age").transform_with_row(row[2].clone(), &row);
+
+            let birth_date = birth_date.string_value().unwrap();
+            assert!(birth_date.ends_with(" 00:00:00"));
+
+            let birth_date = NaiveDate::parse_from_str(&birth_date[..10], "%Y-%m-%d").unwrap();
+            let mut expected_age = now.year() - birth_date.year();
+            if (now.month(), now.day()) < (birth_date.month(), birth_date.day()) {
+                expected_age -= 1;
+            }
+
+            assert!(expected_age >= 18);
+            assert_eq!(*age.number_value().unwrap(), expected_age as i128);
+        }
+    }
+
+    fn get_row(id: i128) -> Vec<Column> {
+        vec![
+            Column::NumberValue("id".to_string(), id),
+            Column::StringValue("birth_date".to_string(), "1987-05-12 00:00:00".to_string()),
+            Column::NumberValue("age".to_string(), 35),
+        ]
+    }
+
+    fn get_transformer(column_name: &str) -> BirthDateTransformer {
+        BirthDateTransformer::new(
+            "github",
+            "users",
+            column_name,
+            BirthDateOptions {
+                birth_date_column: "birth_date".to_string(),
+                age_column: "age".to_string(),
+            },
+        )
+        .unwrap()
+    }
+}
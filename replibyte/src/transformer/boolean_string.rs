@@ -0,0 +1,197 @@
+use rand::Rng;
+
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+/// (true, false) values of the supported vocabularies
+const VOCABULARIES: [(&str, &str); 5] = [
+    ("y", "n"),
+    ("yes", "no"),
+    ("t", "f"),
+    ("true", "false"),
+    ("1", "0"),
+];
+
+/// This struct is dedicated to replacing a boolean-ish string (e.g. Y/N, true/false, 1/0) by a random one
+/// of the same vocabulary and case. The other values are kept as they are.
+pub struct BooleanStringTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+}
+
+impl BooleanStringTransformer {
+    pub fn new<S>(database_name: S, table_name: S, column_name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        BooleanStringTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+        }
+    }
+}
+
+impl Default for BooleanStringTransformer {
+    fn default() -> Self {
+        BooleanStringTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+        }
+    }
+}
+
+/// write `value` with the case of `model` (e.g. `false` -> `FALSE` for `TRUE`, `False` for `True`)
+fn with_case_of(value: &str, model: &str) -> String {
+    if model.chars().all(|c| !c.is_lowercase()) {
+        return value.to_uppercase();
+    }
+
+    match model.chars().next() {
+        Some(c) if c.is_uppercase() => {
+            let mut chars = value.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        }
+        _ => value.to_string(),
+    }
+}
+
+impl Transformer for BooleanStringTransformer {
+    fn id(&self) -> &str {
+        "boolean-string"
+    }
+
+    fn description(&self) -> &str {
+        "Randomize a boolean-ish string but keep its vocabulary and case (string only). [Y]->[N], [true]->[false], [1]->[0]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        match column {
+            Column::StringValue(column_name, value) => {
+                let lowercase_value = value.to_lowercase();
+
+                let new_value = match VOCABULARIES
+                    .iter()
+                    .find(|(t, f)| lowercase_value == *t || lowercase_value == *f)
+                {
+                    Some((t, f)) => {
+                        let new_value = if rand::thread_rng().gen_bool(0.5) {
+                            t
+                        } else {
+                            f
+                        };
+
+                        with_case_of(new_value, value.as_str())
+                    }
+                    None => value,
+                };
+
+                Column::StringValue(column_name, new_value)
+            }
+            column => column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::BooleanStringTransformer;
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = get_transformer();
+        let column = Column::NumberValue("active".to_string(), 1);
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.number_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 1)
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_other_string_value() {
+        let transformer = get_transformer();
+        let column = Column::StringValue("active".to_string(), "maybe".to_string());
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert_eq!(transformed_value, "maybe")
+    }
+
+    #[test]
+    fn transform_y_n() {
+        assert_eq!(
+            transformed_values("Y"),
+            HashSet::from(["Y", "N"].map(String::from))
+        );
+        assert_eq!(
+            transformed_values("n"),
+            HashSet::from(["y", "n"].map(String::from))
+        );
+    }
+
+    #[test]
+    fn transform_true_false() {
+        assert_eq!(
+            transformed_values("true"),
+            HashSet::from(["true", "false"].map(String::from))
+        );
+        assert_eq!(
+            transformed_values("False"),
+            HashSet::from(["True", "False"].map(String::from))
+        );
+        assert_eq!(
+            transformed_values("TRUE"),
+            HashSet::from(["TRUE", "FALSE"].map(String::from))
+        );
+    }
+
+    #[test]
+    fn transform_1_0() {
+        assert_eq!(
+            transformed_values("1"),
+            HashSet::from(["1", "0"].map(String::from))
+        );
+        assert_eq!(
+            transformed_values("0"),
+            HashSet::from(["1", "0"].map(String::from))
+        );
+    }
+
+    /// all the values generated from `value` - both values of the vocabulary are expected after 100 draws
+    fn transformed_values(value: &str) -> HashSet<String> {
+        let transformer = get_transformer();
+
+        (0..100)
+            .map(|_| {
+                let column = Column::StringValue("active".to_string(), value.to_string());
+                let transformed_column = transformer.transform(column);
+                transformed_column.string_value().unwrap().to_string()
+            })
+            .collect()
+    }
+
+    fn get_transformer() -> BooleanStringTransformer {
+        BooleanStringTransformer::new("github", "users", "active")
+    }
+}
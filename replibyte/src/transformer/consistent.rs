@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+use std::io::Error;
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+
+use crate::config::TransformerTypeConfig;
+use crate::transformer::random::RandomTransformer;
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+lazy_static! {
+    /// fake value of each original value, by configuration of the wrapped transformer - shared by all the columns
+    static ref CONSISTENT_VALUES: Mutex<HashMap<String, HashMap<String, String>>> =
+        Mutex::new(HashMap::new());
+}
+
+/// This struct is dedicated to replacing equal values by equal fake values during a dump, whatever the column, e.g.
+/// the same email in two tables. The values are transformed by the wrapped transformer, and all the columns wrapping
+/// the same transformer configuration share the fake values.
+pub struct ConsistentTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    transformer: Box<dyn Transformer>,
+    /// configuration of the wrapped transformer - the fake values are shared by the same configurations
+    key: String,
+}
+
+impl ConsistentTransformer {
+    pub fn new<S>(
+        database_name: S,
+        table_name: S,
+        column_name: S,
+        transformer: &TransformerTypeConfig,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        let database_name = database_name.into();
+        let table_name = table_name.into();
+        let column_name = column_name.into();
+
+        ConsistentTransformer {
+            transformer: transformer.transformer(
+                database_name.as_str(),
+                table_name.as_str(),
+                column_name.as_str(),
+            ),
+            key: serde_json::to_string(transformer).unwrap_or_default(),
+            database_name,
+            table_name,
+            column_name,
+        }
+    }
+
+    fn consistent<F>(&self, column: Column, transform: F) -> Column
+    where
+        F: FnOnce(Column) -> Column,
+    {
+        let (column_name, value) = match column {
+            Column::StringValue(column_name, value) => (column_name, value),
+            // only strings are cached
+            column => return transform(column),
+        };
+
+        let cached_value = CONSISTENT_VALUES
+            .lock()
+            .unwrap()
+            .get(self.key.as_str())
+            .and_then(|values| values.get(value.as_str()))
+            .cloned();
+
+        if let Some(cached_value) = cached_value {
+            return Column::StringValue(column_name, cached_value);
+        }
+
+        // the lock is not held while transforming, the wrapped transformer can be a consistent one too
+        match transform(Column::StringValue(column_name, value.clone())) {
+            Column::StringValue(column_name, new_value) => {
+                let new_value = CONSISTENT_VALUES
+                    .lock()
+                    .unwrap()
+                    .entry(self.key.clone())
+                    .or_default()
+                    .entry(value)
+                    .or_insert(new_value)
+                    .clone();
+
+                Column::StringValue(column_name, new_value)
+            }
+            transformed_column => transformed_column,
+        }
+    }
+}
+
+impl Default for ConsistentTransformer {
+    fn default() -> Self {
+        ConsistentTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            transformer: Box::new(RandomTransformer::default()),
+            key: String::default(),
+        }
+    }
+}
+
+impl Transformer for ConsistentTransformer {
+    fn id(&self) -> &str {
+        "consistent"
+    }
+
+    fn description(&self) -> &str {
+        "Replace equal values by equal fake values of another transformer, in all the columns (string only). [john@gmail.com]->[tony@example.com]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        self.consistent(column, |column| self.transformer.transform(column))
+    }
+
+    fn transform_with_row(&self, column: Column, row: &[Column]) -> Column {
+        self.consistent(column, |column| {
+            self.transformer.transform_with_row(column, row)
+        })
+    }
+
+    fn validate(&self, column: &Column) -> Result<(), Error> {
+        self.transformer.validate(column)
+    }
+
+    fn needs_transformed_row(&self) -> bool {
+        self.transformer.needs_transformed_row()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::TransformerTypeConfig;
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::ConsistentTransformer;
+
+    fn transform(transformer: &ConsistentTransformer, value: &str) -> String {
+        let column = Column::StringValue("email".to_string(), value.to_string());
+        let transformed_column = transformer.transform(column);
+        transformed_column.string_value().unwrap().to_string()
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = ConsistentTransformer::new(
+            "github",
+            "users",
+            "email",
+            &TransformerTypeConfig::Email(None),
+        );
+        let column = Column::NumberValue("email".to_string(), 42);
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.number_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 42)
+    }
+
+    #[test]
+    fn transform_equal_values_to_equal_fake_values_in_all_the_columns() {
+        let users = ConsistentTransformer::new(
+            "github",
+            "users",
+            "email",
+            &TransformerTypeConfig::Email(None),
+        );
+        let invoices = ConsistentTransformer::new(
+            "github",
+            "invoices",
+            "billing_email",
+            &TransformerTypeConfig::Email(None),
+        );
+
+        let email = transform(&users, "octocat@consistent.test");
+        assert_ne!(email, "octocat@consistent.test");
+        assert_eq!(transform(&users, "octocat@consistent.test"), email);
+        assert_eq!(transform(&invoices, "octocat@consistent.test"), email);
+
+        // another value gets another fake value
+        let other_emails = (0..10)
+            .map(|idx| transform(&invoices, format!("user-{}@consistent.test", idx).as_str()))
+            .collect::<Vec<_>>();
+        assert!(other_emails.iter().any(|other_email| *other_email != email));
+    }
+
+    #[test]
+    fn transform_with_another_transformer_doesnt_share_the_fake_values() {
+        let emails = ConsistentTransformer::new(
+            "github",
+            "users",
+            "email",
+            &TransformerTypeConfig::Email(None),
+        );
+        let names =
+            ConsistentTransformer::new("github", "users", "login", &TransformerTypeConfig::Random);
+
+        let email = transform(&emails, "hubot@consistent.test");
+        let login = transform(&names, "hubot@consistent.test");
+        assert_ne!(login, email);
+        assert_eq!(login.len(), "hubot@consistent.test".len());
+    }
+}
@@ -0,0 +1,202 @@
+use crate::transformer::Transformer;
+use crate::types::Column;
+use fake::faker::internet::raw::Username;
+use fake::locales::EN;
+use fake::Fake;
+use serde::{Deserialize, Serialize};
+
+/// This struct is dedicated to replacing a string by a corporate email address
+/// built from the name columns of the same row, once transformed, and a configured domain.
+pub struct CorporateEmailTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    options: CorporateEmailOptions,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct CorporateEmailOptions {
+    pub first_name_column: String,
+    pub last_name_column: String,
+    pub domain: String,
+}
+
+impl CorporateEmailTransformer {
+    pub fn new<S>(
+        database_name: S,
+        table_name: S,
+        column_name: S,
+        options: CorporateEmailOptions,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        CorporateEmailTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            options,
+        }
+    }
+
+    fn email(&self, local_part: &str) -> String {
+        format!("{}@{}", local_part, self.options.domain)
+    }
+}
+
+impl Default for CorporateEmailTransformer {
+    fn default() -> Self {
+        CorporateEmailTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            options: CorporateEmailOptions {
+                first_name_column: "first_name".to_string(),
+                last_name_column: "last_name".to_string(),
+                domain: "example.com".to_string(),
+            },
+        }
+    }
+}
+
+/// lowercase the name and keep only its alphanumeric characters
+fn to_local_part(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect::<String>()
+}
+
+impl Transformer for CorporateEmailTransformer {
+    fn id(&self) -> &str {
+        "corporate-email"
+    }
+
+    fn description(&self) -> &str {
+        "Generate an email address from the name columns of the row and a domain (string only). [john@gmail.com]->[jane.doe@acme.example]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        // without the row, there is no name to build the email from
+        match column {
+            Column::StringValue(column_name, value) => {
+                let new_value = match value.is_empty() {
+                    true => value,
+                    false => self.email(to_local_part(&Username(EN).fake::<String>()).as_str()),
+                };
+
+                Column::StringValue(column_name, new_value)
+            }
+            column => column,
+        }
+    }
+
+    fn transform_with_row(&self, column: Column, row: &[Column]) -> Column {
+        let name_parts = [
+            self.options.first_name_column.as_str(),
+            self.options.last_name_column.as_str(),
+        ]
+        .iter()
+        .filter_map(|name_column| row.iter().find(|c| c.name() == *name_column))
+        .filter_map(|c| c.string_value())
+        .map(to_local_part)
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>();
+
+        match column {
+            Column::StringValue(column_name, value)
+                if !value.is_empty() && !name_parts.is_empty() =>
+            {
+                Column::StringValue(column_name, self.email(name_parts.join(".").as_str()))
+            }
+            column => self.transform(column),
+        }
+    }
+
+    fn needs_transformed_row(&self) -> bool {
+        // the email is built from the anonymized names, not from the original ones
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{CorporateEmailOptions, CorporateEmailTransformer};
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = get_transformer();
+        let column = Column::NumberValue("email".to_string(), 42);
+        let transformed_column = transformer.transform_with_row(column, &get_row());
+        let transformed_value = transformed_column.number_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 42)
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_empty_string_value() {
+        let transformer = get_transformer();
+        let column = Column::StringValue("email".to_string(), "".to_string());
+        let transformed_column = transformer.transform_with_row(column, &get_row());
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert_eq!(transformed_value, "")
+    }
+
+    #[test]
+    fn transform_builds_email_from_name_columns() {
+        let transformer = get_transformer();
+        let column = Column::StringValue("email".to_string(), "jdoe@gmail.com".to_string());
+        let transformed_column = transformer.transform_with_row(column, &get_row());
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert_eq!(transformed_value, "jane.odoe@acme.example")
+    }
+
+    #[test]
+    fn transform_without_name_columns_uses_the_domain() {
+        let transformer = get_transformer();
+        let column = Column::StringValue("email".to_string(), "jdoe@gmail.com".to_string());
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert!(transformed_value.ends_with("@acme.example"));
+        assert!(transformed_value.len() > "@acme.example".len());
+    }
+
+    fn get_row() -> Vec<Column> {
+        vec![
+            Column::NumberValue("id".to_string(), 1),
+            Column::StringValue("first_name".to_string(), "Jane".to_string()),
+            Column::StringValue("last_name".to_string(), "O'Doe".to_string()),
+            Column::StringValue("email".to_string(), "jdoe@gmail.com".to_string()),
+        ]
+    }
+
+    fn get_transformer() -> CorporateEmailTransformer {
+        CorporateEmailTransformer::new(
+            "github",
+            "users",
+            "email",
+            CorporateEmailOptions {
+                first_name_column: "first_name".to_string(),
+                last_name_column: "last_name".to_string(),
+                domain: "acme.example".to_string(),
+            },
+        )
+    }
+}
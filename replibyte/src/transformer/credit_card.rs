@@ -1,3 +1,5 @@
+use std::io::{Error, ErrorKind};
+
 use crate::transformer::Transformer;
 use crate::types::Column;
 use fake::faker::creditcard::raw::CreditCardNumber;
@@ -63,6 +65,27 @@ impl Transformer for CreditCardTransformer {
             column => column,
         }
     }
+
+    fn validate(&self, column: &Column) -> Result<(), Error> {
+        match column {
+            // the digits of a card number can be grouped by spaces or dashes
+            Column::StringValue(_, value)
+                if !value
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || c == ' ' || c == '-') =>
+            {
+                Err(Error::new(
+                    ErrorKind::Other,
+                    "the value is not a credit card number",
+                ))
+            }
+            Column::StringValue(_, _) | Column::None(_) => Ok(()),
+            _ => Err(Error::new(
+                ErrorKind::Other,
+                "only string values are supported",
+            )),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -82,6 +105,34 @@ mod tests {
         assert_ne!(transformed_value, "4242424242424242".to_string());
     }
 
+    #[test]
+    fn validate_a_credit_card_number() {
+        let transformer = get_transformer();
+
+        for value in [
+            "4242424242424242",
+            "4242 4242 4242 4242",
+            "4242-4242-4242-4242",
+            "",
+        ] {
+            let column = Column::StringValue("credit_card".to_string(), value.to_string());
+            assert!(transformer.validate(&column).is_ok());
+        }
+
+        assert!(transformer
+            .validate(&Column::None("credit_card".to_string()))
+            .is_ok());
+        assert!(transformer
+            .validate(&Column::StringValue(
+                "credit_card".to_string(),
+                "see the invoice".to_string()
+            ))
+            .is_err());
+        assert!(transformer
+            .validate(&Column::NumberValue("credit_card".to_string(), 4242))
+            .is_err());
+    }
+
     fn get_transformer() -> CreditCardTransformer {
         CreditCardTransformer::new("github", "users", "credit_card")
     }
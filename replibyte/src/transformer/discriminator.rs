@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::TransformerTypeConfig;
+use crate::transformer::{row_value, Transformer};
+use crate::types::Column;
+
+/// This struct is dedicated to transforming a polymorphic column with the transformer matching the value of
+/// another column of the row, e.g. a `contact_value` holding an email or a phone number depending on `contact_type`.
+pub struct DiscriminatorTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    discriminator_column: String,
+    transformers: HashMap<String, Box<dyn Transformer>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct DiscriminatorOptions {
+    /// column of the row selecting the transformer (e.g. contact_type)
+    pub column: String,
+    /// transformer of each value of `column` - the value is kept as it is for the other ones
+    pub cases: HashMap<String, TransformerTypeConfig>,
+}
+
+impl DiscriminatorTransformer {
+    pub fn new<S>(
+        database_name: S,
+        table_name: S,
+        column_name: S,
+        options: &DiscriminatorOptions,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        let database_name = database_name.into();
+        let table_name = table_name.into();
+        let column_name = column_name.into();
+
+        let transformers = options
+            .cases
+            .iter()
+            .map(|(value, transformer)| {
+                (
+                    value.clone(),
+                    transformer.transformer(
+                        database_name.as_str(),
+                        table_name.as_str(),
+                        column_name.as_str(),
+                    ),
+                )
+            })
+            .collect();
+
+        DiscriminatorTransformer {
+            database_name,
+            table_name,
+            column_name,
+            discriminator_column: options.column.clone(),
+            transformers,
+        }
+    }
+}
+
+impl Default for DiscriminatorTransformer {
+    fn default() -> Self {
+        DiscriminatorTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            discriminator_column: String::default(),
+            transformers: HashMap::new(),
+        }
+    }
+}
+
+impl Transformer for DiscriminatorTransformer {
+    fn id(&self) -> &str {
+        "discriminator"
+    }
+
+    fn description(&self) -> &str {
+        "Apply the transformer matching the value of another column of the row. [email: john@gmail.com]->[email: tony@example.com]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        // the transformer can't be selected without the row
+        column
+    }
+
+    fn transform_with_row(&self, column: Column, row: &[Column]) -> Column {
+        let transformer = row_value(row, self.discriminator_column.as_str())
+            .and_then(|value| self.transformers.get(value.as_str()));
+
+        match transformer {
+            Some(transformer) => transformer.transform_with_row(column, row),
+            None => column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{DiscriminatorOptions, DiscriminatorTransformer};
+
+    fn get_transformer() -> DiscriminatorTransformer {
+        let options: DiscriminatorOptions = serde_yaml::from_str(
+            r#"
+column: contact_type
+cases:
+  email:
+    transformer_name: email
+  phone:
+    transformer_name: keep-first-char
+  url:
+    transformer_name: redacted
+    transformer_options:
+      character: '#'
+      width: 10
+"#,
+        )
+        .unwrap();
+
+        DiscriminatorTransformer::new("github", "contacts", "contact_value", &options)
+    }
+
+    fn transform(contact_type: &str, contact_value: &str) -> String {
+        let transformer = get_transformer();
+        let row = vec![
+            Column::StringValue("contact_type".to_string(), contact_type.to_string()),
+            Column::StringValue("contact_value".to_string(), contact_value.to_string()),
+        ];
+
+        let transformed_column = transformer.transform_with_row(row[1].clone(), &row);
+        transformed_column.string_value().unwrap().to_string()
+    }
+
+    #[test]
+    fn transform_with_the_transformer_of_the_discriminator_value() {
+        let email = transform("email", "nancy.davolio@northwind.com");
+        assert_ne!(email, "nancy.davolio@northwind.com");
+        assert!(email.contains('@'));
+
+        assert_eq!(transform("phone", "+33612345678"), "+");
+
+        let url = transform("url", "https://northwind.com/nancy");
+        assert!(url.starts_with("htt"));
+        assert!(url.ends_with("##########"));
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_other_discriminator_value() {
+        assert_eq!(transform("fax", "+33123456789"), "+33123456789");
+    }
+
+    #[test]
+    fn transform_doesnt_change_without_the_row() {
+        let transformer = get_transformer();
+        let column = Column::StringValue("contact_value".to_string(), "+33612345678".to_string());
+        let transformed_column = transformer.transform(column);
+
+        assert_eq!(transformed_column.string_value().unwrap(), "+33612345678");
+    }
+}
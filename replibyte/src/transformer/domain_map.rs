@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+
+use crate::transformer::{row_value, Transformer};
+use crate::types::Column;
+
+lazy_static! {
+    /// fake domain of each (tenant, real domain) - shared by all the columns of the dump
+    static ref FAKE_DOMAINS: Mutex<HashMap<(Option<String>, String), String>> =
+        Mutex::new(HashMap::new());
+}
+
+/// fake domain always associated with the given real domain (e.g. gmail.com -> domain1.test)
+pub fn fake_domain(domain: &str) -> String {
+    fake_tenant_domain(None, domain)
+}
+
+/// fake domain always associated with the given real domain of the tenant - two tenants get two different fake domains
+pub fn fake_tenant_domain(tenant_id: Option<&str>, domain: &str) -> String {
+    let mut fake_domains = FAKE_DOMAINS.lock().unwrap();
+    let next_domain = format!("domain{}.test", fake_domains.len() + 1);
+
+    fake_domains
+        .entry((tenant_id.map(String::from), domain.to_lowercase()))
+        .or_insert(next_domain)
+        .clone()
+}
+
+/// This struct is dedicated to replacing each distinct domain by a distinct fake domain, the same one everywhere.
+/// It applies on a domain column (e.g. gmail.com) or on the domain of an email column (e.g. john.doe@gmail.com).
+pub struct DomainMapTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    options: DomainMapOptions,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct DomainMapOptions {
+    /// column of the tenant of the row (e.g. organization_id) - each tenant gets its own fake domains
+    pub tenant_column: Option<String>,
+}
+
+impl DomainMapTransformer {
+    pub fn new<S>(
+        database_name: S,
+        table_name: S,
+        column_name: S,
+        options: DomainMapOptions,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        DomainMapTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            options,
+        }
+    }
+
+    fn transform_for_tenant(&self, column: Column, tenant_id: Option<&str>) -> Column {
+        match column {
+            Column::StringValue(column_name, value) => {
+                if value.is_empty() {
+                    return Column::StringValue(column_name, value);
+                }
+
+                let new_value = match value.rsplit_once('@') {
+                    Some((local_part, domain)) => {
+                        format!("{}@{}", local_part, fake_tenant_domain(tenant_id, domain))
+                    }
+                    None => fake_tenant_domain(tenant_id, value.as_str()),
+                };
+
+                Column::StringValue(column_name, new_value)
+            }
+            column => column,
+        }
+    }
+}
+
+impl Default for DomainMapTransformer {
+    fn default() -> Self {
+        DomainMapTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            options: DomainMapOptions::default(),
+        }
+    }
+}
+
+impl Transformer for DomainMapTransformer {
+    fn id(&self) -> &str {
+        "domain-map"
+    }
+
+    fn description(&self) -> &str {
+        "Map each domain to the same fake domain (string only). [john.doe@gmail.com]->[john.doe@domain1.test]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        self.transform_for_tenant(column, None)
+    }
+
+    fn transform_with_row(&self, column: Column, row: &[Column]) -> Column {
+        let tenant_id = match &self.options.tenant_column {
+            Some(tenant_column) => row_value(row, tenant_column.as_str()),
+            None => None,
+        };
+
+        self.transform_for_tenant(column, tenant_id.as_deref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{DomainMapOptions, DomainMapTransformer};
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = get_transformer();
+        let column = Column::NumberValue("domain".to_string(), 42);
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.number_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 42)
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_empty_string_value() {
+        let transformer = get_transformer();
+        let column = Column::StringValue("domain".to_string(), "".to_string());
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert_eq!(transformed_value, "")
+    }
+
+    #[test]
+    fn transform_maps_a_domain_to_the_same_fake_domain() {
+        let transformer = get_transformer();
+
+        let nancy = transformer.transform(Column::StringValue(
+            "email".to_string(),
+            "nancy.davolio@northwind.com".to_string(),
+        ));
+        let andrew = transformer.transform(Column::StringValue(
+            "email".to_string(),
+            "andrew.fuller@Northwind.com".to_string(),
+        ));
+        let other = transformer.transform(Column::StringValue(
+            "email".to_string(),
+            "janet.leverling@contoso.com".to_string(),
+        ));
+        let domain = transformer.transform(Column::StringValue(
+            "domain".to_string(),
+            "northwind.com".to_string(),
+        ));
+
+        let (nancy_local_part, nancy_domain) =
+            nancy.string_value().unwrap().split_once('@').unwrap();
+        let (_, andrew_domain) = andrew.string_value().unwrap().split_once('@').unwrap();
+        let (_, other_domain) = other.string_value().unwrap().split_once('@').unwrap();
+
+        assert_eq!(nancy_local_part, "nancy.davolio");
+        assert!(nancy_domain.ends_with(".test"));
+        assert_eq!(nancy_domain, andrew_domain);
+        assert_eq!(nancy_domain, domain.string_value().unwrap());
+        assert_ne!(nancy_domain, other_domain);
+    }
+
+    #[test]
+    fn transform_maps_a_domain_to_a_fake_domain_per_tenant() {
+        let transformer = DomainMapTransformer::new(
+            "github",
+            "users",
+            "email",
+            DomainMapOptions {
+                tenant_column: Some("tenant_id".to_string()),
+            },
+        );
+
+        let transform = |tenant_id: i128, email: &str| {
+            let row = vec![
+                Column::NumberValue("tenant_id".to_string(), tenant_id),
+                Column::StringValue("email".to_string(), email.to_string()),
+            ];
+            let transformed_column = transformer.transform_with_row(row[1].clone(), &row);
+            transformed_column.string_value().unwrap().to_string()
+        };
+
+        let nancy_tenant_1 = transform(1, "nancy.davolio@fabrikam.com");
+        let andrew_tenant_1 = transform(1, "andrew.fuller@fabrikam.com");
+        let nancy_tenant_2 = transform(2, "nancy.davolio@fabrikam.com");
+
+        // the same tenant and domain always give the same fake domain, another tenant gives another one
+        let domain = |email: &str| email.split_once('@').unwrap().1.to_string();
+        assert_eq!(domain(&nancy_tenant_1), domain(&andrew_tenant_1));
+        assert_eq!(nancy_tenant_1, transform(1, "nancy.davolio@fabrikam.com"));
+        assert_ne!(nancy_tenant_1, nancy_tenant_2);
+        assert_eq!(nancy_tenant_2, transform(2, "nancy.davolio@fabrikam.com"));
+    }
+
+    fn get_transformer() -> DomainMapTransformer {
+        DomainMapTransformer::new("github", "users", "email", DomainMapOptions::default())
+    }
+}
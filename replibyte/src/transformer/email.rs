@@ -1,18 +1,36 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::transformer::domain_map::fake_domain;
+use crate::transformer::seeded::rng;
 use crate::transformer::Transformer;
 use crate::types::Column;
 use fake::faker::internet::raw::SafeEmail;
 use fake::locales::EN;
 use fake::Fake;
+use serde::{Deserialize, Serialize};
 
 /// This struct is dedicated to replacing a string by an email address.
 pub struct EmailTransformer {
     database_name: String,
     table_name: String,
     column_name: String,
+    options: EmailOptions,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct EmailOptions {
+    /// keep the `+tag` of `user+tag@domain` addresses (default to false)
+    pub preserve_plus_tag: Option<bool>,
+    /// replace the domain by the fake domain of the `domain-map` transformer (default to false)
+    pub map_domain: Option<bool>,
+    /// pick the domain from these ones, e.g. domains you control so the emails don't bounce - the same original domain
+    /// always gives the same domain of the pool, and the pool takes precedence over `map_domain`
+    pub domain_pool: Option<Vec<String>>,
 }
 
 impl EmailTransformer {
-    pub fn new<S>(database_name: S, table_name: S, column_name: S) -> Self
+    pub fn new<S>(database_name: S, table_name: S, column_name: S, options: EmailOptions) -> Self
     where
         S: Into<String>,
     {
@@ -20,8 +38,63 @@ impl EmailTransformer {
             database_name: database_name.into(),
             table_name: table_name.into(),
             column_name: column_name.into(),
+            options,
         }
     }
+
+    fn email(&self, value: &str) -> String {
+        let email: String = SafeEmail(EN).fake_with_rng(&mut rng());
+
+        let plus_tag = match self.options.preserve_plus_tag.unwrap_or(false) {
+            true => value
+                .split('@')
+                .next()
+                .and_then(|local_part| local_part.split_once('+'))
+                .map(|(_, tag)| tag),
+            false => None,
+        };
+
+        let mapped_domain = match self.options.map_domain.unwrap_or(false) {
+            true => value
+                .rsplit_once('@')
+                .map(|(_, domain)| fake_domain(domain)),
+            false => None,
+        };
+
+        let mapped_domain = self.pool_domain(value).or(mapped_domain);
+
+        match email.split_once('@') {
+            Some((local_part, domain)) => {
+                let domain = mapped_domain.as_deref().unwrap_or(domain);
+
+                match plus_tag {
+                    Some(tag) => format!("{}+{}@{}", local_part, tag, domain),
+                    None => format!("{}@{}", local_part, domain),
+                }
+            }
+            None => email,
+        }
+    }
+
+    /// domain of the pool for the original domain (or the whole value without domain) - none without pool
+    fn pool_domain(&self, value: &str) -> Option<String> {
+        let domain_pool = self
+            .options
+            .domain_pool
+            .as_ref()
+            .filter(|domain_pool| !domain_pool.is_empty())?;
+
+        let original_domain = value
+            .rsplit_once('@')
+            .map(|(_, domain)| domain)
+            .unwrap_or(value);
+
+        let mut hasher = DefaultHasher::new();
+        original_domain.to_lowercase().hash(&mut hasher);
+        let idx = hasher.finish() as usize % domain_pool.len();
+
+        Some(domain_pool[idx].clone())
+    }
 }
 
 impl Default for EmailTransformer {
@@ -30,6 +103,7 @@ impl Default for EmailTransformer {
             database_name: String::default(),
             table_name: String::default(),
             column_name: String::default(),
+            options: EmailOptions::default(),
         }
     }
 }
@@ -60,7 +134,7 @@ impl Transformer for EmailTransformer {
             Column::StringValue(column_name, value) => {
                 let new_value = match value.len() {
                     len if len == 0 => value,
-                    _ => SafeEmail(EN).fake(),
+                    _ => self.email(value.as_str()),
                 };
 
                 Column::StringValue(column_name, new_value)
@@ -74,7 +148,9 @@ impl Transformer for EmailTransformer {
 mod tests {
     use crate::{transformer::Transformer, types::Column};
 
-    use super::EmailTransformer;
+    use crate::transformer::domain_map::{DomainMapOptions, DomainMapTransformer};
+
+    use super::{EmailOptions, EmailTransformer};
 
     #[test]
     fn transform_email_with_number_value() {
@@ -93,7 +169,7 @@ mod tests {
         let transformer = get_transformer();
         let column = Column::FloatNumberValue("email".to_string(), expected_value);
         let transformed_column = transformer.transform(column);
-        let transformed_value = transformed_column.float_number_value().unwrap();
+        let transformed_value = transformed_column.float_value().unwrap();
 
         assert_eq!(transformed_value.to_owned(), expected_value)
     }
@@ -120,7 +196,124 @@ mod tests {
         assert_ne!(transformed_value, "john.doe@company.com".to_string());
     }
 
+    #[test]
+    fn transform_email_preserves_the_plus_tag() {
+        let transformer = get_transformer_with_options(EmailOptions {
+            preserve_plus_tag: Some(true),
+            map_domain: None,
+            domain_pool: None,
+        });
+        let column = Column::StringValue(
+            "email".to_string(),
+            "john.doe+newsletter@company.com".to_string(),
+        );
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert!(!transformed_value.starts_with("john.doe+"));
+        assert!(transformed_value
+            .split('@')
+            .next()
+            .unwrap()
+            .ends_with("+newsletter"));
+    }
+
+    #[test]
+    fn transform_email_without_plus_tag() {
+        let transformer = get_transformer_with_options(EmailOptions {
+            preserve_plus_tag: Some(true),
+            map_domain: None,
+            domain_pool: None,
+        });
+        let column = Column::StringValue("email".to_string(), "john.doe@company.com".to_string());
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert!(!transformed_value.contains('+'));
+        assert!(transformed_value.contains('@'));
+
+        // the tag is not kept by default
+        let column = Column::StringValue(
+            "email".to_string(),
+            "john.doe+newsletter@company.com".to_string(),
+        );
+        let transformed_column = get_transformer().transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert!(!transformed_value.contains("+newsletter"));
+    }
+
+    #[test]
+    fn transform_email_with_the_domain_map() {
+        let transformer = get_transformer_with_options(EmailOptions {
+            preserve_plus_tag: None,
+            map_domain: Some(true),
+            domain_pool: None,
+        });
+        let domain_map_transformer =
+            DomainMapTransformer::new("github", "users", "domain", DomainMapOptions::default());
+
+        let nancy = transformer.transform(Column::StringValue(
+            "email".to_string(),
+            "nancy.davolio@acme.com".to_string(),
+        ));
+        let andrew = transformer.transform(Column::StringValue(
+            "email".to_string(),
+            "andrew.fuller@acme.com".to_string(),
+        ));
+        let domain = domain_map_transformer.transform(Column::StringValue(
+            "domain".to_string(),
+            "acme.com".to_string(),
+        ));
+
+        let (nancy_local_part, nancy_domain) =
+            nancy.string_value().unwrap().split_once('@').unwrap();
+        let (_, andrew_domain) = andrew.string_value().unwrap().split_once('@').unwrap();
+
+        assert_ne!(nancy_local_part, "nancy.davolio");
+        assert_eq!(nancy_domain, andrew_domain);
+        assert_eq!(nancy_domain, domain.string_value().unwrap());
+    }
+
+    #[test]
+    fn transform_email_with_a_domain_of_the_pool() {
+        let domain_pool = vec!["test.acme.dev".to_string(), "qa.acme.dev".to_string()];
+        let transformer = get_transformer_with_options(EmailOptions {
+            preserve_plus_tag: None,
+            map_domain: Some(true),
+            domain_pool: Some(domain_pool.clone()),
+        });
+
+        let mut domains = vec![];
+        for idx in 0..50 {
+            let column =
+                Column::StringValue("email".to_string(), format!("john.doe@company-{}.com", idx));
+            let transformed_column = transformer.transform(column);
+            let (_, domain) = transformed_column
+                .string_value()
+                .unwrap()
+                .split_once('@')
+                .unwrap();
+
+            assert!(domain_pool.contains(&domain.to_string()));
+            domains.push(domain.to_string());
+        }
+
+        // the same original domain always gives the same domain of the pool
+        let column = Column::StringValue("email".to_string(), "jane.doe@company-0.com".to_string());
+        let transformed_column = transformer.transform(column);
+
+        assert!(transformed_column
+            .string_value()
+            .unwrap()
+            .ends_with(&format!("@{}", domains[0])));
+    }
+
     fn get_transformer() -> EmailTransformer {
-        EmailTransformer::new("github", "users", "email")
+        get_transformer_with_options(EmailOptions::default())
+    }
+
+    fn get_transformer_with_options(options: EmailOptions) -> EmailTransformer {
+        EmailTransformer::new("github", "users", "email", options)
     }
 }
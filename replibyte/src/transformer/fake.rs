@@ -0,0 +1,325 @@
+use std::io::{Error, ErrorKind};
+
+use fake::faker::address::raw::{
+    BuildingNumber, CityName, CountryCode, CountryName, PostCode, StateName, StreetName, ZipCode,
+};
+use fake::faker::company::raw::{Buzzword, CompanyName, Industry, Profession};
+use fake::faker::currency::raw::{CurrencyCode, CurrencyName};
+use fake::faker::filesystem::raw::{FileName, FilePath, MimeType};
+use fake::faker::internet::raw::{
+    DomainSuffix, FreeEmail, IPv4, IPv6, MACAddress, SafeEmail, UserAgent, Username,
+};
+use fake::faker::job::raw as job;
+use fake::faker::lorem::raw::{Paragraph, Sentence, Word};
+use fake::faker::name::raw::{FirstName, LastName, Name, Suffix, Title};
+use fake::faker::phone_number::raw::{CellNumber, PhoneNumber};
+use fake::locales::EN;
+use fake::Fake;
+use serde::{Deserialize, Serialize};
+
+use crate::transformer::seeded::rng;
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+/// This struct is dedicated to replacing a value by a fake one of the faker category set in the options (e.g.
+/// `address.city`), instead of a dedicated transformer per category. [Lille]->[Port Ayla]
+pub struct FakeTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    category: FakeCategory,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct FakeOptions {
+    /// faker category of the generated values, as `<module>.<name>` (e.g. name.first, internet.email)
+    pub kind: String,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum FakeCategory {
+    AddressBuildingNumber,
+    AddressCity,
+    AddressCountry,
+    AddressCountryCode,
+    AddressPostCode,
+    AddressState,
+    AddressStreet,
+    AddressZipCode,
+    CompanyBuzzword,
+    CompanyIndustry,
+    CompanyName,
+    CompanyProfession,
+    CurrencyCode,
+    CurrencyName,
+    FilesystemFileName,
+    FilesystemFilePath,
+    FilesystemMimeType,
+    InternetDomainSuffix,
+    InternetEmail,
+    InternetFreeEmail,
+    InternetIpv4,
+    InternetIpv6,
+    InternetMacAddress,
+    InternetUserAgent,
+    InternetUsername,
+    JobField,
+    JobPosition,
+    JobSeniority,
+    JobTitle,
+    LoremParagraph,
+    LoremSentence,
+    LoremWord,
+    NameFirst,
+    NameFull,
+    NameLast,
+    NameSuffix,
+    NameTitle,
+    PhoneNumberCell,
+    PhoneNumberPhone,
+}
+
+impl FakeCategory {
+    fn from_kind(kind: &str) -> Option<Self> {
+        let category = match kind {
+            "address.building_number" => FakeCategory::AddressBuildingNumber,
+            "address.city" => FakeCategory::AddressCity,
+            "address.country" => FakeCategory::AddressCountry,
+            "address.country_code" => FakeCategory::AddressCountryCode,
+            "address.post_code" => FakeCategory::AddressPostCode,
+            "address.state" => FakeCategory::AddressState,
+            "address.street" => FakeCategory::AddressStreet,
+            "address.zip_code" => FakeCategory::AddressZipCode,
+            "company.buzzword" => FakeCategory::CompanyBuzzword,
+            "company.industry" => FakeCategory::CompanyIndustry,
+            "company.name" => FakeCategory::CompanyName,
+            "company.profession" => FakeCategory::CompanyProfession,
+            "currency.code" => FakeCategory::CurrencyCode,
+            "currency.name" => FakeCategory::CurrencyName,
+            "filesystem.file_name" => FakeCategory::FilesystemFileName,
+            "filesystem.file_path" => FakeCategory::FilesystemFilePath,
+            "filesystem.mime_type" => FakeCategory::FilesystemMimeType,
+            "internet.domain_suffix" => FakeCategory::InternetDomainSuffix,
+            "internet.email" => FakeCategory::InternetEmail,
+            "internet.free_email" => FakeCategory::InternetFreeEmail,
+            "internet.ipv4" => FakeCategory::InternetIpv4,
+            "internet.ipv6" => FakeCategory::InternetIpv6,
+            "internet.mac_address" => FakeCategory::InternetMacAddress,
+            "internet.user_agent" => FakeCategory::InternetUserAgent,
+            "internet.username" => FakeCategory::InternetUsername,
+            "job.field" => FakeCategory::JobField,
+            "job.position" => FakeCategory::JobPosition,
+            "job.seniority" => FakeCategory::JobSeniority,
+            "job.title" => FakeCategory::JobTitle,
+            "lorem.paragraph" => FakeCategory::LoremParagraph,
+            "lorem.sentence" => FakeCategory::LoremSentence,
+            "lorem.word" => FakeCategory::LoremWord,
+            "name.first" => FakeCategory::NameFirst,
+            "name.full" => FakeCategory::NameFull,
+            "name.last" => FakeCategory::NameLast,
+            "name.suffix" => FakeCategory::NameSuffix,
+            "name.title" => FakeCategory::NameTitle,
+            "phone_number.cell" => FakeCategory::PhoneNumberCell,
+            "phone_number.phone" => FakeCategory::PhoneNumberPhone,
+            _ => return None,
+        };
+
+        Some(category)
+    }
+
+    fn fake_value(&self) -> String {
+        let random = &mut rng();
+
+        match self {
+            FakeCategory::AddressBuildingNumber => BuildingNumber(EN).fake_with_rng(random),
+            FakeCategory::AddressCity => CityName(EN).fake_with_rng(random),
+            FakeCategory::AddressCountry => CountryName(EN).fake_with_rng(random),
+            FakeCategory::AddressCountryCode => CountryCode(EN).fake_with_rng(random),
+            FakeCategory::AddressPostCode => PostCode(EN).fake_with_rng(random),
+            FakeCategory::AddressState => StateName(EN).fake_with_rng(random),
+            FakeCategory::AddressStreet => StreetName(EN).fake_with_rng(random),
+            FakeCategory::AddressZipCode => ZipCode(EN).fake_with_rng(random),
+            FakeCategory::CompanyBuzzword => Buzzword(EN).fake_with_rng(random),
+            FakeCategory::CompanyIndustry => Industry(EN).fake_with_rng(random),
+            FakeCategory::CompanyName => CompanyName(EN).fake_with_rng(random),
+            FakeCategory::CompanyProfession => Profession(EN).fake_with_rng(random),
+            FakeCategory::CurrencyCode => CurrencyCode(EN).fake_with_rng(random),
+            FakeCategory::CurrencyName => CurrencyName(EN).fake_with_rng(random),
+            FakeCategory::FilesystemFileName => FileName(EN).fake_with_rng(random),
+            FakeCategory::FilesystemFilePath => FilePath(EN).fake_with_rng(random),
+            FakeCategory::FilesystemMimeType => MimeType(EN).fake_with_rng(random),
+            FakeCategory::InternetDomainSuffix => DomainSuffix(EN).fake_with_rng(random),
+            FakeCategory::InternetEmail => SafeEmail(EN).fake_with_rng(random),
+            FakeCategory::InternetFreeEmail => FreeEmail(EN).fake_with_rng(random),
+            FakeCategory::InternetIpv4 => IPv4(EN).fake_with_rng(random),
+            FakeCategory::InternetIpv6 => IPv6(EN).fake_with_rng(random),
+            FakeCategory::InternetMacAddress => MACAddress(EN).fake_with_rng(random),
+            FakeCategory::InternetUserAgent => UserAgent(EN).fake_with_rng(random),
+            FakeCategory::InternetUsername => Username(EN).fake_with_rng(random),
+            FakeCategory::JobField => job::Field(EN).fake_with_rng(random),
+            FakeCategory::JobPosition => job::Position(EN).fake_with_rng(random),
+            FakeCategory::JobSeniority => job::Seniority(EN).fake_with_rng(random),
+            FakeCategory::JobTitle => job::Title(EN).fake_with_rng(random),
+            FakeCategory::LoremParagraph => Paragraph(EN, 3..6).fake_with_rng(random),
+            FakeCategory::LoremSentence => Sentence(EN, 4..10).fake_with_rng(random),
+            FakeCategory::LoremWord => Word(EN).fake_with_rng(random),
+            FakeCategory::NameFirst => FirstName(EN).fake_with_rng(random),
+            FakeCategory::NameFull => Name(EN).fake_with_rng(random),
+            FakeCategory::NameLast => LastName(EN).fake_with_rng(random),
+            FakeCategory::NameSuffix => Suffix(EN).fake_with_rng(random),
+            FakeCategory::NameTitle => Title(EN).fake_with_rng(random),
+            FakeCategory::PhoneNumberCell => CellNumber(EN).fake_with_rng(random),
+            FakeCategory::PhoneNumberPhone => PhoneNumber(EN).fake_with_rng(random),
+        }
+    }
+}
+
+impl FakeTransformer {
+    pub fn new<S>(
+        database_name: S,
+        table_name: S,
+        column_name: S,
+        options: FakeOptions,
+    ) -> Result<Self, Error>
+    where
+        S: Into<String>,
+    {
+        let category = match FakeCategory::from_kind(options.kind.as_str()) {
+            Some(category) => category,
+            None => {
+                return Err(Error::new(
+                    ErrorKind::Other,
+                    format!("unknown kind '{}'", options.kind),
+                ))
+            }
+        };
+
+        Ok(FakeTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            category,
+        })
+    }
+}
+
+impl Default for FakeTransformer {
+    fn default() -> Self {
+        FakeTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            category: FakeCategory::AddressCity,
+        }
+    }
+}
+
+impl Transformer for FakeTransformer {
+    fn id(&self) -> &str {
+        "fake"
+    }
+
+    fn description(&self) -> &str {
+        "Generate a value of a faker category, e.g. address.city (string only). [Lille]->[Port Ayla]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        match column {
+            Column::StringValue(column_name, value) if value.is_empty() => {
+                Column::StringValue(column_name, value)
+            }
+            Column::StringValue(column_name, _) => {
+                Column::StringValue(column_name, self.category.fake_value())
+            }
+            column => column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{FakeOptions, FakeTransformer};
+
+    fn transform(kind: &str, value: &str) -> String {
+        let options = FakeOptions {
+            kind: kind.to_string(),
+        };
+        let transformer = FakeTransformer::new("github", "users", "profile", options).unwrap();
+        let column = Column::StringValue("profile".to_string(), value.to_string());
+        let transformed_column = transformer.transform(column);
+        transformed_column.string_value().unwrap().to_string()
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = FakeTransformer::default();
+        let column = Column::NumberValue("profile".to_string(), 42);
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.number_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 42)
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_empty_string() {
+        assert_eq!(transform("name.first", ""), "");
+    }
+
+    #[test]
+    fn transform_to_a_value_of_the_kind() {
+        for kind in [
+            "name.first",
+            "name.full",
+            "company.name",
+            "address.city",
+            "lorem.sentence",
+        ] {
+            let value = transform(kind, "Lille");
+            assert!(!value.trim().is_empty());
+            assert_ne!(value, "Lille");
+        }
+
+        let name = transform("name.full", "Lucas Martin");
+        assert!(name.contains(' '));
+
+        let email = transform("internet.email", "lucas@example.org");
+        assert!(email.contains('@'));
+        assert_ne!(email, "lucas@example.org");
+
+        let ip = transform("internet.ipv4", "192.168.0.1");
+        assert!(ip.parse::<Ipv4Addr>().is_ok());
+
+        let ip = transform("internet.ipv6", "::1");
+        assert!(ip.parse::<Ipv6Addr>().is_ok());
+
+        let phone_number = transform("phone_number.phone", "+33 6 12 34 56 78");
+        assert!(phone_number.chars().any(|c| c.is_ascii_digit()));
+    }
+
+    #[test]
+    fn new_fails_with_an_unknown_kind() {
+        for kind in ["name", "name.nickname", "internet.Email", ""] {
+            let options = FakeOptions {
+                kind: kind.to_string(),
+            };
+
+            assert!(FakeTransformer::new("github", "users", "profile", options).is_err());
+        }
+    }
+}
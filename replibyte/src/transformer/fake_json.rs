@@ -0,0 +1,188 @@
+use std::collections::BTreeMap;
+
+use fake::faker::address::raw::CityName;
+use fake::faker::internet::raw::SafeEmail;
+use fake::faker::lorem::raw::Word;
+use fake::faker::name::raw::{FirstName, LastName};
+use fake::faker::phone_number::raw::PhoneNumber;
+use fake::locales::EN;
+use fake::Fake;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+/// This struct is dedicated to replacing a JSON document by a fake one with the configured fields.
+pub struct FakeJsonTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    options: FakeJsonOptions,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct FakeJsonOptions {
+    /// fields of the generated JSON object and the kind of fake value of each one
+    pub shape: BTreeMap<String, FakeJsonKind>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+pub enum FakeJsonKind {
+    FirstName,
+    LastName,
+    Email,
+    PhoneNumber,
+    City,
+    Word,
+    Number,
+    Boolean,
+}
+
+impl FakeJsonKind {
+    fn fake_value(&self) -> Value {
+        match self {
+            FakeJsonKind::FirstName => Value::String(FirstName(EN).fake()),
+            FakeJsonKind::LastName => Value::String(LastName(EN).fake()),
+            FakeJsonKind::Email => Value::String(SafeEmail(EN).fake()),
+            FakeJsonKind::PhoneNumber => Value::String(PhoneNumber(EN).fake()),
+            FakeJsonKind::City => Value::String(CityName(EN).fake()),
+            FakeJsonKind::Word => Value::String(Word(EN).fake()),
+            FakeJsonKind::Number => Value::from(rand::thread_rng().gen_range(0..10_000)),
+            FakeJsonKind::Boolean => Value::Bool(rand::thread_rng().gen()),
+        }
+    }
+}
+
+impl FakeJsonTransformer {
+    pub fn new<S>(database_name: S, table_name: S, column_name: S, options: FakeJsonOptions) -> Self
+    where
+        S: Into<String>,
+    {
+        FakeJsonTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            options,
+        }
+    }
+}
+
+impl Default for FakeJsonTransformer {
+    fn default() -> Self {
+        FakeJsonTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            options: FakeJsonOptions::default(),
+        }
+    }
+}
+
+impl Transformer for FakeJsonTransformer {
+    fn id(&self) -> &str {
+        "fake-json"
+    }
+
+    fn description(&self) -> &str {
+        "Generate a JSON object with the configured fields (string only). [{\"name\": \"Nancy\"}]->[{\"name\": \"Tony\"}]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        match column {
+            Column::StringValue(column_name, value) => {
+                if value.is_empty() {
+                    return Column::StringValue(column_name, value);
+                }
+
+                let fake_json = self
+                    .options
+                    .shape
+                    .iter()
+                    .map(|(field, kind)| (field.to_string(), kind.fake_value()))
+                    .collect::<Map<_, _>>();
+
+                Column::StringValue(column_name, Value::Object(fake_json).to_string())
+            }
+            column => column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde_json::Value;
+
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{FakeJsonKind, FakeJsonOptions, FakeJsonTransformer};
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = get_transformer();
+        let column = Column::NumberValue("profile".to_string(), 42);
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.number_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 42)
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_empty_string_value() {
+        let transformer = get_transformer();
+        let column = Column::StringValue("profile".to_string(), "".to_string());
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert_eq!(transformed_value, "")
+    }
+
+    #[test]
+    fn transform_generates_the_configured_shape() {
+        let transformer = get_transformer();
+        let column = Column::StringValue(
+            "profile".to_string(),
+            r#"{"name": "Nancy", "email": "nancy.davolio@northwind.com", "age": 42, "ssn": "078-05-1120"}"#
+                .to_string(),
+        );
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        let json: Value = serde_json::from_str(transformed_value).unwrap();
+        let json = json.as_object().unwrap();
+
+        assert_eq!(json.len(), 4);
+        assert!(json["name"].is_string());
+        assert!(json["email"].as_str().unwrap().contains('@'));
+        assert!(json["age"].is_u64());
+        assert!(json["active"].is_boolean());
+        assert!(!transformed_value.contains("nancy.davolio@northwind.com"));
+    }
+
+    fn get_transformer() -> FakeJsonTransformer {
+        let shape = BTreeMap::from([
+            ("name".to_string(), FakeJsonKind::FirstName),
+            ("email".to_string(), FakeJsonKind::Email),
+            ("age".to_string(), FakeJsonKind::Number),
+            ("active".to_string(), FakeJsonKind::Boolean),
+        ]);
+
+        FakeJsonTransformer::new("github", "users", "profile", FakeJsonOptions { shape })
+    }
+}
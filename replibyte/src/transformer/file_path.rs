@@ -0,0 +1,180 @@
+use crate::transformer::Transformer;
+use crate::types::Column;
+use fake::faker::lorem::raw::Word;
+use fake::locales::EN;
+use fake::Fake;
+use serde::{Deserialize, Serialize};
+
+/// This struct is dedicated to replacing a file path by a fake one with the same depth.
+pub struct FilePathTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    options: FilePathOptions,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy, Default)]
+pub struct FilePathOptions {
+    /// keep the extension of the file name (default to true)
+    pub keep_extension: Option<bool>,
+}
+
+impl FilePathTransformer {
+    pub fn new<S>(database_name: S, table_name: S, column_name: S, options: FilePathOptions) -> Self
+    where
+        S: Into<String>,
+    {
+        FilePathTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            options,
+        }
+    }
+}
+
+impl Default for FilePathTransformer {
+    fn default() -> Self {
+        FilePathTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            options: FilePathOptions::default(),
+        }
+    }
+}
+
+impl Transformer for FilePathTransformer {
+    fn id(&self) -> &str {
+        "file-path"
+    }
+
+    fn description(&self) -> &str {
+        "Generate a file path with the same depth (string only). [/uploads/u123/passport.jpg]->[/dolor/sit/amet.jpg]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        match column {
+            Column::StringValue(column_name, value) => {
+                if value.is_empty() {
+                    return Column::StringValue(column_name, value);
+                }
+
+                let separator = match value.contains('/') || !value.contains('\\') {
+                    true => '/',
+                    false => '\\',
+                };
+
+                let segments = value.split(separator).collect::<Vec<_>>();
+                let file_name = segments.last().unwrap_or(&"");
+
+                let extension = match file_name.rfind('.') {
+                    Some(pos) if pos > 0 && self.options.keep_extension.unwrap_or(true) => {
+                        &file_name[pos..]
+                    }
+                    _ => "",
+                };
+
+                // empty segments are kept to preserve leading, trailing and doubled separators
+                let mut new_segments = segments
+                    .iter()
+                    .map(|segment| match segment.is_empty() {
+                        true => String::new(),
+                        false => Word(EN).fake::<String>(),
+                    })
+                    .collect::<Vec<_>>();
+
+                if let Some(new_file_name) = new_segments.last_mut() {
+                    if !new_file_name.is_empty() {
+                        new_file_name.push_str(extension);
+                    }
+                }
+
+                Column::StringValue(
+                    column_name,
+                    new_segments.join(separator.to_string().as_str()),
+                )
+            }
+            column => column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{FilePathOptions, FilePathTransformer};
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = get_transformer(None);
+        let column = Column::NumberValue("path".to_string(), 42);
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.number_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 42)
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_empty_string_value() {
+        let transformer = get_transformer(None);
+        let column = Column::StringValue("path".to_string(), "".to_string());
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert_eq!(transformed_value, "")
+    }
+
+    #[test]
+    fn transform_file_path_keeps_the_extension() {
+        let transformer = get_transformer(Some(true));
+        let column =
+            Column::StringValue("path".to_string(), "/uploads/u123/passport.jpg".to_string());
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert!(!transformed_value.contains("u123"));
+        assert!(!transformed_value.contains("passport"));
+        assert!(transformed_value.starts_with('/'));
+        assert!(transformed_value.ends_with(".jpg"));
+        assert_eq!(transformed_value.matches('/').count(), 3);
+    }
+
+    #[test]
+    fn transform_file_path_replaces_the_extension() {
+        let transformer = get_transformer(Some(false));
+        let column = Column::StringValue(
+            "path".to_string(),
+            "uploads\\u123\\passport.jpg".to_string(),
+        );
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert!(!transformed_value.contains("u123"));
+        assert!(!transformed_value.contains("passport"));
+        assert!(!transformed_value.contains('.'));
+        assert_eq!(transformed_value.matches('\\').count(), 2);
+    }
+
+    fn get_transformer(keep_extension: Option<bool>) -> FilePathTransformer {
+        FilePathTransformer::new(
+            "github",
+            "uploads",
+            "path",
+            FilePathOptions { keep_extension },
+        )
+    }
+}
@@ -1,3 +1,4 @@
+use crate::transformer::seeded::rng;
 use crate::transformer::Transformer;
 use crate::types::Column;
 use fake::faker::name::raw::FirstName;
@@ -65,7 +66,7 @@ impl Transformer for FirstNameTransformer {
                 let new_value = if value == "" {
                     "".to_string()
                 } else {
-                    FirstName(EN).fake()
+                    FirstName(EN).fake_with_rng(&mut rng())
                 };
 
                 Column::StringValue(column_name, new_value)
@@ -100,7 +101,7 @@ mod tests {
         let transformer = get_transformer();
         let column = Column::FloatNumberValue("first_name".to_string(), expected_value);
         let transformed_column = transformer.transform(column);
-        let transformed_value = transformed_column.float_number_value().unwrap();
+        let transformed_value = transformed_column.float_value().unwrap();
 
         assert_eq!(transformed_value.to_owned(), expected_value)
     }
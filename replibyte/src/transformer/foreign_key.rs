@@ -0,0 +1,247 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io::{Error, ErrorKind};
+use std::rc::Rc;
+
+use crate::config::ForeignKeyConfig;
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+/// Transformer of a primary key, with the transformed value of each original value shared by the primary key column
+/// and the foreign key columns referencing it.
+struct SharedMapping {
+    transformer: Box<dyn Transformer>,
+    values: RefCell<HashMap<String, Column>>,
+}
+
+/// This struct is dedicated to giving a primary key and the foreign keys referencing it the same transformed values.
+/// The first table read (the referenced one or not) transforms the value, the other ones reuse it, so the foreign
+/// keys still match after the restore.
+pub struct ForeignKeyTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    mapping: Rc<SharedMapping>,
+}
+
+impl ForeignKeyTransformer {
+    fn new<S>(database_name: S, table_name: S, column_name: S, mapping: Rc<SharedMapping>) -> Self
+    where
+        S: Into<String>,
+    {
+        ForeignKeyTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            mapping,
+        }
+    }
+}
+
+/// original value of the column, to find its transformed value - none for NULL
+fn mapping_key(column: &Column) -> Option<String> {
+    match column {
+        Column::NumberValue(_, value) => Some(value.to_string()),
+        Column::FloatNumberValue(_, value) => Some(value.to_string()),
+        Column::StringValue(_, value) => Some(value.clone()),
+        Column::CharValue(_, value) => Some(value.to_string()),
+        Column::BooleanValue(_, value) => Some(value.to_string()),
+        Column::None(_) => None,
+    }
+}
+
+/// the same column value under another column name
+fn with_name(column: Column, column_name: String) -> Column {
+    match column {
+        Column::NumberValue(_, value) => Column::NumberValue(column_name, value),
+        Column::FloatNumberValue(_, value) => Column::FloatNumberValue(column_name, value),
+        Column::StringValue(_, value) => Column::StringValue(column_name, value),
+        Column::CharValue(_, value) => Column::CharValue(column_name, value),
+        Column::BooleanValue(_, value) => Column::BooleanValue(column_name, value),
+        Column::None(_) => Column::None(column_name),
+    }
+}
+
+impl Transformer for ForeignKeyTransformer {
+    fn id(&self) -> &str {
+        self.mapping.transformer.id()
+    }
+
+    fn description(&self) -> &str {
+        self.mapping.transformer.description()
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        let key = match mapping_key(&column) {
+            Some(key) => key,
+            None => return column,
+        };
+
+        let column_name = column.name().to_string();
+        let mut values = self.mapping.values.borrow_mut();
+        let transformed_column = values
+            .entry(key)
+            .or_insert_with(|| self.mapping.transformer.transform(column))
+            .clone();
+
+        with_name(transformed_column, column_name)
+    }
+
+    fn validate(&self, column: &Column) -> Result<(), Error> {
+        self.mapping.transformer.validate(column)
+    }
+}
+
+/// Replace the transformer of each referenced primary key by transformers sharing its transformed values with the
+/// foreign key columns referencing it.
+pub fn link_foreign_keys(
+    transformers: Vec<Box<dyn Transformer>>,
+    foreign_keys: &[ForeignKeyConfig],
+) -> Result<Vec<Box<dyn Transformer>>, Error> {
+    let mut transformers = transformers;
+    let mut mappings: HashMap<String, Rc<SharedMapping>> = HashMap::new();
+
+    for foreign_key in foreign_keys {
+        let column_name = format!(
+            "{}.{}.{}",
+            foreign_key.database, foreign_key.table, foreign_key.column
+        );
+        let referenced_column_name = format!(
+            "{}.{}.{}",
+            foreign_key.database, foreign_key.referenced_table, foreign_key.referenced_column
+        );
+
+        if transformers
+            .iter()
+            .any(|transformer| transformer.database_and_table_and_column_name() == column_name)
+        {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!(
+                    "foreign key \"{}\" can't have a transformer - it gets the one of \"{}\"",
+                    column_name, referenced_column_name
+                ),
+            ));
+        }
+
+        if !mappings.contains_key(&referenced_column_name) {
+            let idx = transformers
+                .iter()
+                .position(|transformer| {
+                    transformer.database_and_table_and_column_name() == referenced_column_name
+                })
+                .ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::Other,
+                        format!(
+                            "foreign key \"{}\" references \"{}\", which has no transformer",
+                            column_name, referenced_column_name
+                        ),
+                    )
+                })?;
+
+            let mapping = Rc::new(SharedMapping {
+                transformer: transformers.remove(idx),
+                values: RefCell::new(HashMap::new()),
+            });
+
+            transformers.push(Box::new(ForeignKeyTransformer::new(
+                foreign_key.database.as_str(),
+                foreign_key.referenced_table.as_str(),
+                foreign_key.referenced_column.as_str(),
+                mapping.clone(),
+            )));
+            mappings.insert(referenced_column_name.clone(), mapping);
+        }
+
+        transformers.push(Box::new(ForeignKeyTransformer::new(
+            foreign_key.database.as_str(),
+            foreign_key.table.as_str(),
+            foreign_key.column.as_str(),
+            mappings[&referenced_column_name].clone(),
+        )));
+    }
+
+    Ok(transformers)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::ForeignKeyConfig;
+    use crate::transformer::random::RandomTransformer;
+    use crate::transformer::Transformer;
+    use crate::types::Column;
+
+    use super::link_foreign_keys;
+
+    fn foreign_key(table: &str, column: &str) -> ForeignKeyConfig {
+        ForeignKeyConfig {
+            database: "public".to_string(),
+            table: table.to_string(),
+            column: column.to_string(),
+            referenced_table: "users".to_string(),
+            referenced_column: "id".to_string(),
+        }
+    }
+
+    fn transform(transformers: &[Box<dyn Transformer>], name: &str, value: i128) -> Column {
+        let transformer = transformers
+            .iter()
+            .find(|transformer| transformer.database_and_table_and_column_name() == name)
+            .unwrap();
+
+        transformer.transform(Column::NumberValue(name.to_string(), value))
+    }
+
+    #[test]
+    fn transform_the_foreign_keys_to_the_values_of_the_primary_key() {
+        let transformers: Vec<Box<dyn Transformer>> =
+            vec![Box::new(RandomTransformer::new("public", "users", "id"))];
+        let foreign_keys = vec![
+            foreign_key("orders", "user_id"),
+            foreign_key("reviews", "author_id"),
+        ];
+
+        let transformers = link_foreign_keys(transformers, &foreign_keys).unwrap();
+        assert_eq!(transformers.len(), 3);
+
+        for id in 0..100 {
+            // a foreign key can be read before the primary key
+            let user_id = transform(&transformers, "public.orders.user_id", id);
+            let primary_key = transform(&transformers, "public.users.id", id);
+            let author_id = transform(&transformers, "public.reviews.author_id", id);
+
+            assert_eq!(user_id.name(), "public.orders.user_id");
+            assert_eq!(user_id.number_value(), primary_key.number_value());
+            assert_eq!(author_id.number_value(), primary_key.number_value());
+        }
+
+        let user_id = transformers[1].transform(Column::None("user_id".to_string()));
+        assert!(user_id.is_null());
+    }
+
+    #[test]
+    fn link_foreign_keys_fails_without_the_primary_key_transformer() {
+        let transformers: Vec<Box<dyn Transformer>> = vec![Box::new(RandomTransformer::new(
+            "public", "orders", "user_id",
+        ))];
+
+        // the foreign key has a transformer of its own
+        assert!(link_foreign_keys(transformers, &[foreign_key("orders", "user_id")]).is_err());
+
+        // the primary key has no transformer
+        assert!(link_foreign_keys(vec![], &[foreign_key("orders", "user_id")]).is_err());
+    }
+}
@@ -0,0 +1,261 @@
+use std::collections::HashSet;
+use std::fs::read_to_string;
+use std::io::{Error, ErrorKind};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::TransformerTypeConfig;
+use crate::transformer::transient::TransientTransformer;
+use crate::transformer::{row_value, Transformer};
+use crate::types::Column;
+
+/// This struct is dedicated to transforming a column only for the rows whose id is listed (e.g. the users asking
+/// for their data to be erased). The other rows are kept as they are.
+pub struct IdListTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    ids_column: String,
+    ids: HashSet<String>,
+    transformer: Box<dyn Transformer>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct IdListOptions {
+    /// column of the row holding its id (e.g. user_id)
+    pub ids_column: String,
+    /// ids of the rows to transform
+    #[serde(default)]
+    pub ids: Vec<String>,
+    /// file with one id per line, in addition to `ids`
+    pub ids_file: Option<String>,
+
+    /// transformer of the listed rows
+    #[serde(flatten)]
+    pub transformer: Box<TransformerTypeConfig>,
+}
+
+/// parse the ids of an ids file - one per line, the empty lines are ignored
+fn parse_ids(content: &str) -> impl Iterator<Item = String> + '_ {
+    content
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| line.to_string())
+}
+
+impl IdListTransformer {
+    pub fn new<S>(
+        database_name: S,
+        table_name: S,
+        column_name: S,
+        options: &IdListOptions,
+    ) -> Result<Self, Error>
+    where
+        S: Into<String>,
+    {
+        let database_name = database_name.into();
+        let table_name = table_name.into();
+        let column_name = column_name.into();
+
+        let mut ids = options.ids.iter().cloned().collect::<HashSet<_>>();
+
+        if let Some(path) = &options.ids_file {
+            let content = read_to_string(path.as_str()).map_err(|err| {
+                Error::new(
+                    ErrorKind::Other,
+                    format!("cannot read ids file '{}': {}", path, err),
+                )
+            })?;
+
+            ids.extend(parse_ids(content.as_str()));
+        }
+
+        let transformer = options.transformer.transformer(
+            database_name.as_str(),
+            table_name.as_str(),
+            column_name.as_str(),
+        );
+
+        Ok(IdListTransformer {
+            database_name,
+            table_name,
+            column_name,
+            ids_column: options.ids_column.clone(),
+            ids,
+            transformer,
+        })
+    }
+}
+
+impl Default for IdListTransformer {
+    fn default() -> Self {
+        IdListTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            ids_column: String::default(),
+            ids: HashSet::new(),
+            transformer: Box::new(TransientTransformer::default()),
+        }
+    }
+}
+
+impl Transformer for IdListTransformer {
+    fn id(&self) -> &str {
+        "id-list"
+    }
+
+    fn description(&self) -> &str {
+        "Apply a transformer only to the rows whose id is listed. [id: 42, email: john@gmail.com]->[id: 42, email: tony@example.com]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        // the id is unknown without the row
+        column
+    }
+
+    fn transform_with_row(&self, column: Column, row: &[Column]) -> Column {
+        match row_value(row, self.ids_column.as_str()) {
+            Some(id) if self.ids.contains(id.as_str()) => {
+                self.transformer.transform_with_row(column, row)
+            }
+            _ => column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::write;
+
+    use tempfile::tempdir;
+
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{IdListOptions, IdListTransformer};
+
+    fn get_transformer(yaml: &str) -> IdListTransformer {
+        let options: IdListOptions = serde_yaml::from_str(yaml).unwrap();
+        IdListTransformer::new("github", "users", "email", &options).unwrap()
+    }
+
+    fn transform(transformer: &IdListTransformer, id: i128, email: &str) -> String {
+        let row = vec![
+            Column::NumberValue("id".to_string(), id),
+            Column::StringValue("email".to_string(), email.to_string()),
+        ];
+
+        let transformed_column = transformer.transform_with_row(row[1].clone(), &row);
+        transformed_column.string_value().unwrap().to_string()
+    }
+
+    #[test]
+    fn transform_only_the_listed_ids() {
+        let transformer = get_transformer(
+            r#"
+ids_column: id
+ids: ['2', '3']
+transformer_name: redacted
+"#,
+        );
+
+        assert_eq!(
+            transform(&transformer, 1, "nancy.davolio@northwind.com"),
+            "nancy.davolio@northwind.com"
+        );
+        assert_eq!(
+            transform(&transformer, 2, "andrew.fuller@northwind.com"),
+            "and**********"
+        );
+        assert_eq!(
+            transform(&transformer, 3, "janet@northwind.com"),
+            "jan**********"
+        );
+        assert_eq!(
+            transform(&transformer, 4, "margaret.peacock@northwind.com"),
+            "margaret.peacock@northwind.com"
+        );
+    }
+
+    #[test]
+    fn transform_the_ids_of_the_ids_file() {
+        let dir = tempdir().expect("cannot create tempdir");
+        let path = dir.path().join("ids.txt");
+        write(&path, "2\n\n 4 \n").unwrap();
+
+        let transformer = get_transformer(
+            format!(
+                r#"
+ids_column: id
+ids: ['1']
+ids_file: {}
+transformer_name: redacted
+"#,
+                path.to_str().unwrap()
+            )
+            .as_str(),
+        );
+
+        assert_eq!(
+            transform(&transformer, 1, "nancy@northwind.com"),
+            "nan**********"
+        );
+        assert_eq!(
+            transform(&transformer, 2, "andrew@northwind.com"),
+            "and**********"
+        );
+        assert_eq!(
+            transform(&transformer, 3, "janet@northwind.com"),
+            "janet@northwind.com"
+        );
+        assert_eq!(
+            transform(&transformer, 4, "margaret@northwind.com"),
+            "mar**********"
+        );
+    }
+
+    #[test]
+    fn transform_doesnt_change_without_the_row() {
+        let transformer = get_transformer(
+            r#"
+ids_column: id
+ids: ['1']
+transformer_name: redacted
+"#,
+        );
+        let column = Column::StringValue("email".to_string(), "nancy@northwind.com".to_string());
+        let transformed_column = transformer.transform(column);
+
+        assert_eq!(
+            transformed_column.string_value().unwrap(),
+            "nancy@northwind.com"
+        );
+    }
+
+    #[test]
+    fn new_fails_with_a_missing_ids_file() {
+        let options: IdListOptions = serde_yaml::from_str(
+            r#"
+ids_column: id
+ids_file: /nonexistent/ids.txt
+transformer_name: redacted
+"#,
+        )
+        .unwrap();
+
+        assert!(IdListTransformer::new("github", "users", "email", &options).is_err());
+    }
+}
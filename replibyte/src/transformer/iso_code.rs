@@ -0,0 +1,185 @@
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+/// ISO 3166-1 alpha-2 country codes
+const COUNTRY_CODES: [&str; 40] = [
+    "AR", "AT", "AU", "BE", "BR", "CA", "CH", "CL", "CN", "CO", "CZ", "DE", "DK", "EG", "ES", "FI",
+    "FR", "GB", "GR", "HU", "ID", "IE", "IL", "IN", "IT", "JP", "KR", "MA", "MX", "NG", "NL", "NO",
+    "NZ", "PL", "PT", "SE", "SG", "TR", "US", "ZA",
+];
+
+/// ISO 639-1 language codes
+const LANGUAGE_CODES: [&str; 32] = [
+    "ar", "bg", "cs", "da", "de", "el", "en", "es", "et", "fi", "fr", "he", "hi", "hu", "id", "it",
+    "ja", "ko", "lt", "lv", "nl", "no", "pl", "pt", "ro", "ru", "sk", "sv", "th", "tr", "uk", "zh",
+];
+
+/// ISO 4217 currency codes
+const CURRENCY_CODES: [&str; 30] = [
+    "ARS", "AUD", "BRL", "CAD", "CHF", "CLP", "CNY", "CZK", "DKK", "EGP", "EUR", "GBP", "HKD",
+    "HUF", "IDR", "ILS", "INR", "JPY", "KRW", "MAD", "MXN", "NGN", "NOK", "NZD", "PLN", "SEK",
+    "SGD", "TRY", "USD", "ZAR",
+];
+
+/// This struct is dedicated to replacing an ISO code by another valid code of the same kind.
+pub struct IsoCodeTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    options: IsoCodeOptions,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+pub struct IsoCodeOptions {
+    pub kind: IsoCodeKind,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum IsoCodeKind {
+    /// ISO 3166-1 alpha-2 (e.g. FR)
+    Country,
+    /// ISO 639-1 (e.g. fr)
+    Language,
+    /// ISO 4217 (e.g. EUR)
+    Currency,
+}
+
+impl IsoCodeKind {
+    fn codes(&self) -> &'static [&'static str] {
+        match self {
+            IsoCodeKind::Country => &COUNTRY_CODES,
+            IsoCodeKind::Language => &LANGUAGE_CODES,
+            IsoCodeKind::Currency => &CURRENCY_CODES,
+        }
+    }
+}
+
+impl IsoCodeTransformer {
+    pub fn new<S>(database_name: S, table_name: S, column_name: S, options: IsoCodeOptions) -> Self
+    where
+        S: Into<String>,
+    {
+        IsoCodeTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            options,
+        }
+    }
+}
+
+impl Default for IsoCodeTransformer {
+    fn default() -> Self {
+        IsoCodeTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            options: IsoCodeOptions {
+                kind: IsoCodeKind::Country,
+            },
+        }
+    }
+}
+
+impl Transformer for IsoCodeTransformer {
+    fn id(&self) -> &str {
+        "iso-code"
+    }
+
+    fn description(&self) -> &str {
+        "Generate a valid ISO country, language or currency code (string only). [FR]->[JP]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        match column {
+            Column::StringValue(column_name, value) => {
+                if value.is_empty() {
+                    return Column::StringValue(column_name, value);
+                }
+
+                // pick another code than the original one
+                let new_value = self
+                    .options
+                    .kind
+                    .codes()
+                    .iter()
+                    .filter(|code| !code.eq_ignore_ascii_case(value.as_str()))
+                    .copied()
+                    .collect::<Vec<_>>()
+                    .choose(&mut rand::thread_rng())
+                    .unwrap()
+                    .to_string();
+
+                Column::StringValue(column_name, new_value)
+            }
+            column => column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{IsoCodeKind, IsoCodeOptions, IsoCodeTransformer};
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = get_transformer(IsoCodeKind::Country);
+        let column = Column::NumberValue("country".to_string(), 42);
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.number_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 42)
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_empty_string_value() {
+        let transformer = get_transformer(IsoCodeKind::Country);
+        let column = Column::StringValue("country".to_string(), "".to_string());
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert_eq!(transformed_value, "")
+    }
+
+    #[test]
+    fn transform_returns_a_valid_code_of_the_kind() {
+        for (kind, value) in [
+            (IsoCodeKind::Country, "FR"),
+            (IsoCodeKind::Language, "fr"),
+            (IsoCodeKind::Currency, "EUR"),
+        ] {
+            let transformer = get_transformer(kind);
+
+            for _ in 0..50 {
+                let column = Column::StringValue("code".to_string(), value.to_string());
+                let transformed_column = transformer.transform(column);
+                let transformed_value = transformed_column.string_value().unwrap();
+
+                assert!(kind.codes().contains(&transformed_value));
+                assert_ne!(transformed_value, value);
+            }
+        }
+    }
+
+    fn get_transformer(kind: IsoCodeKind) -> IsoCodeTransformer {
+        IsoCodeTransformer::new("github", "users", "country", IsoCodeOptions { kind })
+    }
+}
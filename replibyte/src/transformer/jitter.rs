@@ -0,0 +1,178 @@
+use std::io::{Error, ErrorKind};
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::transformer::seeded::rng;
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+/// This struct is dedicated to multiplying a number by a random factor within ± a percentage of it, to hide the exact
+/// values of a column (e.g. metrics) while keeping them realistic. [1000]->[1043]
+pub struct JitterTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    options: JitterOptions,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy, Default)]
+pub struct JitterOptions {
+    /// maximum variation of the values, in percent (e.g. 10 for ±10%)
+    pub percent: f64,
+}
+
+impl JitterTransformer {
+    pub fn new<S>(
+        database_name: S,
+        table_name: S,
+        column_name: S,
+        options: JitterOptions,
+    ) -> Result<Self, Error>
+    where
+        S: Into<String>,
+    {
+        if !(0.0..=100.0).contains(&options.percent) {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!("percent ({}) must be between 0 and 100", options.percent),
+            ));
+        }
+
+        Ok(JitterTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            options,
+        })
+    }
+
+    fn jitter(&self, value: f64) -> f64 {
+        let ratio = self.options.percent / 100.0;
+        let factor = rng().gen_range(1.0 - ratio..=1.0 + ratio);
+
+        value * factor
+    }
+}
+
+impl Default for JitterTransformer {
+    fn default() -> Self {
+        JitterTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            options: JitterOptions::default(),
+        }
+    }
+}
+
+impl Transformer for JitterTransformer {
+    fn id(&self) -> &str {
+        "jitter"
+    }
+
+    fn description(&self) -> &str {
+        "Multiply a number by a random factor within ± a percentage of it (number and float only). [1000]->[1043]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        match column {
+            Column::NumberValue(column_name, value) => {
+                // rounded to stay an integer - the cast saturates instead of overflowing
+                Column::NumberValue(column_name, self.jitter(value as f64).round() as i128)
+            }
+            Column::FloatNumberValue(column_name, value) => {
+                Column::FloatNumberValue(column_name, self.jitter(value))
+            }
+            column => column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{JitterOptions, JitterTransformer};
+
+    fn transformer(percent: f64) -> JitterTransformer {
+        JitterTransformer::new("github", "metrics", "count", JitterOptions { percent }).unwrap()
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_string_value() {
+        let transformer = transformer(10.0);
+        let column = Column::StringValue("count".to_string(), "1000".to_string());
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert_eq!(transformed_value, "1000")
+    }
+
+    #[test]
+    fn transform_number_within_the_jitter_band() {
+        let transformer = transformer(10.0);
+        let mut values = vec![];
+
+        for _ in 0..1000 {
+            let column = Column::NumberValue("count".to_string(), 1000);
+            let transformed_column = transformer.transform(column);
+            let transformed_value = *transformed_column.number_value().unwrap();
+
+            assert!((900..=1100).contains(&transformed_value));
+            values.push(transformed_value);
+        }
+
+        // the values are perturbed
+        assert!(values.iter().any(|value| *value != 1000));
+
+        let column = Column::NumberValue("count".to_string(), -50);
+        let transformed_column = transformer.transform(column);
+        assert!((-55..=-45).contains(transformed_column.number_value().unwrap()));
+    }
+
+    #[test]
+    fn transform_float_within_the_jitter_band() {
+        let transformer = transformer(25.0);
+
+        for _ in 0..1000 {
+            let column = Column::FloatNumberValue("ratio".to_string(), 0.8);
+            let transformed_column = transformer.transform(column);
+            let transformed_value = *transformed_column.float_value().unwrap();
+
+            assert!((0.6 - f64::EPSILON..=1.0 + f64::EPSILON).contains(&transformed_value));
+        }
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_no_jitter() {
+        let transformer = transformer(0.0);
+        let column = Column::NumberValue("count".to_string(), 1000);
+        let transformed_column = transformer.transform(column);
+
+        assert_eq!(*transformed_column.number_value().unwrap(), 1000);
+        assert!(transformer
+            .transform(Column::None("count".to_string()))
+            .is_null());
+    }
+
+    #[test]
+    fn new_fails_with_an_invalid_percent() {
+        for percent in [-1.0, 100.5, f64::NAN] {
+            let options = JitterOptions { percent };
+            assert!(JitterTransformer::new("github", "metrics", "count", options).is_err());
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 use crate::transformer::Transformer;
 use crate::types::Column;
 
@@ -5,10 +7,39 @@ pub struct KeepFirstCharTransformer {
     database_name: String,
     table_name: String,
     column_name: String,
+    options: KeepFirstCharOptions,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+pub struct KeepFirstCharOptions {
+    /// number of characters (or digits for numbers) kept at the start of the value
+    pub keep: usize,
+    /// character replacing each of the other characters (default to `*`) - set to null to remove them
+    #[serde(default = "default_mask_char")]
+    pub mask_char: Option<char>,
+}
+
+fn default_mask_char() -> Option<char> {
+    Some('*')
+}
+
+impl Default for KeepFirstCharOptions {
+    /// keep the first character only, without mask
+    fn default() -> Self {
+        KeepFirstCharOptions {
+            keep: 1,
+            mask_char: None,
+        }
+    }
 }
 
 impl KeepFirstCharTransformer {
-    pub fn new<S>(database_name: S, table_name: S, column_name: S) -> Self
+    pub fn new<S>(
+        database_name: S,
+        table_name: S,
+        column_name: S,
+        options: KeepFirstCharOptions,
+    ) -> Self
     where
         S: Into<String>,
     {
@@ -16,6 +47,7 @@ impl KeepFirstCharTransformer {
             database_name: database_name.into(),
             table_name: table_name.into(),
             column_name: column_name.into(),
+            options,
         }
     }
 }
@@ -26,6 +58,7 @@ impl Default for KeepFirstCharTransformer {
             database_name: String::default(),
             table_name: String::default(),
             column_name: String::default(),
+            options: KeepFirstCharOptions::default(),
         }
     }
 }
@@ -36,7 +69,7 @@ impl Transformer for KeepFirstCharTransformer {
     }
 
     fn description(&self) -> &str {
-        "Keep only the first character of the column."
+        "Keep only the first character of the column, or the first characters and mask the others."
     }
 
     fn database_name(&self) -> &str {
@@ -63,18 +96,20 @@ impl Transformer for KeepFirstCharTransformer {
     fn transform(&self, column: Column) -> Column {
         match column {
             Column::NumberValue(column_name, value) => {
-                Column::NumberValue(column_name, get_first_digit(value))
+                Column::NumberValue(column_name, get_first_digits(value, self.options.keep))
             }
             Column::StringValue(column_name, value) => {
-                let new_value = match value.len() {
-                    len if len > 1 => {
-                        if let Some(first_char) = value.chars().nth(0) {
-                            first_char.to_string()
-                        } else {
-                            "".to_string()
-                        }
-                    }
-                    _ => value,
+                // chars, not bytes, so a multi-byte character is never split
+                let kept_value = value.chars().take(self.options.keep).collect::<String>();
+                let masked_chars = value.chars().count().saturating_sub(self.options.keep);
+
+                let new_value = match self.options.mask_char {
+                    Some(mask_char) => format!(
+                        "{}{}",
+                        kept_value,
+                        mask_char.to_string().repeat(masked_chars)
+                    ),
+                    None => kept_value,
                 };
 
                 Column::StringValue(column_name, new_value)
@@ -84,8 +119,17 @@ impl Transformer for KeepFirstCharTransformer {
     }
 }
 
-fn get_first_digit(mut number: i128) -> i128 {
-    while number >= 10 {
+fn get_first_digits(mut number: i128, keep: usize) -> i128 {
+    // a number can't have more digits than the largest power of 10 of an i128
+    let limit = u32::try_from(keep)
+        .ok()
+        .and_then(|keep| 10i128.checked_pow(keep));
+    let limit = match limit {
+        Some(limit) => limit,
+        None => return number,
+    };
+
+    while number >= limit {
         number /= 10;
     }
 
@@ -96,7 +140,7 @@ fn get_first_digit(mut number: i128) -> i128 {
 mod tests {
     use crate::{transformer::Transformer, types::Column};
 
-    use super::KeepFirstCharTransformer;
+    use super::{KeepFirstCharOptions, KeepFirstCharTransformer};
 
     #[test]
     fn transform_keep_first_char_only_with_number_value() {
@@ -119,7 +163,7 @@ mod tests {
         let transformer = get_transformer();
         let column = Column::FloatNumberValue("a_column".to_string(), expected_value);
         let transformed_column = transformer.transform(column);
-        let transformed_value = transformed_column.float_number_value().unwrap();
+        let transformed_value = transformed_column.float_value().unwrap();
 
         assert_eq!(transformed_value.to_owned(), expected_value);
     }
@@ -148,7 +192,60 @@ mod tests {
         assert_eq!(transformed_value, "L".to_string());
     }
 
+    #[test]
+    fn transform_keep_first_chars_and_mask_the_others() {
+        let transformer = get_transformer_with_options(4, Some('*'));
+        let column = Column::StringValue("a_column".to_string(), "FR7630006000".to_string());
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+        assert_eq!(transformed_value, "FR76********".to_string());
+
+        let transformer = get_transformer_with_options(2, Some('#'));
+        let column = Column::StringValue("a_column".to_string(), "Zoë".to_string());
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+        assert_eq!(transformed_value, "Zo#".to_string());
+
+        let transformer = get_transformer_with_options(3, None);
+        let column = Column::NumberValue("a_column".to_string(), 123456);
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.number_value().unwrap();
+        assert_eq!(transformed_value.to_owned(), 123);
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_value_shorter_than_keep() {
+        let transformer = get_transformer_with_options(8, Some('*'));
+        let column = Column::StringValue("a_column".to_string(), "Zoë".to_string());
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+        assert_eq!(transformed_value, "Zoë".to_string());
+
+        let transformer = get_transformer_with_options(usize::MAX, Some('*'));
+        let column = Column::NumberValue("a_column".to_string(), 123456);
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.number_value().unwrap();
+        assert_eq!(transformed_value.to_owned(), 123456);
+    }
+
     fn get_transformer() -> KeepFirstCharTransformer {
-        KeepFirstCharTransformer::new("github", "users", "a_column")
+        KeepFirstCharTransformer::new(
+            "github",
+            "users",
+            "a_column",
+            KeepFirstCharOptions::default(),
+        )
+    }
+
+    fn get_transformer_with_options(
+        keep: usize,
+        mask_char: Option<char>,
+    ) -> KeepFirstCharTransformer {
+        KeepFirstCharTransformer::new(
+            "github",
+            "users",
+            "a_column",
+            KeepFirstCharOptions { keep, mask_char },
+        )
     }
 }
@@ -0,0 +1,167 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+/// This struct is dedicated to keep only the part of a string matching a pattern.
+/// The first capture group is kept if there is one, otherwise the whole match is kept.
+pub struct KeepMatchingTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    regex: Regex,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct KeepMatchingOptions {
+    pub pattern: String,
+}
+
+impl KeepMatchingTransformer {
+    pub fn new<S>(
+        database_name: S,
+        table_name: S,
+        column_name: S,
+        options: KeepMatchingOptions,
+    ) -> Result<Self, regex::Error>
+    where
+        S: Into<String>,
+    {
+        Ok(KeepMatchingTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            regex: Regex::new(options.pattern.as_str())?,
+        })
+    }
+}
+
+impl Default for KeepMatchingTransformer {
+    fn default() -> Self {
+        KeepMatchingTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            regex: Regex::new("").unwrap(),
+        }
+    }
+}
+
+impl Transformer for KeepMatchingTransformer {
+    fn id(&self) -> &str {
+        "keep-matching"
+    }
+
+    fn description(&self) -> &str {
+        "Keep only the part matching a pattern and blank the rest (string only). [4242 4242 4242 4242]->[4242]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        match column {
+            Column::StringValue(column_name, value) => {
+                let new_value = match self.regex.captures(value.as_str()) {
+                    Some(captures) => captures
+                        .get(1)
+                        .or_else(|| captures.get(0))
+                        .map(|m| m.as_str().to_string())
+                        .unwrap_or_default(),
+                    None => String::new(),
+                };
+
+                Column::StringValue(column_name, new_value)
+            }
+            column => column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{KeepMatchingOptions, KeepMatchingTransformer};
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = get_transformer(r"\d{4}$");
+        let column = Column::NumberValue("card_number".to_string(), 4242);
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.number_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 4242)
+    }
+
+    #[test]
+    fn transform_keeps_the_matching_part() {
+        let transformer = get_transformer(r"\d{4}$");
+        let column = Column::StringValue(
+            "card_number".to_string(),
+            "4242 4242 4242 1234".to_string(),
+        );
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert_eq!(transformed_value, "1234")
+    }
+
+    #[test]
+    fn transform_keeps_the_first_capture_group() {
+        let transformer = get_transformer(r"^[^@]+@(.+)$");
+        let column = Column::StringValue(
+            "card_number".to_string(),
+            "john.doe@example.com".to_string(),
+        );
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert_eq!(transformed_value, "example.com")
+    }
+
+    #[test]
+    fn transform_blanks_a_non_matching_value() {
+        let transformer = get_transformer(r"\d{4}$");
+        let column = Column::StringValue("card_number".to_string(), "no digits".to_string());
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert_eq!(transformed_value, "")
+    }
+
+    #[test]
+    fn new_fails_with_an_invalid_pattern() {
+        assert!(KeepMatchingTransformer::new(
+            "github",
+            "users",
+            "card_number",
+            KeepMatchingOptions {
+                pattern: "(".to_string(),
+            },
+        )
+        .is_err());
+    }
+
+    fn get_transformer(pattern: &str) -> KeepMatchingTransformer {
+        KeepMatchingTransformer::new(
+            "github",
+            "users",
+            "card_number",
+            KeepMatchingOptions {
+                pattern: pattern.to_string(),
+            },
+        )
+        .unwrap()
+    }
+}
@@ -0,0 +1,203 @@
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+use crate::transformer::seeded::rng;
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+/// words of the lorem ipsum, of 1 to 13 letters
+const LOREM_WORDS: &str = "a ad et in id ut est non sed qui amet enim esse elit nisi sint culpa dolor ipsum lorem \
+    magna minim velit fugiat labore mollit nostrud officia aliqua dolore tempor laboris commodo eiusmod ullamco \
+    occaecat proident pariatur deserunt voluptate cupidatat excepteur consequat adipiscing incididunt exercitation \
+    reprehenderit consectetur";
+
+/// This struct is dedicated to replacing a free text by lorem ipsum of the same word count and approximate length.
+pub struct LoremTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    options: LoremOptions,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy, Default)]
+pub struct LoremOptions {
+    /// generate as many words as the original text (default to true) - only its length is kept otherwise
+    pub match_word_count: Option<bool>,
+}
+
+impl LoremTransformer {
+    pub fn new<S>(database_name: S, table_name: S, column_name: S, options: LoremOptions) -> Self
+    where
+        S: Into<String>,
+    {
+        LoremTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            options,
+        }
+    }
+
+    /// lorem text with a word of about the same length for each word of `value`
+    fn words_like(value: &str) -> String {
+        value
+            .split_whitespace()
+            .map(|word| lorem_word(word.chars().count()))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// lorem text of the length of `value`, whatever its words
+    fn text_like(value: &str) -> String {
+        let length = value.chars().count();
+        let words = LOREM_WORDS.split_whitespace().collect::<Vec<_>>();
+        let mut random = rng();
+        let mut text = String::new();
+
+        while text.len() < length {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            text.push_str(words.choose(&mut random).unwrap());
+        }
+
+        // the lorem words are ASCII only
+        text.truncate(length);
+        text.trim_end().to_string()
+    }
+}
+
+/// a random lorem word among the ones with the closest length to `length`
+fn lorem_word(length: usize) -> &'static str {
+    let closest_length = LOREM_WORDS
+        .split_whitespace()
+        .map(|word| word.len())
+        .min_by_key(|word_length| (*word_length as i64 - length as i64).abs())
+        .unwrap();
+
+    let words = LOREM_WORDS
+        .split_whitespace()
+        .filter(|word| word.len() == closest_length)
+        .collect::<Vec<_>>();
+
+    words.choose(&mut rng()).unwrap()
+}
+
+impl Default for LoremTransformer {
+    fn default() -> Self {
+        LoremTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            options: LoremOptions::default(),
+        }
+    }
+}
+
+impl Transformer for LoremTransformer {
+    fn id(&self) -> &str {
+        "lorem"
+    }
+
+    fn description(&self) -> &str {
+        "Replace a text by lorem ipsum of the same word count and approximate length (string only). [Call me back tomorrow]->[sint ut amet deserunt]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        match column {
+            // there is no text to replace
+            Column::StringValue(column_name, value) if value.trim().is_empty() => {
+                Column::StringValue(column_name, value)
+            }
+            Column::StringValue(column_name, value) => {
+                let new_value = match self.options.match_word_count.unwrap_or(true) {
+                    true => LoremTransformer::words_like(value.as_str()),
+                    false => LoremTransformer::text_like(value.as_str()),
+                };
+
+                Column::StringValue(column_name, new_value)
+            }
+            column => column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{LoremOptions, LoremTransformer};
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = get_transformer(None);
+        let column = Column::NumberValue("comment".to_string(), 42);
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.number_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 42)
+    }
+
+    #[test]
+    fn transform_keeps_the_word_count() {
+        let transformer = get_transformer(None);
+        let value = "Please call me back tomorrow, I am not available\ttoday";
+        let column = Column::StringValue("comment".to_string(), value.to_string());
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert_ne!(transformed_value, value);
+        assert_eq!(
+            transformed_value.split_whitespace().count(),
+            value.split_whitespace().count()
+        );
+        // each word is replaced by a lorem word of the same length
+        assert_eq!(transformed_value.len(), value.len());
+    }
+
+    #[test]
+    fn transform_keeps_the_length() {
+        let transformer = get_transformer(Some(false));
+        let value = "Please call me back tomorrow";
+        let column = Column::StringValue("comment".to_string(), value.to_string());
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert!(transformed_value.len() <= value.len());
+        assert!(transformed_value.len() >= value.len() - 1);
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_empty_string() {
+        for match_word_count in [Some(true), Some(false)] {
+            let transformer = get_transformer(match_word_count);
+            for value in ["", "  "] {
+                let column = Column::StringValue("comment".to_string(), value.to_string());
+                let transformed_column = transformer.transform(column);
+
+                assert_eq!(transformed_column.string_value().unwrap(), value);
+            }
+        }
+    }
+
+    fn get_transformer(match_word_count: Option<bool>) -> LoremTransformer {
+        LoremTransformer::new(
+            "github",
+            "tickets",
+            "comment",
+            LoremOptions { match_word_count },
+        )
+    }
+}
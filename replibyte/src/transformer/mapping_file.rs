@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+use std::fs::read_to_string;
+use std::io::{Error, ErrorKind};
+
+use serde::{Deserialize, Serialize};
+
+use crate::transformer::random::RandomTransformer;
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+/// This struct is dedicated to replacing a string by its fake value from a `real,fake` CSV mapping file.
+/// The file is loaded once, so the same real value gets the same fake value across runs.
+pub struct MappingFileTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    mapping: HashMap<String, String>,
+    on_missing: OnMissing,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct MappingFileOptions {
+    /// CSV file with one `real,fake` pair per line
+    pub path: String,
+    /// what to do with a value missing from the file (default to passthrough)
+    pub on_missing: Option<OnMissing>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum OnMissing {
+    /// keep the value as it is
+    Passthrough,
+    /// replace the value by a random one, like the `random` transformer
+    Fake,
+    /// stop the dump
+    Error,
+}
+
+/// parse the `real,fake` lines of a mapping file - the fake value is everything after the first comma
+fn parse_mapping(content: &str) -> Result<HashMap<String, String>, Error> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| match line.split_once(',') {
+            Some((real, fake)) => Ok((real.trim().to_string(), fake.trim().to_string())),
+            None => Err(Error::new(
+                ErrorKind::Other,
+                format!("line {} is not a 'real,fake' pair", idx + 1),
+            )),
+        })
+        .collect()
+}
+
+impl MappingFileTransformer {
+    pub fn new<S>(
+        database_name: S,
+        table_name: S,
+        column_name: S,
+        options: MappingFileOptions,
+    ) -> Result<Self, Error>
+    where
+        S: Into<String>,
+    {
+        let content = read_to_string(options.path.as_str()).map_err(|err| {
+            Error::new(
+                ErrorKind::Other,
+                format!("cannot read mapping file '{}': {}", options.path, err),
+            )
+        })?;
+
+        Ok(MappingFileTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            mapping: parse_mapping(content.as_str())?,
+            on_missing: options.on_missing.unwrap_or(OnMissing::Passthrough),
+        })
+    }
+}
+
+impl Default for MappingFileTransformer {
+    fn default() -> Self {
+        MappingFileTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            mapping: HashMap::default(),
+            on_missing: OnMissing::Passthrough,
+        }
+    }
+}
+
+impl Transformer for MappingFileTransformer {
+    fn id(&self) -> &str {
+        "mapping-file"
+    }
+
+    fn description(&self) -> &str {
+        "Replace the value by its fake value from a 'real,fake' CSV file (string only). [Nancy]->[Tony]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        match column {
+            Column::StringValue(column_name, value) => {
+                if let Some(fake_value) = self.mapping.get(value.as_str()) {
+                    return Column::StringValue(column_name, fake_value.to_string());
+                }
+
+                match self.on_missing {
+                    OnMissing::Passthrough => Column::StringValue(column_name, value),
+                    OnMissing::Fake => RandomTransformer::default()
+                        .transform(Column::StringValue(column_name, value)),
+                    OnMissing::Error => panic!(
+                        "value of {}.{}.{} is missing from the mapping file",
+                        self.database_name, self.table_name, column_name
+                    ),
+                }
+            }
+            column => column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{MappingFileOptions, MappingFileTransformer, OnMissing};
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = get_transformer(OnMissing::Error);
+        let column = Column::NumberValue("first_name".to_string(), 42);
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.number_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 42)
+    }
+
+    #[test]
+    fn transform_replaces_a_mapped_value() {
+        let transformer = get_transformer(OnMissing::Error);
+        let column = Column::StringValue("first_name".to_string(), "Nancy".to_string());
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert_eq!(transformed_value, "Tony")
+    }
+
+    #[test]
+    fn transform_keeps_a_missing_value() {
+        let transformer = get_transformer(OnMissing::Passthrough);
+        let column = Column::StringValue("first_name".to_string(), "Janet".to_string());
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert_eq!(transformed_value, "Janet")
+    }
+
+    #[test]
+    fn transform_fakes_a_missing_value() {
+        let transformer = get_transformer(OnMissing::Fake);
+        let column = Column::StringValue("first_name".to_string(), "Janet".to_string());
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert_eq!(transformed_value.len(), "Janet".len());
+        assert_ne!(transformed_value, "Janet")
+    }
+
+    #[test]
+    #[should_panic(expected = "missing from the mapping file")]
+    fn transform_fails_on_a_missing_value() {
+        let transformer = get_transformer(OnMissing::Error);
+        let column = Column::StringValue("first_name".to_string(), "Janet".to_string());
+        let _ = transformer.transform(column);
+    }
+
+    #[test]
+    fn new_fails_on_an_invalid_mapping_file() {
+        let dir = tempdir().expect("cannot create tempdir");
+        let path = dir.path().join("mapping.csv");
+        std::fs::write(&path, "Nancy,Tony\nAndrew\n").unwrap();
+
+        let options = MappingFileOptions {
+            path: path.to_str().unwrap().to_string(),
+            on_missing: None,
+        };
+
+        assert!(MappingFileTransformer::new("github", "users", "first_name", options).is_err());
+    }
+
+    fn get_transformer(on_missing: OnMissing) -> MappingFileTransformer {
+        let dir = tempdir().expect("cannot create tempdir");
+        let path = dir.path().join("mapping.csv");
+        std::fs::write(&path, "Nancy,Tony\nAndrew, Steven\n\n").unwrap();
+
+        let options = MappingFileOptions {
+            path: path.to_str().unwrap().to_string(),
+            on_missing: Some(on_missing),
+        };
+
+        // the mapping file is loaded once, it can be deleted afterwards
+        MappingFileTransformer::new("github", "users", "first_name", options).unwrap()
+    }
+}
@@ -1,22 +1,100 @@
+use crate::transformer::birth_date::BirthDateTransformer;
+use crate::transformer::boolean_string::BooleanStringTransformer;
+use crate::transformer::consistent::ConsistentTransformer;
+use crate::transformer::corporate_email::CorporateEmailTransformer;
 use crate::transformer::credit_card::CreditCardTransformer;
 use crate::transformer::custom_wasm::CustomWasmTransformer;
+use crate::transformer::discriminator::DiscriminatorTransformer;
+use crate::transformer::domain_map::DomainMapTransformer;
 use crate::transformer::email::EmailTransformer;
+use crate::transformer::fake::FakeTransformer;
+use crate::transformer::fake_json::FakeJsonTransformer;
+use crate::transformer::file_path::FilePathTransformer;
 use crate::transformer::first_name::FirstNameTransformer;
+use crate::transformer::id_list::IdListTransformer;
+use crate::transformer::iso_code::IsoCodeTransformer;
+use crate::transformer::jitter::JitterTransformer;
 use crate::transformer::keep_first_char::KeepFirstCharTransformer;
+use crate::transformer::keep_matching::KeepMatchingTransformer;
+use crate::transformer::lorem::LoremTransformer;
+use crate::transformer::mapping_file::MappingFileTransformer;
+use crate::transformer::name_initials::NameInitialsTransformer;
+use crate::transformer::nearby::NearbyTransformer;
+use crate::transformer::number_distribution::NumberDistributionTransformer;
+use crate::transformer::pattern_preserving::PatternPreservingTransformer;
 use crate::transformer::phone_number::PhoneNumberTransformer;
+use crate::transformer::prefix_preserving_fake::PrefixPreservingFakeTransformer;
+use crate::transformer::place::PlaceTransformer;
+use crate::transformer::plugin::PluginTransformer;
 use crate::transformer::random::RandomTransformer;
+use crate::transformer::random_date::RandomDateTransformer;
 use crate::transformer::redacted::RedactedTransformer;
+use crate::transformer::reference_code::ReferenceCodeTransformer;
+use crate::transformer::regex_replace::RegexReplaceTransformer;
+use crate::transformer::round::RoundTransformer;
+use crate::transformer::scramble::ScrambleTransformer;
+use crate::transformer::script::ScriptTransformer;
+use crate::transformer::shift_date::ShiftDateTransformer;
 use crate::transformer::transient::TransientTransformer;
+use crate::transformer::truncate_date::TruncateDateTransformer;
+use crate::transformer::url::UrlTransformer;
+use crate::transformer::user_agent::UserAgentTransformer;
+use crate::transformer::uuid_to_int::UuidToIntTransformer;
+use crate::transformer::vat::VatTransformer;
+use crate::transformer::xml_attr::XmlAttrTransformer;
 use crate::types::Column;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io::{Error, ErrorKind};
 
+pub mod birth_date;
+pub mod boolean_string;
+pub mod consistent;
+pub mod corporate_email;
 pub mod credit_card;
+pub mod discriminator;
+pub mod domain_map;
 pub mod email;
+pub mod fake;
+pub mod fake_json;
+pub mod file_path;
 pub mod first_name;
+pub mod foreign_key;
+pub mod id_list;
+pub mod iso_code;
+pub mod jitter;
 pub mod keep_first_char;
+pub mod keep_matching;
+pub mod lorem;
+pub mod mapping_file;
+pub mod name_initials;
+pub mod nearby;
+pub mod number_distribution;
+pub mod pattern_preserving;
 pub mod phone_number;
+pub mod prefix_preserving_fake;
+pub mod place;
+pub mod plugin;
 pub mod random;
+pub mod random_date;
 pub mod redacted;
+pub mod reference_code;
+pub mod regex_replace;
+pub mod round;
+pub mod scramble;
+pub mod script;
+pub mod seeded;
+pub mod shift_date;
+pub mod tracked;
 pub mod transient;
+pub mod truncate_date;
+pub mod unique;
+pub mod url;
+pub mod user_agent;
+pub mod uuid_to_int;
+pub mod vat;
+pub mod xml_attr;
 
 // FIXME: CI release build is broken because of feature flag
 //#[cfg(feature = "wasm")]
@@ -32,6 +110,41 @@ pub fn transformers() -> Vec<Box<dyn Transformer>> {
         Box::new(TransientTransformer::default()),
         Box::new(CreditCardTransformer::default()),
         Box::new(RedactedTransformer::default()),
+        Box::new(ScrambleTransformer::default()),
+        Box::new(KeepMatchingTransformer::default()),
+        Box::new(CorporateEmailTransformer::default()),
+        Box::new(ScriptTransformer::default()),
+        Box::new(FilePathTransformer::default()),
+        Box::new(PlaceTransformer::default()),
+        Box::new(BirthDateTransformer::default()),
+        Box::new(IsoCodeTransformer::default()),
+        Box::new(TruncateDateTransformer::default()),
+        Box::new(DomainMapTransformer::default()),
+        Box::new(XmlAttrTransformer::default()),
+        Box::new(PatternPreservingTransformer::default()),
+        Box::new(FakeJsonTransformer::default()),
+        Box::new(MappingFileTransformer::default()),
+        Box::new(UrlTransformer::default()),
+        Box::new(BooleanStringTransformer::default()),
+        Box::new(NumberDistributionTransformer::default()),
+        Box::new(LoremTransformer::default()),
+        Box::new(NameInitialsTransformer::default()),
+        Box::new(PluginTransformer::default()),
+        Box::new(RoundTransformer::default()),
+        Box::new(VatTransformer::default()),
+        Box::new(DiscriminatorTransformer::default()),
+        Box::new(IdListTransformer::default()),
+        Box::new(ShiftDateTransformer::default()),
+        Box::new(UuidToIntTransformer::default()),
+        Box::new(UserAgentTransformer::default()),
+        Box::new(PrefixPreservingFakeTransformer::default()),
+        Box::new(ReferenceCodeTransformer::default()),
+        Box::new(RandomDateTransformer::default()),
+        Box::new(FakeTransformer::default()),
+        Box::new(JitterTransformer::default()),
+        Box::new(NearbyTransformer::default()),
+        Box::new(ConsistentTransformer::default()),
+        Box::new(RegexReplaceTransformer::default()),
         Box::new(CustomWasmTransformer::default()),
     ]
 }
@@ -84,4 +197,148 @@ pub trait Transformer {
     }
 
     fn transform(&self, column: Column) -> Column;
+
+    /// Transform a column knowing the original values of the other columns of the row.
+    /// Override it for transformers depending on sibling columns (not supported by MongoDB).
+    fn transform_with_row(&self, column: Column, _row: &[Column]) -> Column {
+        self.transform(column)
+    }
+
+    /// Whether `transform_with_row` gets the transformed values of the other columns of the row instead of the original
+    /// ones, e.g. not to leak the original values. The column is transformed after the other columns of the row.
+    fn needs_transformed_row(&self) -> bool {
+        false
+    }
+
+    /// Check that the transformer can handle the value of a column - the failure is handled by the
+    /// `on_transform_error` policy of the source. Override it for transformers rejecting some values.
+    fn validate(&self, _column: &Column) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// What to do with a value a transformer can't handle (e.g. a credit card transformer on a text).
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum OnTransformError {
+    /// stop the dump
+    Abort,
+    /// keep the original value
+    Passthrough,
+    /// replace the value by an empty string, or by NULL if it is not a string
+    Blank,
+}
+
+/// Transform a column with the original values of its row, applying the `on_transform_error` policy to a value the
+/// transformer can't handle - an error is returned to abort the dump. Without policy, the value is transformed anyway.
+pub fn transform_column(
+    transformer: &dyn Transformer,
+    column: Column,
+    row: &[Column],
+    on_transform_error: Option<OnTransformError>,
+) -> Result<Column, Error> {
+    let on_transform_error = match on_transform_error {
+        Some(on_transform_error) => on_transform_error,
+        None => return Ok(transformer.transform_with_row(column, row)),
+    };
+
+    match transformer.validate(&column) {
+        Ok(_) => Ok(transformer.transform_with_row(column, row)),
+        Err(err) => match on_transform_error {
+            OnTransformError::Abort => Err(Error::new(
+                ErrorKind::Other,
+                format!(
+                    "{} transformer cannot transform {}: {}",
+                    transformer.id(),
+                    transformer.database_and_table_and_column_name(),
+                    err
+                ),
+            )),
+            OnTransformError::Passthrough => Ok(column),
+            OnTransformError::Blank => match column {
+                Column::StringValue(column_name, _) => {
+                    Ok(Column::StringValue(column_name, String::new()))
+                }
+                column => Ok(Column::None(column.name().to_string())),
+            },
+        },
+    }
+}
+
+/// Transform the columns of a row having a transformer with `transform`, which gets the original values of the row -
+/// or the transformed ones for the transformers needing them, applied once the other columns are transformed.
+/// The first error of `transform` is returned.
+pub fn transform_row_columns<'t, F>(
+    original_columns: &[Column],
+    transformers: &[Option<&'t dyn Transformer>],
+    mut transform: F,
+) -> Result<Vec<Column>, Error>
+where
+    F: FnMut(usize, &'t dyn Transformer, Column, &[Column]) -> Result<Column, Error>,
+{
+    let mut columns = original_columns.to_vec();
+
+    for needs_transformed_row in [false, true] {
+        for (idx, transformer) in transformers.iter().enumerate() {
+            let transformer = match transformer {
+                Some(transformer)
+                    if transformer.needs_transformed_row() == needs_transformed_row =>
+                {
+                    *transformer
+                }
+                _ => continue,
+            };
+
+            let row = match needs_transformed_row {
+                true => columns.as_slice(),
+                false => original_columns,
+            };
+
+            let column = transform(idx, transformer, original_columns[idx].clone(), row)?;
+            columns[idx] = column;
+        }
+    }
+
+    Ok(columns)
+}
+
+/// Hash the original values of a row, so the transformers of the same row can pick the same fake values.
+/// Only the `key_column` is hashed if it is set and part of the row.
+pub fn hash_row(row: &[Column], key_column: Option<&str>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+
+    let key_column =
+        key_column.and_then(|key_column| row.iter().find(|column| column.name() == key_column));
+
+    let columns = match key_column {
+        Some(column) => std::slice::from_ref(column),
+        None => row,
+    };
+
+    for column in columns {
+        column.name().hash(&mut hasher);
+
+        match column {
+            Column::NumberValue(_, value) => value.hash(&mut hasher),
+            Column::FloatNumberValue(_, value) => value.to_bits().hash(&mut hasher),
+            Column::StringValue(_, value) => value.hash(&mut hasher),
+            Column::CharValue(_, value) => value.hash(&mut hasher),
+            Column::BooleanValue(_, value) => value.hash(&mut hasher),
+            Column::None(_) => {}
+        }
+    }
+
+    hasher.finish()
+}
+
+/// Original value of a column of the row as text - none if the column is missing or NULL.
+pub fn row_value(row: &[Column], column_name: &str) -> Option<String> {
+    match row.iter().find(|column| column.name() == column_name)? {
+        Column::NumberValue(_, value) => Some(value.to_string()),
+        Column::FloatNumberValue(_, value) => Some(value.to_string()),
+        Column::StringValue(_, value) => Some(value.clone()),
+        Column::CharValue(_, value) => Some(value.to_string()),
+        Column::BooleanValue(_, value) => Some(value.to_string()),
+        Column::None(_) => None,
+    }
 }
@@ -0,0 +1,198 @@
+use fake::locales::{Data, EN};
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+/// This struct is dedicated to replacing a name by a fake one with the same initials.
+/// e.g. a full name is replaced word by word: a first name, then last names, each starting with the original letter.
+pub struct NameInitialsTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    options: NameInitialsOptions,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy, Default)]
+pub struct NameInitialsOptions {
+    /// kind of name of the column (default to full_name)
+    pub kind: Option<NameKind>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+pub enum NameKind {
+    /// e.g. John
+    #[serde(rename = "first_name")]
+    First,
+    /// e.g. Doe
+    #[serde(rename = "last_name")]
+    Last,
+    /// a first name followed by last names, e.g. John Doe
+    #[serde(rename = "full_name")]
+    Full,
+}
+
+impl NameInitialsTransformer {
+    pub fn new<S>(
+        database_name: S,
+        table_name: S,
+        column_name: S,
+        options: NameInitialsOptions,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        NameInitialsTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            options,
+        }
+    }
+
+    fn fake_name(&self, value: &str) -> String {
+        let kind = self.options.kind.unwrap_or(NameKind::Full);
+
+        value
+            .split_whitespace()
+            .enumerate()
+            .map(|(idx, word)| {
+                let names = match (kind, idx) {
+                    (NameKind::First, _) | (NameKind::Full, 0) => EN::NAME_FIRST_NAME,
+                    _ => EN::NAME_LAST_NAME,
+                };
+
+                name_with_initial_of(word, names)
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// a random name starting with the same letter as `word` - any name if there is none
+fn name_with_initial_of(word: &str, names: &[&'static str]) -> &'static str {
+    let mut random = rand::thread_rng();
+
+    let matching_names = match word.chars().next() {
+        Some(initial) => names
+            .iter()
+            .filter(|name| match name.chars().next() {
+                Some(c) => c.to_lowercase().eq(initial.to_lowercase()),
+                None => false,
+            })
+            .copied()
+            .collect::<Vec<_>>(),
+        None => vec![],
+    };
+
+    match matching_names.choose(&mut random) {
+        Some(name) => name,
+        None => names.choose(&mut random).unwrap(),
+    }
+}
+
+impl Default for NameInitialsTransformer {
+    fn default() -> Self {
+        NameInitialsTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            options: NameInitialsOptions::default(),
+        }
+    }
+}
+
+impl Transformer for NameInitialsTransformer {
+    fn id(&self) -> &str {
+        "name-initials"
+    }
+
+    fn description(&self) -> &str {
+        "Generate a name with the same initials (string only). [Jane Doe]->[John Dickens]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        match column {
+            Column::StringValue(column_name, value) => {
+                Column::StringValue(column_name, self.fake_name(value.as_str()))
+            }
+            column => column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{NameInitialsOptions, NameInitialsTransformer, NameKind};
+
+    fn transform(value: &str, kind: Option<NameKind>) -> String {
+        let transformer =
+            NameInitialsTransformer::new("github", "users", "name", NameInitialsOptions { kind });
+        let column = Column::StringValue("name".to_string(), value.to_string());
+        let transformed_column = transformer.transform(column);
+        transformed_column.string_value().unwrap().to_string()
+    }
+
+    fn initials(value: &str) -> Vec<char> {
+        value
+            .split_whitespace()
+            .filter_map(|word| word.chars().next())
+            .map(|c| c.to_ascii_uppercase())
+            .collect()
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = NameInitialsTransformer::default();
+        let column = Column::NumberValue("name".to_string(), 42);
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.number_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 42)
+    }
+
+    #[test]
+    fn transform_keeps_the_initials() {
+        for _ in 0..20 {
+            let transformed_value = transform("Jane Doe", None);
+            assert_eq!(initials(transformed_value.as_str()), vec!['J', 'D']);
+
+            let transformed_value = transform("mary ann smith", None);
+            assert_eq!(initials(transformed_value.as_str()), vec!['M', 'A', 'S']);
+
+            let transformed_value = transform("Lucas", Some(NameKind::First));
+            assert_eq!(initials(transformed_value.as_str()), vec!['L']);
+
+            let transformed_value = transform("Wright", Some(NameKind::Last));
+            assert_eq!(initials(transformed_value.as_str()), vec!['W']);
+        }
+    }
+
+    #[test]
+    fn transform_falls_back_to_any_name() {
+        let transformed_value = transform("Émile 42", None);
+
+        assert_eq!(transformed_value.split_whitespace().count(), 2);
+        assert!(!transformed_value.starts_with('É'));
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_empty_string() {
+        assert_eq!(transform("", None), "");
+    }
+}
@@ -0,0 +1,239 @@
+use std::io::{Error, ErrorKind};
+
+use rand::rngs::StdRng;
+use rand::seq::index::sample;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::transformer::seeded::rng;
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+/// This struct is dedicated to replacing a string by a similar one, a few random single-character edits away from the
+/// original, e.g. to test the fuzzy matching of an application. [Johnson]->[Jihnsen]
+pub struct NearbyTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    options: NearbyOptions,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+pub struct NearbyOptions {
+    /// number of single-character edits (substitution, insertion or deletion) applied to each value
+    pub edits: usize,
+}
+
+impl Default for NearbyOptions {
+    fn default() -> Self {
+        NearbyOptions { edits: 1 }
+    }
+}
+
+impl NearbyTransformer {
+    pub fn new<S>(
+        database_name: S,
+        table_name: S,
+        column_name: S,
+        options: NearbyOptions,
+    ) -> Result<Self, Error>
+    where
+        S: Into<String>,
+    {
+        if options.edits == 0 {
+            return Err(Error::new(ErrorKind::Other, "edits must be at least 1"));
+        }
+
+        Ok(NearbyTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            options,
+        })
+    }
+
+    /// apply the edits on distinct characters of the value - at most one edit per character
+    fn nearby(&self, value: &str) -> String {
+        let chars = value.chars().collect::<Vec<_>>();
+        let edits = self.options.edits.min(chars.len());
+        let mut random = rng();
+
+        loop {
+            let mut positions = sample(&mut random, chars.len(), edits).into_vec();
+            positions.sort_unstable();
+            let mut positions = positions.into_iter().peekable();
+
+            let mut new_value = String::with_capacity(value.len() + edits);
+            for (idx, c) in chars.iter().enumerate() {
+                if positions.peek() != Some(&idx) {
+                    new_value.push(*c);
+                    continue;
+                }
+
+                let _ = positions.next();
+                match random.gen_range(0..3) {
+                    // substitution
+                    0 => new_value.push(similar_char(*c, &mut random)),
+                    // insertion before the character
+                    1 => {
+                        new_value.push(similar_char(*c, &mut random));
+                        new_value.push(*c);
+                    }
+                    // deletion
+                    _ => {}
+                }
+            }
+
+            // the edits can cancel each other (e.g. a deletion and the insertion of the same character)
+            if !new_value.is_empty() && new_value != value {
+                return new_value;
+            }
+        }
+    }
+}
+
+/// another character of the same kind - a lowercase letter for the characters other than letters and digits
+fn similar_char(c: char, random: &mut StdRng) -> char {
+    let (first, last) = match c {
+        'A'..='Z' => ('A', 'Z'),
+        '0'..='9' => ('0', '9'),
+        _ => ('a', 'z'),
+    };
+
+    loop {
+        let new_char = random.gen_range(first..=last);
+        if new_char != c {
+            return new_char;
+        }
+    }
+}
+
+impl Default for NearbyTransformer {
+    fn default() -> Self {
+        NearbyTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            options: NearbyOptions::default(),
+        }
+    }
+}
+
+impl Transformer for NearbyTransformer {
+    fn id(&self) -> &str {
+        "nearby"
+    }
+
+    fn description(&self) -> &str {
+        "Apply a few random single-character edits (string only). [Johnson]->[Jihnsen]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        match column {
+            Column::StringValue(column_name, value) if value.is_empty() => {
+                Column::StringValue(column_name, value)
+            }
+            Column::StringValue(column_name, value) => {
+                Column::StringValue(column_name, self.nearby(value.as_str()))
+            }
+            column => column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{NearbyOptions, NearbyTransformer};
+
+    fn transform(value: &str, edits: usize) -> String {
+        let transformer =
+            NearbyTransformer::new("github", "users", "last_name", NearbyOptions { edits })
+                .unwrap();
+        let column = Column::StringValue("last_name".to_string(), value.to_string());
+        let transformed_column = transformer.transform(column);
+        transformed_column.string_value().unwrap().to_string()
+    }
+
+    fn levenshtein_distance(a: &str, b: &str) -> usize {
+        let b = b.chars().collect::<Vec<_>>();
+        let mut distances = (0..=b.len()).collect::<Vec<_>>();
+
+        for (i, a_char) in a.chars().enumerate() {
+            let mut previous_diagonal = distances[0];
+            distances[0] = i + 1;
+
+            for (j, b_char) in b.iter().enumerate() {
+                let substitution = previous_diagonal + usize::from(a_char != *b_char);
+                previous_diagonal = distances[j + 1];
+                distances[j + 1] = substitution.min(distances[j] + 1).min(distances[j + 1] + 1);
+            }
+        }
+
+        distances[b.len()]
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = NearbyTransformer::default();
+        let column = Column::NumberValue("last_name".to_string(), 42);
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.number_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 42)
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_empty_string() {
+        assert_eq!(transform("", 2), "");
+    }
+
+    #[test]
+    fn transform_to_a_nearby_value() {
+        for edits in 1..=3 {
+            let mut exact_edits = 0;
+
+            for _ in 0..1000 {
+                let value = transform("Johnson-1987", edits);
+                let distance = levenshtein_distance("Johnson-1987", value.as_str());
+
+                assert!((1..=edits).contains(&distance));
+                if distance == edits {
+                    exact_edits += 1;
+                }
+            }
+
+            // the edits rarely cancel each other
+            assert!(exact_edits > 800);
+        }
+    }
+
+    #[test]
+    fn transform_a_short_value() {
+        for _ in 0..100 {
+            let value = transform("J", 3);
+            assert!(!value.is_empty());
+            assert_ne!(value, "J");
+            assert_eq!(levenshtein_distance("J", value.as_str()), 1);
+        }
+    }
+
+    #[test]
+    fn new_fails_without_edits() {
+        let options = NearbyOptions { edits: 0 };
+        assert!(NearbyTransformer::new("github", "users", "last_name", options).is_err());
+    }
+}
@@ -0,0 +1,220 @@
+use std::f64::consts::PI;
+use std::io::{Error, ErrorKind};
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+/// This struct is dedicated to replacing a number by a random one following the distribution of the column:
+/// values are drawn from a normal distribution of the column mean and standard deviation, within its min and max.
+pub struct NumberDistributionTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    min: f64,
+    max: f64,
+    mean: f64,
+    stddev: f64,
+}
+
+/// statistics of the column - computed at the start of the dump with a PostgreSQL source when missing
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy, Default)]
+pub struct NumberDistributionOptions {
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub mean: Option<f64>,
+    pub stddev: Option<f64>,
+}
+
+impl NumberDistributionOptions {
+    pub fn is_complete(&self) -> bool {
+        self.min.is_some() && self.max.is_some() && self.mean.is_some() && self.stddev.is_some()
+    }
+
+    /// the statistics of `self`, completed by the ones of `other`
+    pub fn or(self, other: NumberDistributionOptions) -> Self {
+        NumberDistributionOptions {
+            min: self.min.or(other.min),
+            max: self.max.or(other.max),
+            mean: self.mean.or(other.mean),
+            stddev: self.stddev.or(other.stddev),
+        }
+    }
+}
+
+impl NumberDistributionTransformer {
+    pub fn new<S>(
+        database_name: S,
+        table_name: S,
+        column_name: S,
+        options: NumberDistributionOptions,
+    ) -> Result<Self, Error>
+    where
+        S: Into<String>,
+    {
+        let (min, max, mean, stddev) = match options {
+            NumberDistributionOptions {
+                min: Some(min),
+                max: Some(max),
+                mean: Some(mean),
+                stddev: Some(stddev),
+            } => (min, max, mean, stddev),
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::Other,
+                    "min, max, mean and stddev are required when they can't be computed from the source",
+                ))
+            }
+        };
+
+        if min > max {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!("min ({}) is greater than max ({})", min, max),
+            ));
+        }
+
+        Ok(NumberDistributionTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            min,
+            max,
+            mean,
+            stddev,
+        })
+    }
+
+    /// draw a value from the normal distribution (Box-Muller transform), clamped to [min, max]
+    fn sample(&self) -> f64 {
+        let mut random = rand::thread_rng();
+        // u1 must not be 0 for its logarithm
+        let u1 = 1.0 - random.gen::<f64>();
+        let u2 = random.gen::<f64>();
+        let standard_normal = (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos();
+
+        (self.mean + self.stddev * standard_normal).clamp(self.min, self.max)
+    }
+}
+
+impl Default for NumberDistributionTransformer {
+    fn default() -> Self {
+        NumberDistributionTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            min: 0.0,
+            max: 0.0,
+            mean: 0.0,
+            stddev: 0.0,
+        }
+    }
+}
+
+impl Transformer for NumberDistributionTransformer {
+    fn id(&self) -> &str {
+        "number-distribution"
+    }
+
+    fn description(&self) -> &str {
+        "Randomize a number but keep the min, max, mean and standard deviation of the column (number only). [42]->[37]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        match column {
+            Column::NumberValue(column_name, _) => {
+                // rounding may go past a bound which is not an integer
+                let value = self
+                    .sample()
+                    .round()
+                    .max(self.min.ceil())
+                    .min(self.max.floor());
+                Column::NumberValue(column_name, value as i128)
+            }
+            Column::FloatNumberValue(column_name, _) => {
+                Column::FloatNumberValue(column_name, self.sample())
+            }
+            column => column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{NumberDistributionOptions, NumberDistributionTransformer};
+
+    #[test]
+    fn transform_doesnt_change_with_string_value() {
+        let transformer = get_transformer();
+        let column = Column::StringValue("amount".to_string(), "42".to_string());
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert_eq!(transformed_value, "42")
+    }
+
+    #[test]
+    fn transform_keeps_the_distribution() {
+        let transformer = get_transformer();
+
+        let numbers = (0..2000)
+            .map(|_| {
+                let column = Column::NumberValue("amount".to_string(), 42);
+                *transformer.transform(column).number_value().unwrap() as f64
+            })
+            .collect::<Vec<_>>();
+
+        let floats = (0..2000)
+            .map(|_| {
+                let column = Column::FloatNumberValue("amount".to_string(), 42.0);
+                *transformer.transform(column).float_value().unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        for values in [numbers, floats] {
+            assert!(values.iter().all(|value| (10.0..=90.0).contains(value)));
+
+            let mean = values.iter().sum::<f64>() / values.len() as f64;
+            assert!((mean - 40.0).abs() < 2.0, "mean is {}", mean);
+        }
+    }
+
+    #[test]
+    fn new_fails_without_the_statistics() {
+        let options = NumberDistributionOptions {
+            min: Some(10.0),
+            max: Some(90.0),
+            mean: None,
+            stddev: None,
+        };
+
+        assert!(NumberDistributionTransformer::new("github", "orders", "amount", options).is_err());
+    }
+
+    fn get_transformer() -> NumberDistributionTransformer {
+        let options = NumberDistributionOptions {
+            min: Some(10.0),
+            max: Some(90.0),
+            mean: Some(40.0),
+            stddev: Some(15.0),
+        };
+
+        NumberDistributionTransformer::new("github", "orders", "amount", options).unwrap()
+    }
+}
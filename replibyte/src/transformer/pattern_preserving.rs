@@ -0,0 +1,140 @@
+use rand::Rng;
+
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+const UPPERCASE_LETTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const LOWERCASE_LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+const DIGITS: &[u8] = b"0123456789";
+
+/// This struct is dedicated to replacing each letter and digit by a random one of the same class.
+/// Uppercase letters, lowercase letters and digits keep their positions, the other characters are kept as they are.
+pub struct PatternPreservingTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+}
+
+impl PatternPreservingTransformer {
+    pub fn new<S>(database_name: S, table_name: S, column_name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        PatternPreservingTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+        }
+    }
+}
+
+impl Default for PatternPreservingTransformer {
+    fn default() -> Self {
+        PatternPreservingTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+        }
+    }
+}
+
+impl Transformer for PatternPreservingTransformer {
+    fn id(&self) -> &str {
+        "pattern-preserving"
+    }
+
+    fn description(&self) -> &str {
+        "Randomize letters and digits but keep their positions and case (string only). [AB12-cd34]->[QZ70-kx19]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        match column {
+            Column::StringValue(column_name, value) => {
+                let mut random = rand::thread_rng();
+
+                let new_value = value
+                    .chars()
+                    .map(|c| {
+                        let chars = match c {
+                            c if c.is_uppercase() => UPPERCASE_LETTERS,
+                            c if c.is_lowercase() => LOWERCASE_LETTERS,
+                            c if c.is_ascii_digit() => DIGITS,
+                            c => return c,
+                        };
+
+                        chars[random.gen_range(0..chars.len())] as char
+                    })
+                    .collect::<String>();
+
+                Column::StringValue(column_name, new_value)
+            }
+            column => column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::PatternPreservingTransformer;
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = get_transformer();
+        let column = Column::NumberValue("code".to_string(), 1234);
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.number_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 1234)
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_empty_string_value() {
+        let transformer = get_transformer();
+        let column = Column::StringValue("code".to_string(), "".to_string());
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert_eq!(transformed_value, "")
+    }
+
+    #[test]
+    fn transform_keeps_the_pattern() {
+        let value = "AB12-cd34 Éé_x";
+        let transformer = get_transformer();
+
+        for _ in 0..20 {
+            let column = Column::StringValue("code".to_string(), value.to_string());
+            let transformed_column = transformer.transform(column);
+            let transformed_value = transformed_column.string_value().unwrap();
+
+            assert_eq!(transformed_value.chars().count(), value.chars().count());
+
+            for (c, new_c) in value.chars().zip(transformed_value.chars()) {
+                match c {
+                    c if c.is_uppercase() => assert!(new_c.is_ascii_uppercase()),
+                    c if c.is_lowercase() => assert!(new_c.is_ascii_lowercase()),
+                    c if c.is_ascii_digit() => assert!(new_c.is_ascii_digit()),
+                    c => assert_eq!(new_c, c),
+                }
+            }
+        }
+    }
+
+    fn get_transformer() -> PatternPreservingTransformer {
+        PatternPreservingTransformer::new("github", "orders", "code")
+    }
+}
@@ -1,18 +1,33 @@
+use crate::transformer::seeded::rng;
 use crate::transformer::Transformer;
 use crate::types::Column;
 use fake::faker::phone_number::raw::PhoneNumber;
 use fake::locales::EN;
 use fake::Fake;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 
 /// This struct is dedicated to replacing a string by an email address.
 pub struct PhoneNumberTransformer {
     database_name: String,
     table_name: String,
     column_name: String,
+    options: PhoneNumberOptions,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy, Default)]
+pub struct PhoneNumberOptions {
+    /// keep the area code of US numbers and only randomize the subscriber number (default to false)
+    pub keep_area_code: Option<bool>,
 }
 
 impl PhoneNumberTransformer {
-    pub fn new<S>(database_name: S, table_name: S, column_name: S) -> Self
+    pub fn new<S>(
+        database_name: S,
+        table_name: S,
+        column_name: S,
+        options: PhoneNumberOptions,
+    ) -> Self
     where
         S: Into<String>,
     {
@@ -20,16 +35,54 @@ impl PhoneNumberTransformer {
             database_name: database_name.into(),
             table_name: table_name.into(),
             column_name: column_name.into(),
+            options,
         }
     }
 }
 
+/// randomize the digits after the area code and keep the formatting (e.g. `+1 (212) 555-0123` -> `+1 (212) 830-4417`).
+/// Only US numbers are supported: 10 digits, or 11 digits with the `1` country code.
+fn randomize_subscriber_number(value: &str) -> Option<String> {
+    let digits = value
+        .chars()
+        .filter(|c| c.is_ascii_digit())
+        .collect::<Vec<_>>();
+
+    // country code and area code
+    let kept_digits = match digits.len() {
+        10 => 3,
+        11 if digits[0] == '1' => 4,
+        _ => return None,
+    };
+
+    let mut random = rng();
+    let mut digit_idx = 0;
+
+    let new_value = value
+        .chars()
+        .map(|c| {
+            if !c.is_ascii_digit() {
+                return c;
+            }
+
+            digit_idx += 1;
+            match digit_idx > kept_digits {
+                true => char::from_digit(random.gen_range(0..10), 10).unwrap(),
+                false => c,
+            }
+        })
+        .collect::<String>();
+
+    Some(new_value)
+}
+
 impl Default for PhoneNumberTransformer {
     fn default() -> Self {
         PhoneNumberTransformer {
             database_name: String::default(),
             table_name: String::default(),
             column_name: String::default(),
+            options: PhoneNumberOptions::default(),
         }
     }
 }
@@ -57,8 +110,17 @@ impl Transformer for PhoneNumberTransformer {
 
     fn transform(&self, column: Column) -> Column {
         match column {
-            Column::StringValue(column_name, _) => {
-                Column::StringValue(column_name, PhoneNumber(EN).fake())
+            Column::StringValue(column_name, value) => {
+                let new_value = match self.options.keep_area_code {
+                    Some(true) => randomize_subscriber_number(value.as_str()),
+                    _ => None,
+                };
+
+                // a malformed phone number is replaced by a fake one
+                Column::StringValue(
+                    column_name,
+                    new_value.unwrap_or_else(|| PhoneNumber(EN).fake_with_rng(&mut rng())),
+                )
             }
             column => column,
         }
@@ -69,7 +131,7 @@ impl Transformer for PhoneNumberTransformer {
 mod tests {
     use crate::{transformer::Transformer, types::Column};
 
-    use super::PhoneNumberTransformer;
+    use super::{PhoneNumberOptions, PhoneNumberTransformer};
 
     #[test]
     fn transform_string_with_a_phone_number() {
@@ -82,7 +144,53 @@ mod tests {
         assert_ne!(transformed_value, "+123456789".to_string());
     }
 
+    #[test]
+    fn transform_keeps_the_area_code() {
+        let transformer = get_transformer_keeping_area_code();
+
+        for (value, kept_part) in [
+            ("+1 (212) 555-0123", "+1 (212) "),
+            ("212-555-0123", "212-"),
+            ("12125550123", "1212"),
+        ] {
+            let column = Column::StringValue("phone_number".to_string(), value.to_string());
+            let transformed_column = transformer.transform(column);
+            let transformed_value = transformed_column.string_value().unwrap();
+
+            assert_eq!(transformed_value.len(), value.len());
+            assert!(transformed_value.starts_with(kept_part));
+            assert_ne!(transformed_value, value);
+        }
+    }
+
+    #[test]
+    fn transform_malformed_phone_number_with_area_code_option() {
+        let transformer = get_transformer_keeping_area_code();
+        let column = Column::StringValue("phone_number".to_string(), "+33 1 23".to_string());
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert!(!transformed_value.is_empty());
+        assert_ne!(transformed_value, "+33 1 23".to_string());
+    }
+
     fn get_transformer() -> PhoneNumberTransformer {
-        PhoneNumberTransformer::new("github", "users", "phone_number")
+        PhoneNumberTransformer::new(
+            "github",
+            "users",
+            "phone_number",
+            PhoneNumberOptions::default(),
+        )
+    }
+
+    fn get_transformer_keeping_area_code() -> PhoneNumberTransformer {
+        PhoneNumberTransformer::new(
+            "github",
+            "users",
+            "phone_number",
+            PhoneNumberOptions {
+                keep_area_code: Some(true),
+            },
+        )
     }
 }
@@ -0,0 +1,206 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::transformer::seeded::rng;
+use crate::transformer::{hash_row, Transformer};
+use crate::types::Column;
+
+/// (city, country, latitude, longitude)
+const PLACES: [(&str, &str, f64, f64); 16] = [
+    ("Paris", "France", 48.8566, 2.3522),
+    ("Lyon", "France", 45.7640, 4.8357),
+    ("Berlin", "Germany", 52.5200, 13.4050),
+    ("Munich", "Germany", 48.1351, 11.5820),
+    ("Madrid", "Spain", 40.4168, -3.7038),
+    ("Rome", "Italy", 41.9028, 12.4964),
+    ("London", "United Kingdom", 51.5072, -0.1276),
+    ("Dublin", "Ireland", 53.3498, -6.2603),
+    ("New York", "United States", 40.7128, -74.0060),
+    ("San Francisco", "United States", 37.7749, -122.4194),
+    ("Toronto", "Canada", 43.6532, -79.3832),
+    ("Mexico City", "Mexico", 19.4326, -99.1332),
+    ("São Paulo", "Brazil", -23.5505, -46.6333),
+    ("Tokyo", "Japan", 35.6762, 139.6503),
+    ("Sydney", "Australia", -33.8688, 151.2093),
+    ("Cape Town", "South Africa", -33.9249, 18.4241),
+];
+
+/// This struct is dedicated to replacing a part of a place (city, country, latitude or longitude).
+/// All the place transformers of a row pick the same place, so the city, the country and the coordinates agree.
+pub struct PlaceTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    options: PlaceOptions,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct PlaceOptions {
+    pub field: PlaceField,
+    /// column identifying the row (e.g. id) - the whole row is used if not set
+    pub key_column: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaceField {
+    City,
+    Country,
+    Latitude,
+    Longitude,
+}
+
+impl PlaceTransformer {
+    pub fn new<S>(database_name: S, table_name: S, column_name: S, options: PlaceOptions) -> Self
+    where
+        S: Into<String>,
+    {
+        PlaceTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            options,
+        }
+    }
+
+    /// pick the same place for every column of the same row
+    fn place_index(&self, row: &[Column]) -> usize {
+        let hash = hash_row(row, self.options.key_column.as_deref());
+        (hash % PLACES.len() as u64) as usize
+    }
+
+    fn transform_with_place(&self, column: Column, place_index: usize) -> Column {
+        let (city, country, latitude, longitude) = PLACES[place_index];
+
+        match (self.options.field, column) {
+            (PlaceField::City, Column::StringValue(column_name, _)) => {
+                Column::StringValue(column_name, city.to_string())
+            }
+            (PlaceField::Country, Column::StringValue(column_name, _)) => {
+                Column::StringValue(column_name, country.to_string())
+            }
+            (PlaceField::Latitude, Column::FloatNumberValue(column_name, _)) => {
+                Column::FloatNumberValue(column_name, latitude)
+            }
+            (PlaceField::Latitude, Column::StringValue(column_name, _)) => {
+                Column::StringValue(column_name, latitude.to_string())
+            }
+            (PlaceField::Longitude, Column::FloatNumberValue(column_name, _)) => {
+                Column::FloatNumberValue(column_name, longitude)
+            }
+            (PlaceField::Longitude, Column::StringValue(column_name, _)) => {
+                Column::StringValue(column_name, longitude.to_string())
+            }
+            (_, column) => column,
+        }
+    }
+}
+
+impl Default for PlaceTransformer {
+    fn default() -> Self {
+        PlaceTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            options: PlaceOptions {
+                field: PlaceField::City,
+                key_column: None,
+            },
+        }
+    }
+}
+
+impl Transformer for PlaceTransformer {
+    fn id(&self) -> &str {
+        "place"
+    }
+
+    fn description(&self) -> &str {
+        "Generate the city, country, latitude or longitude of the same place for the whole row. [Lille, France]->[Tokyo, Japan]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        // without the row, the place can't be shared with the other columns
+        let place_index = rng().gen_range(0..PLACES.len());
+        self.transform_with_place(column, place_index)
+    }
+
+    fn transform_with_row(&self, column: Column, row: &[Column]) -> Column {
+        self.transform_with_place(column, self.place_index(row))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{PlaceField, PlaceOptions, PlaceTransformer, PLACES};
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = get_transformer(PlaceField::City);
+        let column = Column::NumberValue("city".to_string(), 42);
+        let transformed_column = transformer.transform_with_row(column, &get_row(1));
+        let transformed_value = transformed_column.number_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 42)
+    }
+
+    #[test]
+    fn transform_picks_the_same_place_for_the_row() {
+        for id in 0..50 {
+            let row = get_row(id);
+
+            let city = get_transformer(PlaceField::City).transform_with_row(row[1].clone(), &row);
+            let country =
+                get_transformer(PlaceField::Country).transform_with_row(row[2].clone(), &row);
+            let latitude =
+                get_transformer(PlaceField::Latitude).transform_with_row(row[3].clone(), &row);
+            let longitude =
+                get_transformer(PlaceField::Longitude).transform_with_row(row[4].clone(), &row);
+
+            let place = PLACES
+                .iter()
+                .find(|(city_name, _, _, _)| *city_name == city.string_value().unwrap())
+                .unwrap();
+
+            assert_eq!(place.1, country.string_value().unwrap());
+            assert_eq!(place.2, *latitude.float_value().unwrap());
+            assert_eq!(place.3.to_string(), longitude.string_value().unwrap());
+        }
+    }
+
+    fn get_row(id: i128) -> Vec<Column> {
+        vec![
+            Column::NumberValue("id".to_string(), id),
+            Column::StringValue("city".to_string(), "Lille".to_string()),
+            Column::StringValue("country".to_string(), "France".to_string()),
+            Column::FloatNumberValue("latitude".to_string(), 50.6292),
+            Column::StringValue("longitude".to_string(), "3.0573".to_string()),
+        ]
+    }
+
+    fn get_transformer(field: PlaceField) -> PlaceTransformer {
+        PlaceTransformer::new(
+            "github",
+            "offices",
+            "city",
+            PlaceOptions {
+                field,
+                key_column: Some("id".to_string()),
+            },
+        )
+    }
+}
@@ -0,0 +1,325 @@
+use std::ffi::{CStr, CString};
+use std::io::{Error, ErrorKind};
+use std::os::raw::{c_char, c_void};
+use std::sync::{Arc, Mutex};
+
+use lazy_static::lazy_static;
+use libloading::Library;
+
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+/// version of the plugin interface - a plugin built for another version is not loaded
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+
+/// `extern "C" fn replibyte_plugin_abi_version() -> u32`: the `PLUGIN_ABI_VERSION` the plugin is built for
+type AbiVersionFn = unsafe extern "C" fn() -> u32;
+/// `extern "C" fn replibyte_transformer_new(id, options) -> *mut c_void`: a new transformer, from its id and its
+/// options as JSON (`null` when there is none) - or null if the plugin does not provide this transformer
+type NewFn = unsafe extern "C" fn(*const c_char, *const c_char) -> *mut c_void;
+/// `extern "C" fn replibyte_transformer_transform(transformer, value) -> *mut c_char`: the transformed value - or null
+/// on error. The value is written as text, like for the `custom-wasm` transformer
+type TransformFn = unsafe extern "C" fn(*mut c_void, *const c_char) -> *mut c_char;
+/// `extern "C" fn replibyte_transformer_free(transformer)`: release a transformer created by the plugin
+type FreeFn = unsafe extern "C" fn(*mut c_void);
+/// `extern "C" fn replibyte_string_free(value)`: release a value returned by the plugin
+type FreeStringFn = unsafe extern "C" fn(*mut c_char);
+
+lazy_static! {
+    /// plugins listed in the `plugins` of the configuration - they are never unloaded
+    static ref PLUGINS: Mutex<Vec<Arc<Plugin>>> = Mutex::new(vec![]);
+}
+
+/// shared library exporting the functions of the plugin interface
+struct Plugin {
+    path: String,
+    new: NewFn,
+    transform: TransformFn,
+    free: FreeFn,
+    free_string: FreeStringFn,
+    // the functions above point into the library, it must live as long as them
+    _library: Library,
+}
+
+impl Plugin {
+    fn load(path: &str) -> Result<Self, Error> {
+        let to_error = |err: libloading::Error| {
+            Error::new(
+                ErrorKind::Other,
+                format!("failed to load plugin '{}': {}", path, err),
+            )
+        };
+
+        // the plugin runs in the process: loading it is as safe as the library it is
+        unsafe {
+            let library = Library::new(path).map_err(to_error)?;
+
+            let abi_version = *library
+                .get::<AbiVersionFn>(b"replibyte_plugin_abi_version\0")
+                .map_err(to_error)?;
+            if abi_version() != PLUGIN_ABI_VERSION {
+                return Err(Error::new(
+                    ErrorKind::Other,
+                    format!(
+                        "plugin '{}' is built for the plugin interface v{}, v{} is expected",
+                        path,
+                        abi_version(),
+                        PLUGIN_ABI_VERSION
+                    ),
+                ));
+            }
+
+            Ok(Plugin {
+                path: path.to_string(),
+                new: *library
+                    .get::<NewFn>(b"replibyte_transformer_new\0")
+                    .map_err(to_error)?,
+                transform: *library
+                    .get::<TransformFn>(b"replibyte_transformer_transform\0")
+                    .map_err(to_error)?,
+                free: *library
+                    .get::<FreeFn>(b"replibyte_transformer_free\0")
+                    .map_err(to_error)?,
+                free_string: *library
+                    .get::<FreeStringFn>(b"replibyte_string_free\0")
+                    .map_err(to_error)?,
+                _library: library,
+            })
+        }
+    }
+}
+
+/// load the plugins, their transformers can then be used with the `plugin` transformer
+pub fn load_plugins(paths: &[String]) -> Result<(), Error> {
+    let mut plugins = PLUGINS.lock().unwrap();
+
+    for path in paths {
+        if plugins.iter().any(|plugin| plugin.path == *path) {
+            continue;
+        }
+
+        plugins.push(Arc::new(Plugin::load(path.as_str())?));
+    }
+
+    Ok(())
+}
+
+/// transformer created by a plugin
+struct PluginInstance {
+    plugin: Arc<Plugin>,
+    transformer: *mut c_void,
+}
+
+impl PluginInstance {
+    fn transform(&self, value: &str) -> Result<String, Error> {
+        let value = CString::new(value).map_err(|err| Error::new(ErrorKind::Other, err))?;
+        // SAFETY: the plugin interface requires `replibyte_transformer_transform` to only read the NUL-terminated
+        // value for the duration of the call, and `self.transformer` is a live transformer created by this plugin
+        let new_value = unsafe { (self.plugin.transform)(self.transformer, value.as_ptr()) };
+
+        if new_value.is_null() {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!("plugin '{}' failed to transform a value", self.plugin.path),
+            ));
+        }
+
+        // SAFETY: a value returned by the plugin is a NUL-terminated string it owns until `replibyte_string_free`
+        let result = unsafe { CStr::from_ptr(new_value) }
+            .to_str()
+            .map(String::from)
+            .map_err(|err| Error::new(ErrorKind::Other, err));
+        // SAFETY: the value was returned by this plugin and is released once, it is not read afterwards
+        unsafe { (self.plugin.free_string)(new_value) };
+
+        result
+    }
+}
+
+impl Drop for PluginInstance {
+    fn drop(&mut self) {
+        // SAFETY: the transformer was created by this plugin and is released once, when this instance is dropped
+        unsafe { (self.plugin.free)(self.transformer) };
+    }
+}
+
+/// This struct is dedicated to applying a transformer provided by a plugin (a shared library listed in `plugins`).
+pub struct PluginTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    instance: Option<PluginInstance>,
+}
+
+impl PluginTransformer {
+    pub fn new<S>(
+        database_name: S,
+        table_name: S,
+        column_name: S,
+        id: &str,
+        options: &Option<serde_json::Value>,
+    ) -> Result<Self, Error>
+    where
+        S: Into<String>,
+    {
+        let c_id = CString::new(id).map_err(|err| Error::new(ErrorKind::Other, err))?;
+        let c_options = CString::new(serde_json::to_string(options)?)
+            .map_err(|err| Error::new(ErrorKind::Other, err))?;
+
+        // the first plugin providing the transformer is used
+        let instance = PLUGINS.lock().unwrap().iter().find_map(|plugin| {
+            // SAFETY: the plugin interface requires `replibyte_transformer_new` to only read the NUL-terminated id and
+            // options for the duration of the call - the transformer it returns is owned by the caller
+            let transformer = unsafe { (plugin.new)(c_id.as_ptr(), c_options.as_ptr()) };
+
+            match transformer.is_null() {
+                true => None,
+                false => Some(PluginInstance {
+                    plugin: plugin.clone(),
+                    transformer,
+                }),
+            }
+        });
+
+        match instance {
+            Some(instance) => Ok(PluginTransformer {
+                database_name: database_name.into(),
+                table_name: table_name.into(),
+                column_name: column_name.into(),
+                instance: Some(instance),
+            }),
+            None => Err(Error::new(
+                ErrorKind::Other,
+                format!("no plugin provides the transformer '{}'", id),
+            )),
+        }
+    }
+
+    fn call_plugin(&self, value: &str) -> String {
+        match &self.instance {
+            Some(instance) => instance.transform(value).expect("Plugin call failed"),
+            None => value.to_string(),
+        }
+    }
+}
+
+impl Default for PluginTransformer {
+    fn default() -> Self {
+        PluginTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            instance: None,
+        }
+    }
+}
+
+impl Transformer for PluginTransformer {
+    fn id(&self) -> &str {
+        "plugin"
+    }
+
+    fn description(&self) -> &str {
+        "Provide a custom transformer as a shared library loaded at runtime."
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        match column {
+            Column::StringValue(column_name, value) => {
+                Column::StringValue(column_name, self.call_plugin(value.as_str()))
+            }
+            Column::NumberValue(column_name, value) => Column::NumberValue(
+                column_name,
+                self.call_plugin(value.to_string().as_str())
+                    .parse::<i128>()
+                    .expect("Plugin failed to return a number"),
+            ),
+            Column::FloatNumberValue(column_name, value) => Column::FloatNumberValue(
+                column_name,
+                self.call_plugin(value.to_string().as_str())
+                    .parse::<f64>()
+                    .expect("Plugin failed to return a number (float)"),
+            ),
+            Column::CharValue(column_name, value) => Column::CharValue(
+                column_name,
+                self.call_plugin(value.to_string().as_str())
+                    .parse::<char>()
+                    .expect("Plugin failed to return a char"),
+            ),
+            Column::BooleanValue(column_name, value) => Column::BooleanValue(
+                column_name,
+                self.call_plugin(value.to_string().as_str())
+                    .parse::<bool>()
+                    .expect("Plugin failed to return a boolean"),
+            ),
+            Column::None(column_name) => Column::None(column_name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+    use std::process::Command;
+
+    use tempfile::tempdir;
+
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{load_plugins, PluginTransformer};
+
+    /// build the example plugin and return the path of the shared library
+    fn build_plugin(dir: &std::path::Path) -> String {
+        let path = dir.join(format!("{}reverse_string{}", DLL_PREFIX, DLL_SUFFIX));
+
+        let status = Command::new(std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string()))
+            .args(["--edition", "2021", "--crate-type", "cdylib", "-o"])
+            .arg(&path)
+            .arg("../examples/plugin/reverse_string.rs")
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn transform_with_a_plugin() {
+        let dir = tempdir().unwrap();
+        load_plugins(&[build_plugin(dir.path())]).unwrap();
+
+        let transformer =
+            PluginTransformer::new("github", "users", "name", "reverse-string", &None).unwrap();
+
+        let column = Column::StringValue("name".to_string(), "Nancy".to_string());
+        let transformed_column = transformer.transform(column);
+        assert_eq!(transformed_column.string_value().unwrap(), "ycnaN");
+
+        let column = Column::NumberValue("name".to_string(), 123);
+        let transformed_column = transformer.transform(column);
+        assert_eq!(transformed_column.number_value().unwrap().to_owned(), 321);
+
+        // the plugin does not provide this transformer
+        assert!(PluginTransformer::new("github", "users", "name", "upper-case", &None).is_err());
+    }
+
+    #[test]
+    fn load_plugins_fails_with_a_missing_library() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("missing.so").to_str().unwrap().to_string();
+
+        assert!(load_plugins(&[path]).is_err());
+    }
+}
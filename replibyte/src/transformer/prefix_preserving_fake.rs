@@ -0,0 +1,255 @@
+use fake::locales::{Data, EN};
+use rand::seq::{IteratorRandom, SliceRandom};
+use serde::{Deserialize, Serialize};
+
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+/// This struct is dedicated to replacing a value by a fake one of the same kind starting with the same characters,
+/// e.g. for the search features relying on the first characters of a city or a name. [Lille]->[Lilyton]
+pub struct PrefixPreservingFakeTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    options: PrefixPreservingFakeOptions,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+pub struct PrefixPreservingFakeOptions {
+    /// number of characters kept at the start of the value
+    pub keep_prefix: usize,
+    pub kind: FakeKind,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum FakeKind {
+    /// e.g. Lilyton
+    City,
+    /// e.g. Lily
+    Name,
+    /// e.g. consequatur
+    Word,
+}
+
+impl FakeKind {
+    /// words completing the prefix - a city is a name followed by a city suffix
+    fn words(&self) -> Vec<&'static str> {
+        match self {
+            FakeKind::City | FakeKind::Name => EN::NAME_FIRST_NAME
+                .iter()
+                .chain(EN::NAME_LAST_NAME.iter())
+                .copied()
+                .collect(),
+            FakeKind::Word => EN::LOREM_WORD.to_vec(),
+        }
+    }
+}
+
+impl PrefixPreservingFakeTransformer {
+    pub fn new<S>(
+        database_name: S,
+        table_name: S,
+        column_name: S,
+        options: PrefixPreservingFakeOptions,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        PrefixPreservingFakeTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            options,
+        }
+    }
+
+    fn fake_value(&self, value: &str) -> String {
+        let prefix = value
+            .chars()
+            .take(self.options.keep_prefix)
+            .collect::<String>();
+        let prefix_length = prefix.chars().count();
+
+        let mut random = rand::thread_rng();
+        let words = self.options.kind.words();
+
+        // the end of a word starting with the prefix (e.g. Lil -> y for Lily), or of any longer word if there is none
+        let completions = words
+            .iter()
+            .filter_map(|word| completion_of(word, prefix.as_str()))
+            .filter(|completion| format!("{}{}", prefix, completion) != value)
+            .collect::<Vec<_>>();
+
+        let completion = match completions.choose(&mut random) {
+            Some(completion) => completion.to_string(),
+            None => words
+                .iter()
+                .filter(|word| word.chars().count() > prefix_length)
+                .choose(&mut random)
+                .map(|word| word.chars().skip(prefix_length).collect())
+                .unwrap_or_default(),
+        };
+
+        let completion = match self.options.kind {
+            FakeKind::City => format!(
+                "{}{}",
+                completion,
+                EN::ADDRESS_CITY_SUFFIX.choose(&mut random).unwrap()
+            ),
+            FakeKind::Name | FakeKind::Word => completion,
+        };
+
+        // an upper case value stays upper case (e.g. LILLE -> LILYTON)
+        let completion = match is_upper_case(value) {
+            true => completion.to_uppercase(),
+            false => completion,
+        };
+
+        format!("{}{}", prefix, completion)
+    }
+}
+
+/// the non-empty end of `word` after `prefix` (case insensitive) - none if `word` doesn't start with `prefix`
+fn completion_of(word: &'static str, prefix: &str) -> Option<&'static str> {
+    let word_prefix = word
+        .chars()
+        .take(prefix.chars().count())
+        .collect::<String>();
+
+    match word_prefix.to_lowercase() == prefix.to_lowercase() && word_prefix.len() < word.len() {
+        true => Some(&word[word_prefix.len()..]),
+        false => None,
+    }
+}
+
+fn is_upper_case(value: &str) -> bool {
+    value.chars().any(char::is_alphabetic) && !value.chars().any(char::is_lowercase)
+}
+
+impl Default for PrefixPreservingFakeTransformer {
+    fn default() -> Self {
+        PrefixPreservingFakeTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            options: PrefixPreservingFakeOptions {
+                keep_prefix: 3,
+                kind: FakeKind::Word,
+            },
+        }
+    }
+}
+
+impl Transformer for PrefixPreservingFakeTransformer {
+    fn id(&self) -> &str {
+        "prefix-preserving-fake"
+    }
+
+    fn description(&self) -> &str {
+        "Generate a city, a name or a word starting with the same characters (string only). [Lille]->[Lilyton]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        match column {
+            Column::StringValue(column_name, value) => {
+                if value.is_empty() {
+                    return Column::StringValue(column_name, value);
+                }
+
+                Column::StringValue(column_name, self.fake_value(value.as_str()))
+            }
+            column => column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fake::locales::{Data, EN};
+
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{FakeKind, PrefixPreservingFakeOptions, PrefixPreservingFakeTransformer};
+
+    fn transform(value: &str, keep_prefix: usize, kind: FakeKind) -> String {
+        let transformer = PrefixPreservingFakeTransformer::new(
+            "github",
+            "customers",
+            "city",
+            PrefixPreservingFakeOptions { keep_prefix, kind },
+        );
+
+        let column = Column::StringValue("city".to_string(), value.to_string());
+        let transformed_column = transformer.transform(column);
+        transformed_column.string_value().unwrap().to_string()
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = PrefixPreservingFakeTransformer::default();
+        let column = Column::NumberValue("city".to_string(), 42);
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.number_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 42)
+    }
+
+    #[test]
+    fn transform_to_a_city_with_the_same_prefix() {
+        for _ in 0..20 {
+            let city = transform("Lille", 3, FakeKind::City);
+
+            assert!(city.starts_with("Lil"));
+            assert_ne!(city, "Lille");
+            assert!(EN::ADDRESS_CITY_SUFFIX
+                .iter()
+                .any(|suffix| city.ends_with(suffix)));
+        }
+    }
+
+    #[test]
+    fn transform_to_a_name_with_the_same_prefix() {
+        for _ in 0..20 {
+            let name = transform("Margaret", 3, FakeKind::Name);
+
+            assert!(name.starts_with("Mar"));
+            assert_ne!(name, "Margaret");
+            assert!(
+                EN::NAME_FIRST_NAME.contains(&name.as_str())
+                    || EN::NAME_LAST_NAME.contains(&name.as_str())
+            );
+        }
+    }
+
+    #[test]
+    fn transform_to_a_word_with_the_same_prefix() {
+        let word = transform("Contract", 3, FakeKind::Word);
+
+        assert!(word.starts_with("Con"));
+        assert!(EN::LOREM_WORD.contains(&word.to_lowercase().as_str()));
+    }
+
+    #[test]
+    fn transform_keeps_the_prefix_without_matching_word() {
+        let name = transform("Xqzt", 3, FakeKind::Name);
+        assert!(name.starts_with("Xqz"));
+        assert!(name.len() > 3);
+
+        let city = transform("PARIS", 2, FakeKind::City);
+        assert!(city.starts_with("PA"));
+        assert_eq!(city, city.to_uppercase());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::transformer::seeded::rng;
 use crate::transformer::Transformer;
 use crate::types::Column;
 use rand::distributions::Alphanumeric;
@@ -55,7 +56,7 @@ impl Transformer for RandomTransformer {
     }
 
     fn transform(&self, column: Column) -> Column {
-        let mut random = rand::thread_rng();
+        let mut random = rng();
 
         match column {
             Column::NumberValue(column_name, _) => {
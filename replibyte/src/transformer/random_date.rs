@@ -0,0 +1,252 @@
+use std::io::{Error, ErrorKind};
+
+use chrono::format::{Item, StrftimeItems};
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::transformer::seeded::rng;
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+/// smallest generated date by default
+const DEFAULT_MIN: &str = "1970-01-01";
+/// format of the generated dates by default
+const DEFAULT_FORMAT: &str = "%Y-%m-%d";
+
+/// This struct is dedicated to replacing a date by a random one between a min and a max date.
+pub struct RandomDateTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    min: NaiveDateTime,
+    max: NaiveDateTime,
+    format: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct RandomDateOptions {
+    /// smallest generated date, as an ISO 8601 date or timestamp (default to 1970-01-01)
+    pub min: Option<String>,
+    /// largest generated date, as an ISO 8601 date or timestamp (default to now)
+    pub max: Option<String>,
+    /// strftime format of the generated dates (default to %Y-%m-%d)
+    pub format: Option<String>,
+}
+
+/// date or timestamp of an ISO 8601 string (e.g. 1987-05-12 or 1987-05-12T08:30:00) - the time zone is ignored
+fn parse_date_time(value: &str) -> Option<NaiveDateTime> {
+    let date = NaiveDate::parse_from_str(value.get(..10)?, "%Y-%m-%d").ok()?;
+
+    // midnight for a date without time
+    let time = value
+        .get(11..19)
+        .and_then(|time| NaiveTime::parse_from_str(time, "%H:%M:%S").ok())
+        .unwrap_or_else(|| NaiveTime::from_hms_opt(0, 0, 0).unwrap());
+
+    Some(NaiveDateTime::new(date, time))
+}
+
+impl RandomDateTransformer {
+    pub fn new<S>(
+        database_name: S,
+        table_name: S,
+        column_name: S,
+        options: RandomDateOptions,
+    ) -> Result<Self, Error>
+    where
+        S: Into<String>,
+    {
+        let parse_option = |name: &str, value: &str| {
+            parse_date_time(value).ok_or_else(|| {
+                Error::new(
+                    ErrorKind::Other,
+                    format!("{} '{}' is not an ISO 8601 date", name, value),
+                )
+            })
+        };
+
+        let min = parse_option("min", options.min.as_deref().unwrap_or(DEFAULT_MIN))?;
+        let max = match options.max.as_deref() {
+            Some(max) => parse_option("max", max)?,
+            None => Utc::now().naive_utc(),
+        };
+
+        if min > max {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!("min ({}) is after max ({})", min, max),
+            ));
+        }
+
+        let format = options.format.unwrap_or_else(|| DEFAULT_FORMAT.to_string());
+
+        if StrftimeItems::new(format.as_str()).any(|item| item == Item::Error) {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!("'{}' is not a valid date format", format),
+            ));
+        }
+
+        Ok(RandomDateTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            min,
+            max,
+            format,
+        })
+    }
+
+    fn random_date(&self) -> String {
+        let seconds = (self.max - self.min).num_seconds();
+        let date = self.min + Duration::seconds(rng().gen_range(0..=seconds));
+
+        date.format(self.format.as_str()).to_string()
+    }
+}
+
+impl Default for RandomDateTransformer {
+    fn default() -> Self {
+        RandomDateTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            min: parse_date_time(DEFAULT_MIN).unwrap(),
+            max: Utc::now().naive_utc(),
+            format: DEFAULT_FORMAT.to_string(),
+        }
+    }
+}
+
+impl Transformer for RandomDateTransformer {
+    fn id(&self) -> &str {
+        "random-date"
+    }
+
+    fn description(&self) -> &str {
+        "Generate a random date between a min and a max date (string only). [1987-05-12]->[2003-11-27]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        match column {
+            // only ISO 8601 dates and timestamps are replaced (e.g. 1987-05-12 or 1987-05-12T08:30:00Z)
+            Column::StringValue(column_name, value) => match parse_date_time(value.as_str()) {
+                Some(_) => Column::StringValue(column_name, self.random_date()),
+                None => Column::StringValue(column_name, value),
+            },
+            column => column,
+        }
+    }
+
+    fn validate(&self, column: &Column) -> Result<(), Error> {
+        match column {
+            Column::StringValue(_, value) if parse_date_time(value.as_str()).is_none() => Err(
+                Error::new(ErrorKind::Other, "the value is not an ISO 8601 date"),
+            ),
+            Column::StringValue(_, _) | Column::None(_) => Ok(()),
+            _ => Err(Error::new(
+                ErrorKind::Other,
+                "only string values are supported",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDateTime;
+
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{RandomDateOptions, RandomDateTransformer};
+
+    fn get_transformer(min: &str, max: &str, format: Option<&str>) -> RandomDateTransformer {
+        let options = RandomDateOptions {
+            min: Some(min.to_string()),
+            max: Some(max.to_string()),
+            format: format.map(|format| format.to_string()),
+        };
+
+        RandomDateTransformer::new("github", "users", "birth_date", options).unwrap()
+    }
+
+    fn transform(transformer: &RandomDateTransformer, value: &str) -> String {
+        let column = Column::StringValue("birth_date".to_string(), value.to_string());
+        let transformed_column = transformer.transform(column);
+        transformed_column.string_value().unwrap().to_string()
+    }
+
+    #[test]
+    fn transform_to_a_date_within_the_range() {
+        let transformer = get_transformer("2020-01-01", "2020-12-31", None);
+
+        for value in ["1987-05-12", "1987-05-12 08:30:00", "1987-05-12T08:30:00Z"] {
+            for _ in 0..50 {
+                let date = transform(&transformer, value);
+
+                assert_eq!(date.len(), 10);
+                assert!(date.as_str() >= "2020-01-01" && date.as_str() <= "2020-12-31");
+            }
+        }
+    }
+
+    #[test]
+    fn transform_with_the_format() {
+        let transformer = get_transformer(
+            "2020-01-01T08:00:00",
+            "2020-01-01T18:00:00",
+            Some("%Y-%m-%d %H:%M:%S"),
+        );
+
+        let date = transform(&transformer, "1987-05-12");
+
+        assert!(NaiveDateTime::parse_from_str(date.as_str(), "%Y-%m-%d %H:%M:%S").is_ok());
+        assert!(date.as_str() >= "2020-01-01 08:00:00" && date.as_str() <= "2020-01-01 18:00:00");
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_other_value() {
+        let transformer = RandomDateTransformer::default();
+
+        assert_eq!(transform(&transformer, "not a date"), "not a date");
+        assert_eq!(transform(&transformer, ""), "");
+
+        let column = Column::None("birth_date".to_string());
+        let transformed_column = transformer.transform(column);
+        assert!(matches!(transformed_column, Column::None(_)));
+
+        let column = Column::NumberValue("birth_date".to_string(), 42);
+        let transformed_column = transformer.transform(column);
+        assert_eq!(transformed_column.number_value().unwrap().to_owned(), 42);
+    }
+
+    #[test]
+    fn new_fails_with_invalid_options() {
+        for (min, max, format) in [
+            ("2020-12-31", "2020-01-01", None),
+            ("31/12/2020", "2021-01-01", None),
+            ("2020-01-01", "2021-01-01", Some("%Y-%m-%")),
+        ] {
+            let options = RandomDateOptions {
+                min: Some(min.to_string()),
+                max: Some(max.to_string()),
+                format: format.map(|format| format.to_string()),
+            };
+
+            assert!(RandomDateTransformer::new("github", "users", "birth_date", options).is_err());
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use std::io::{Error, ErrorKind};
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+/// This struct is dedicated to replacing an order or reference code by a fake one made of a prefix, random digits and
+/// an optional check digit, so validators still accept it. [ORD-2024-0001230]->[ORD-2024-8273641]
+pub struct ReferenceCodeTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    options: ReferenceCodeOptions,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct ReferenceCodeOptions {
+    /// start of every code (e.g. ORD-2024-)
+    pub prefix: String,
+    /// number of random digits after the prefix, the check digits excluded
+    pub digits: usize,
+    /// check digits appended to the random ones (default to none)
+    pub checksum: Option<ChecksumKind>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum ChecksumKind {
+    /// 1 check digit, the mod 10 algorithm of the credit card numbers
+    Luhn,
+    /// 2 check digits, the ISO 7064 MOD 97-10 algorithm of the IBANs
+    Mod97,
+}
+
+impl ChecksumKind {
+    /// check digits of `digits`
+    fn check_digits(&self, digits: &[u32]) -> String {
+        match self {
+            ChecksumKind::Luhn => {
+                // double the digits at even positions from the end, the check digit excluded
+                let sum: u32 = digits
+                    .iter()
+                    .rev()
+                    .enumerate()
+                    .map(|(idx, digit)| match idx % 2 {
+                        0 if digit * 2 > 9 => digit * 2 - 9,
+                        0 => digit * 2,
+                        _ => *digit,
+                    })
+                    .sum();
+
+                ((10 - sum % 10) % 10).to_string()
+            }
+            ChecksumKind::Mod97 => {
+                // the number followed by 2 zeros, mod 97 - computed digit by digit as it can be larger than an u64
+                let remainder = digits
+                    .iter()
+                    .chain([0, 0].iter())
+                    .fold(0, |remainder, digit| (remainder * 10 + digit) % 97);
+
+                format!("{:02}", 98 - remainder)
+            }
+        }
+    }
+}
+
+impl ReferenceCodeTransformer {
+    pub fn new<S>(
+        database_name: S,
+        table_name: S,
+        column_name: S,
+        options: ReferenceCodeOptions,
+    ) -> Result<Self, Error>
+    where
+        S: Into<String>,
+    {
+        if options.digits == 0 {
+            return Err(Error::new(
+                ErrorKind::Other,
+                "a reference code needs at least 1 digit",
+            ));
+        }
+
+        Ok(ReferenceCodeTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            options,
+        })
+    }
+
+    fn reference_code(&self) -> String {
+        let mut random = rand::thread_rng();
+        let digits = (0..self.options.digits)
+            .map(|_| random.gen_range(0..10))
+            .collect::<Vec<u32>>();
+
+        let check_digits = match self.options.checksum {
+            Some(checksum) => checksum.check_digits(&digits),
+            None => String::new(),
+        };
+
+        format!(
+            "{}{}{}",
+            self.options.prefix,
+            digits
+                .iter()
+                .map(|digit| digit.to_string())
+                .collect::<String>(),
+            check_digits
+        )
+    }
+}
+
+impl Default for ReferenceCodeTransformer {
+    fn default() -> Self {
+        ReferenceCodeTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            options: ReferenceCodeOptions {
+                prefix: "REF-".to_string(),
+                digits: 8,
+                checksum: None,
+            },
+        }
+    }
+}
+
+impl Transformer for ReferenceCodeTransformer {
+    fn id(&self) -> &str {
+        "reference-code"
+    }
+
+    fn description(&self) -> &str {
+        "Generate a reference code with a prefix and an optional check digit (string only). [ORD-2024-0001230]->[ORD-2024-8273641]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        match column {
+            Column::StringValue(column_name, value) if value.is_empty() => {
+                Column::StringValue(column_name, value)
+            }
+            Column::StringValue(column_name, _) => {
+                Column::StringValue(column_name, self.reference_code())
+            }
+            column => column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{ChecksumKind, ReferenceCodeOptions, ReferenceCodeTransformer};
+
+    fn transform(digits: usize, checksum: Option<ChecksumKind>) -> String {
+        let options = ReferenceCodeOptions {
+            prefix: "ORD-2024-".to_string(),
+            digits,
+            checksum,
+        };
+        let transformer =
+            ReferenceCodeTransformer::new("github", "orders", "reference", options).unwrap();
+        let column = Column::StringValue("reference".to_string(), "ORD-2024-0001230".to_string());
+        let transformed_column = transformer.transform(column);
+        transformed_column.string_value().unwrap().to_string()
+    }
+
+    fn digits(value: &str) -> Vec<u32> {
+        value.chars().filter_map(|c| c.to_digit(10)).collect()
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = ReferenceCodeTransformer::default();
+        let column = Column::NumberValue("reference".to_string(), 42);
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.number_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 42)
+    }
+
+    #[test]
+    fn transform_to_a_code_with_the_prefix_and_digits() {
+        let value = transform(6, None);
+
+        assert_eq!(value.len(), 15);
+        assert!(value.starts_with("ORD-2024-"));
+        assert!(value["ORD-2024-".len()..]
+            .chars()
+            .all(|c| c.is_ascii_digit()));
+    }
+
+    #[test]
+    fn transform_to_a_code_with_a_luhn_check_digit() {
+        for _ in 0..100 {
+            let value = transform(6, Some(ChecksumKind::Luhn));
+            assert_eq!(value.len(), 16);
+
+            // the sum of the digits, every second one doubled from the end, is a multiple of 10
+            let sum: u32 = digits(&value["ORD-2024-".len()..])
+                .iter()
+                .rev()
+                .enumerate()
+                .map(|(idx, digit)| match idx % 2 {
+                    1 if digit * 2 > 9 => digit * 2 - 9,
+                    1 => digit * 2,
+                    _ => *digit,
+                })
+                .sum();
+            assert_eq!(sum % 10, 0);
+        }
+    }
+
+    #[test]
+    fn transform_to_a_code_with_mod97_check_digits() {
+        for _ in 0..100 {
+            let value = transform(30, Some(ChecksumKind::Mod97));
+            assert_eq!(value.len(), 41);
+
+            // the whole number mod 97 is 1
+            let remainder = digits(&value["ORD-2024-".len()..])
+                .iter()
+                .fold(0, |remainder, digit| (remainder * 10 + digit) % 97);
+            assert_eq!(remainder, 1);
+        }
+    }
+
+    #[test]
+    fn new_fails_without_digits() {
+        let options = ReferenceCodeOptions {
+            prefix: "ORD-".to_string(),
+            digits: 0,
+            checksum: None,
+        };
+
+        assert!(ReferenceCodeTransformer::new("github", "orders", "reference", options).is_err());
+    }
+}
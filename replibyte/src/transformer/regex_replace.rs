@@ -0,0 +1,167 @@
+use log::warn;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+/// This struct is dedicated to replacing the parts of a string matching a pattern, for the formats no other transformer
+/// knows (e.g. order references). The replacement can refer to the capture groups. [ORD-12345]->[ORD-XXXXX]
+pub struct RegexReplaceTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    /// none when the pattern is invalid - the values are then kept as they are
+    regex: Option<Regex>,
+    replacement: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct RegexReplaceOptions {
+    pub pattern: String,
+    /// replacement of each match - `$1` (or `${1}` when followed by a letter or a digit) is the first capture group
+    pub replacement: String,
+}
+
+impl RegexReplaceTransformer {
+    pub fn new<S>(
+        database_name: S,
+        table_name: S,
+        column_name: S,
+        options: RegexReplaceOptions,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        let database_name = database_name.into();
+        let table_name = table_name.into();
+        let column_name = column_name.into();
+
+        let regex = match Regex::new(options.pattern.as_str()) {
+            Ok(regex) => Some(regex),
+            Err(err) => {
+                warn!(
+                    "regex-replace transformer of {}.{}.{} keeps the values unchanged - invalid pattern: {}",
+                    database_name, table_name, column_name, err
+                );
+                None
+            }
+        };
+
+        RegexReplaceTransformer {
+            database_name,
+            table_name,
+            column_name,
+            regex,
+            replacement: options.replacement,
+        }
+    }
+}
+
+impl Default for RegexReplaceTransformer {
+    fn default() -> Self {
+        RegexReplaceTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            regex: None,
+            replacement: String::default(),
+        }
+    }
+}
+
+impl Transformer for RegexReplaceTransformer {
+    fn id(&self) -> &str {
+        "regex-replace"
+    }
+
+    fn description(&self) -> &str {
+        "Replace the parts matching a pattern, with capture groups (string only). [ORD-12345]->[ORD-XXXXX]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        match (column, &self.regex) {
+            (Column::StringValue(column_name, value), Some(regex)) => {
+                let new_value = regex.replace_all(value.as_str(), self.replacement.as_str());
+                Column::StringValue(column_name, new_value.into_owned())
+            }
+            (column, _) => column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{RegexReplaceOptions, RegexReplaceTransformer};
+
+    fn get_transformer(pattern: &str, replacement: &str) -> RegexReplaceTransformer {
+        RegexReplaceTransformer::new(
+            "github",
+            "orders",
+            "reference",
+            RegexReplaceOptions {
+                pattern: pattern.to_string(),
+                replacement: replacement.to_string(),
+            },
+        )
+    }
+
+    fn transform(transformer: &RegexReplaceTransformer, value: &str) -> String {
+        let column = Column::StringValue("reference".to_string(), value.to_string());
+        let transformed_column = transformer.transform(column);
+        transformed_column.string_value().unwrap().to_string()
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = get_transformer(r"\d", "X");
+        let column = Column::NumberValue("reference".to_string(), 12345);
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.number_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 12345)
+    }
+
+    #[test]
+    fn transform_with_capture_groups() {
+        let transformer = get_transformer(r"^(ORD)-(\d{2})\d+$", "${1}-${2}000");
+        assert_eq!(transform(&transformer, "ORD-12345"), "ORD-12000");
+
+        let transformer = get_transformer(r"(\w+)@(\w+)", "$2 at $1");
+        assert_eq!(
+            transform(&transformer, "contact: octocat@github"),
+            "contact: github at octocat"
+        );
+
+        // all the matches are replaced
+        let transformer = get_transformer(r"\d", "X");
+        assert_eq!(transform(&transformer, "ORD-12345"), "ORD-XXXXX");
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_non_matching_value() {
+        let transformer = get_transformer(r"^ORD-\d+$", "ORD-00000");
+        assert_eq!(transform(&transformer, "INV-12345"), "INV-12345");
+        assert_eq!(transform(&transformer, ""), "");
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_invalid_pattern() {
+        let transformer = get_transformer(r"ORD-(\d+", "ORD-$1");
+        assert_eq!(transform(&transformer, "ORD-12345"), "ORD-12345");
+    }
+}
@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+/// This struct is dedicated to rounding a float to a few decimals (e.g. to blur a latitude or a longitude).
+pub struct RoundTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    options: RoundOptions,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy, Default)]
+pub struct RoundOptions {
+    /// number of decimals to keep (e.g. 2 decimals of a coordinate are about 1 km)
+    pub decimals: u8,
+}
+
+impl RoundTransformer {
+    pub fn new<S>(database_name: S, table_name: S, column_name: S, options: RoundOptions) -> Self
+    where
+        S: Into<String>,
+    {
+        RoundTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            options,
+        }
+    }
+
+    fn round(&self, value: f64) -> f64 {
+        let factor = 10f64.powi(self.options.decimals as i32);
+        let rounded_value = (value * factor).round() / factor;
+
+        // a value too large for the factor is already less precise than the decimals
+        match rounded_value.is_finite() {
+            true => rounded_value,
+            false => value,
+        }
+    }
+}
+
+impl Default for RoundTransformer {
+    fn default() -> Self {
+        RoundTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            options: RoundOptions::default(),
+        }
+    }
+}
+
+impl Transformer for RoundTransformer {
+    fn id(&self) -> &str {
+        "round"
+    }
+
+    fn description(&self) -> &str {
+        "Round a number to a number of decimals (float only). [50.637188]->[50.64]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        match column {
+            Column::FloatNumberValue(column_name, value) => {
+                Column::FloatNumberValue(column_name, self.round(value))
+            }
+            column => column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{RoundOptions, RoundTransformer};
+
+    fn transform(value: f64, decimals: u8) -> f64 {
+        let transformer =
+            RoundTransformer::new("github", "stores", "latitude", RoundOptions { decimals });
+        let column = Column::FloatNumberValue("latitude".to_string(), value);
+        let transformed_column = transformer.transform(column);
+        *transformed_column.float_value().unwrap()
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = RoundTransformer::default();
+        let column = Column::NumberValue("latitude".to_string(), 42);
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.number_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 42)
+    }
+
+    #[test]
+    fn transform_rounds_to_2_decimals() {
+        assert_eq!(transform(50.637188, 2), 50.64);
+        assert_eq!(transform(3.063352, 2), 3.06);
+        assert_eq!(transform(-73.985428, 2), -73.99);
+        assert_eq!(transform(-0.001, 2), -0.0);
+    }
+
+    #[test]
+    fn transform_rounds_to_4_decimals() {
+        assert_eq!(transform(50.637188, 4), 50.6372);
+        assert_eq!(transform(40.748817, 4), 40.7488);
+        assert_eq!(transform(-73.985428, 4), -73.9854);
+        assert_eq!(transform(-12.5, 4), -12.5);
+    }
+
+    #[test]
+    fn transform_rounds_to_an_integer() {
+        assert_eq!(transform(50.637188, 0), 51.0);
+        assert_eq!(transform(-73.485428, 0), -73.0);
+    }
+}
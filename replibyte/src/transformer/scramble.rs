@@ -0,0 +1,219 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::transformer::Transformer;
+use crate::types::Column;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+/// This struct is dedicated to shuffling the characters of a string.
+/// The shuffle is seeded with the original value, so the same input always gives the same output.
+pub struct ScrambleTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    options: ScrambleOptions,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy, Default)]
+pub struct ScrambleOptions {
+    /// share of the distinct values which are scrambled, from 0.0 to 1.0 (default to 1.0) - the others are kept
+    pub coverage: Option<f64>,
+}
+
+impl ScrambleTransformer {
+    pub fn new<S>(database_name: S, table_name: S, column_name: S, options: ScrambleOptions) -> Self
+    where
+        S: Into<String>,
+    {
+        ScrambleTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            options,
+        }
+    }
+
+    /// the hash of the value decides, so a value is either always scrambled or always kept
+    fn is_covered(&self, hash: u64) -> bool {
+        match self.options.coverage {
+            Some(coverage) => (hash as f64 / u64::MAX as f64) < coverage,
+            None => true,
+        }
+    }
+}
+
+impl Default for ScrambleTransformer {
+    fn default() -> Self {
+        ScrambleTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            options: ScrambleOptions::default(),
+        }
+    }
+}
+
+impl Transformer for ScrambleTransformer {
+    fn id(&self) -> &str {
+        "scramble"
+    }
+
+    fn description(&self) -> &str {
+        "Shuffle the characters but keep the same length and character set (string only). [ABC123]->[2CA31B]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        match column {
+            Column::StringValue(column_name, value) => {
+                let mut hasher = DefaultHasher::new();
+                value.hash(&mut hasher);
+                let hash = hasher.finish();
+
+                if !self.is_covered(hash) {
+                    return Column::StringValue(column_name, value);
+                }
+
+                let mut random = StdRng::seed_from_u64(hash);
+
+                let mut chars = value.chars().collect::<Vec<_>>();
+                chars.shuffle(&mut random);
+
+                Column::StringValue(column_name, chars.into_iter().collect::<String>())
+            }
+            column => column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{ScrambleOptions, ScrambleTransformer};
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = get_transformer();
+        let column = Column::NumberValue("code".to_string(), 1234);
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.number_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 1234)
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_empty_string_value() {
+        let transformer = get_transformer();
+        let column = Column::StringValue("code".to_string(), "".to_string());
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert_eq!(transformed_value, "")
+    }
+
+    #[test]
+    fn transform_scramble_keeps_the_same_characters() {
+        let value = "ABCD-1234-efgh";
+        let transformer = get_transformer();
+        let column = Column::StringValue("code".to_string(), value.to_string());
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert_ne!(transformed_value, value);
+
+        let mut expected_chars = value.chars().collect::<Vec<_>>();
+        let mut transformed_chars = transformed_value.chars().collect::<Vec<_>>();
+        expected_chars.sort();
+        transformed_chars.sort();
+
+        assert_eq!(transformed_chars, expected_chars);
+    }
+
+    #[test]
+    fn transform_scramble_is_deterministic() {
+        let transformer = get_transformer();
+        let column = Column::StringValue("code".to_string(), "ABCD-1234-efgh".to_string());
+        let first_value = transformer.transform(column.clone());
+        let second_value = transformer.transform(column);
+
+        assert_eq!(
+            first_value.string_value().unwrap(),
+            second_value.string_value().unwrap()
+        );
+    }
+
+    #[test]
+    fn transform_scramble_only_the_covered_values() {
+        let transformer = ScrambleTransformer::new(
+            "github",
+            "users",
+            "code",
+            ScrambleOptions {
+                coverage: Some(0.5),
+            },
+        );
+
+        let values = (0..1000)
+            .map(|idx| format!("ABCD-{:04}-efgh", idx))
+            .collect::<Vec<_>>();
+
+        let mut covered_values = 0;
+        for value in &values {
+            let column = Column::StringValue("code".to_string(), value.to_string());
+            let first_value = transformer.transform(column.clone());
+            let second_value = transformer.transform(column);
+
+            // a value is always treated the same way
+            assert_eq!(
+                first_value.string_value().unwrap(),
+                second_value.string_value().unwrap()
+            );
+
+            if first_value.string_value().unwrap() != value {
+                covered_values += 1;
+            }
+        }
+
+        assert!(
+            (400..=600).contains(&covered_values),
+            "{} values scrambled out of 1000",
+            covered_values
+        );
+    }
+
+    #[test]
+    fn transform_scramble_nothing_without_coverage() {
+        let transformer = ScrambleTransformer::new(
+            "github",
+            "users",
+            "code",
+            ScrambleOptions {
+                coverage: Some(0.0),
+            },
+        );
+
+        let column = Column::StringValue("code".to_string(), "ABCD-1234-efgh".to_string());
+        let transformed_column = transformer.transform(column);
+
+        assert_eq!(transformed_column.string_value().unwrap(), "ABCD-1234-efgh");
+    }
+
+    fn get_transformer() -> ScrambleTransformer {
+        ScrambleTransformer::new("github", "users", "code", ScrambleOptions::default())
+    }
+}
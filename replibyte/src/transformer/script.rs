@@ -0,0 +1,181 @@
+use mlua::{Lua, RegistryKey};
+use serde::{Deserialize, Serialize};
+
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+/// This struct is dedicated to transforming a string with a user-supplied script.
+/// The script reads the original value from the `value` global and returns the new value.
+pub struct ScriptTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    lua: Lua,
+    script: RegistryKey,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct ScriptOptions {
+    pub engine: ScriptEngine,
+    pub script: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub enum ScriptEngine {
+    #[serde(rename = "lua")]
+    Lua,
+}
+
+impl ScriptTransformer {
+    pub fn new<S>(
+        database_name: S,
+        table_name: S,
+        column_name: S,
+        options: ScriptOptions,
+    ) -> Result<Self, mlua::Error>
+    where
+        S: Into<String>,
+    {
+        let lua = match options.engine {
+            ScriptEngine::Lua => Lua::new(),
+        };
+
+        // compile the script once, it is executed for each value
+        let script = lua.load(options.script.as_str()).into_function()?;
+        let script = lua.create_registry_value(script)?;
+
+        Ok(ScriptTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            lua,
+            script,
+        })
+    }
+
+    fn call_script(&self, value: String) -> Result<String, mlua::Error> {
+        self.lua.globals().set("value", value)?;
+        let script: mlua::Function = self.lua.registry_value(&self.script)?;
+        script.call::<_, String>(())
+    }
+}
+
+impl Default for ScriptTransformer {
+    fn default() -> Self {
+        ScriptTransformer::new(
+            "",
+            "",
+            "",
+            ScriptOptions {
+                engine: ScriptEngine::Lua,
+                script: "return value".to_string(),
+            },
+        )
+        .unwrap()
+    }
+}
+
+impl Transformer for ScriptTransformer {
+    fn id(&self) -> &str {
+        "script"
+    }
+
+    fn description(&self) -> &str {
+        "Transform the value with a Lua script (string only). [john]->[JOHN] with 'return string.upper(value)'"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        match column {
+            Column::StringValue(column_name, value) => {
+                let new_value = match self.call_script(value) {
+                    Ok(new_value) => new_value,
+                    Err(err) => {
+                        // The script which the user provided failed
+                        panic!(
+                            "Script transformer failed on column {}: {}",
+                            self.database_and_table_and_column_name(),
+                            err
+                        );
+                    }
+                };
+
+                Column::StringValue(column_name, new_value)
+            }
+            column => column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{ScriptEngine, ScriptOptions, ScriptTransformer};
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = get_transformer("return string.upper(value)");
+        let column = Column::NumberValue("name".to_string(), 42);
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.number_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 42)
+    }
+
+    #[test]
+    fn transform_with_uppercase_script() {
+        let transformer = get_transformer("return string.upper(value)");
+        let column = Column::StringValue("name".to_string(), "john doe".to_string());
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert_eq!(transformed_value, "JOHN DOE")
+    }
+
+    #[test]
+    fn new_fails_with_an_invalid_script() {
+        assert!(ScriptTransformer::new(
+            "github",
+            "users",
+            "name",
+            ScriptOptions {
+                engine: ScriptEngine::Lua,
+                script: "return (".to_string(),
+            },
+        )
+        .is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "1:")]
+    fn transform_fails_with_the_script_error_line() {
+        let transformer = get_transformer("error('invalid value')");
+        let column = Column::StringValue("name".to_string(), "john doe".to_string());
+        let _ = transformer.transform(column);
+    }
+
+    fn get_transformer(script: &str) -> ScriptTransformer {
+        ScriptTransformer::new(
+            "github",
+            "users",
+            "name",
+            ScriptOptions {
+                engine: ScriptEngine::Lua,
+                script: script.to_string(),
+            },
+        )
+        .unwrap()
+    }
+}
@@ -0,0 +1,352 @@
+use std::cell::Cell;
+use std::io::Error;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use sha2::{Digest, Sha256};
+
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+/// increment of the state between two generators of the same value (the golden ratio, as in SplitMix64)
+const STATE_INCREMENT: u64 = 0x9E37_79B9_7F4A_7C15;
+
+thread_local! {
+    /// state of the random number generators of the value being transformed - none when the dump is not seeded
+    static SEEDED_STATE: Cell<Option<u64>> = const { Cell::new(None) };
+    /// salt mixed into the seeded states - none for the rows of the dump
+    static SEED_SALT: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+/// Random number generator of the transformers. While a seeded transformer transforms a value, it is derived from the
+/// seed and the original value, so the same value always gives the same output - it is random otherwise.
+pub fn rng() -> StdRng {
+    SEEDED_STATE.with(|state| match state.get() {
+        Some(value) => {
+            // each generator of the same value is different, in the same order on every run
+            let next_value = value.wrapping_add(STATE_INCREMENT);
+            state.set(Some(next_value));
+            StdRng::seed_from_u64(next_value)
+        }
+        None => StdRng::from_rng(rand::thread_rng()).unwrap(),
+    })
+}
+
+/// This struct is dedicated to making the wrapped transformer deterministic: the random number generators it gets
+/// from [rng] are derived from the seed and the original value.
+pub struct SeededTransformer {
+    transformer: Box<dyn Transformer>,
+    seed: u64,
+}
+
+/// Wrap the transformers to derive the values they generate from `seed` and the original values.
+pub fn seed_transformers(
+    transformers: Vec<Box<dyn Transformer>>,
+    seed: u64,
+) -> Vec<Box<dyn Transformer>> {
+    transformers
+        .into_iter()
+        .map(|transformer| {
+            Box::new(SeededTransformer { transformer, seed }) as Box<dyn Transformer>
+        })
+        .collect()
+}
+
+impl SeededTransformer {
+    fn seeded<F>(&self, column: &Column, transform: F) -> Column
+    where
+        F: FnOnce() -> Column,
+    {
+        with_seed(Some(self.seed), column, transform)
+    }
+}
+
+/// Run `f` with the random number generators from [rng] derived from `seed` and the value of `column` - just run it
+/// when there is no seed.
+pub fn with_seed<F, T>(seed: Option<u64>, column: &Column, f: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    let seed = match seed {
+        Some(seed) => seed,
+        None => return f(),
+    };
+
+    // the column name is left out, so a value gives the same output in all the columns with the same transformer.
+    // SHA-256 gives the same state on every platform and Rust version, unlike the hasher of the standard library
+    let mut hasher = Sha256::new();
+    hasher.update(seed.to_le_bytes());
+
+    match column {
+        Column::NumberValue(_, value) => hasher.update(value.to_le_bytes()),
+        Column::FloatNumberValue(_, value) => hasher.update(value.to_bits().to_le_bytes()),
+        Column::StringValue(_, value) => hasher.update(value.as_bytes()),
+        Column::CharValue(_, value) => hasher.update(value.to_string().as_bytes()),
+        Column::BooleanValue(_, value) => hasher.update([*value as u8]),
+        Column::None(_) => {}
+    }
+
+    if let Some(salt) = SEED_SALT.with(Cell::get) {
+        hasher.update(salt.to_le_bytes());
+    }
+
+    let mut seeded_state = [0u8; 8];
+    seeded_state.copy_from_slice(&hasher.finalize()[..8]);
+    let seeded_state = u64::from_le_bytes(seeded_state);
+
+    let previous_state = SEEDED_STATE.with(|state| state.replace(Some(seeded_state)));
+    let result = f();
+    SEEDED_STATE.with(|state| state.set(previous_state));
+
+    result
+}
+
+/// Run `f` with `salt` mixed into the seeded states, so the same values give other outputs than without it - e.g. for
+/// the copies of an amplified row.
+pub fn with_salt<F, T>(salt: u64, f: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    let previous_salt = SEED_SALT.with(|seed_salt| seed_salt.replace(Some(salt)));
+    let result = f();
+    SEED_SALT.with(|seed_salt| seed_salt.set(previous_salt));
+
+    result
+}
+
+impl Transformer for SeededTransformer {
+    fn id(&self) -> &str {
+        self.transformer.id()
+    }
+
+    fn description(&self) -> &str {
+        self.transformer.description()
+    }
+
+    fn database_name(&self) -> &str {
+        self.transformer.database_name()
+    }
+
+    fn table_name(&self) -> &str {
+        self.transformer.table_name()
+    }
+
+    fn column_name(&self) -> &str {
+        self.transformer.column_name()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        self.seeded(&column.clone(), || self.transformer.transform(column))
+    }
+
+    fn transform_with_row(&self, column: Column, row: &[Column]) -> Column {
+        self.seeded(&column.clone(), || {
+            self.transformer.transform_with_row(column, row)
+        })
+    }
+
+    fn validate(&self, column: &Column) -> Result<(), Error> {
+        self.transformer.validate(column)
+    }
+
+    fn needs_transformed_row(&self) -> bool {
+        self.transformer.needs_transformed_row()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::transformer::email::EmailTransformer;
+    use crate::transformer::fake::{FakeOptions, FakeTransformer};
+    use crate::transformer::first_name::FirstNameTransformer;
+    use crate::transformer::jitter::{JitterOptions, JitterTransformer};
+    use crate::transformer::lorem::{LoremOptions, LoremTransformer};
+    use crate::transformer::phone_number::PhoneNumberTransformer;
+    use crate::transformer::random::RandomTransformer;
+    use crate::transformer::random_date::{RandomDateOptions, RandomDateTransformer};
+    use crate::transformer::unique::UniqueTransformer;
+    use crate::transformer::vat::{VatOptions, VatTransformer};
+    use crate::transformer::Transformer;
+    use crate::types::Column;
+
+    use super::seed_transformers;
+
+    fn transformers(seed: u64) -> Vec<Box<dyn Transformer>> {
+        seed_transformers(
+            vec![
+                Box::new(EmailTransformer::new(
+                    "public",
+                    "customers",
+                    "email",
+                    Default::default(),
+                )),
+                Box::new(FirstNameTransformer::new(
+                    "public",
+                    "customers",
+                    "first_name",
+                )),
+                Box::new(PhoneNumberTransformer::new(
+                    "public",
+                    "customers",
+                    "phone",
+                    Default::default(),
+                )),
+                Box::new(RandomTransformer::new("public", "customers", "last_name")),
+            ],
+            seed,
+        )
+    }
+
+    fn transform(transformers: &[Box<dyn Transformer>], values: &[&str]) -> Vec<String> {
+        transformers
+            .iter()
+            .zip(values)
+            .map(|(transformer, value)| {
+                let column = Column::StringValue("column".to_string(), value.to_string());
+                transformer
+                    .transform(column)
+                    .string_value()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn transform_the_same_value_to_the_same_output() {
+        let values = ["maria.anders@gmail.com", "Maria", "030-0074321", "Anders"];
+
+        let first_run = transform(&transformers(42), &values);
+        let second_run = transform(&transformers(42), &values);
+        assert_eq!(first_run, second_run);
+        assert_eq!(transform(&transformers(42), &values), first_run);
+
+        // the values are still transformed
+        assert_ne!(first_run[0], values[0]);
+        assert_eq!(first_run[3].len(), values[3].len());
+
+        // another seed gives other values
+        let values = ["ana@factchecker.com", "Ana", "(5) 555-4729", "Trujillo"];
+        let other_runs = (0..5)
+            .map(|seed| transform(&transformers(seed), &values))
+            .collect::<Vec<_>>();
+        assert!(other_runs.iter().any(|run| *run != other_runs[0]));
+    }
+
+    #[test]
+    fn transform_the_same_value_to_the_same_output_with_the_generators() {
+        let transformers = |seed| {
+            seed_transformers(
+                vec![
+                    Box::new(
+                        FakeTransformer::new(
+                            "public",
+                            "customers",
+                            "city",
+                            FakeOptions {
+                                kind: "address.city".to_string(),
+                            },
+                        )
+                        .unwrap(),
+                    ),
+                    Box::new(LoremTransformer::new(
+                        "public",
+                        "customers",
+                        "notes",
+                        LoremOptions {
+                            match_word_count: None,
+                        },
+                    )),
+                    Box::new(
+                        RandomDateTransformer::new(
+                            "public",
+                            "customers",
+                            "birth_date",
+                            RandomDateOptions {
+                                min: None,
+                                max: Some("2000-01-01".to_string()),
+                                format: None,
+                            },
+                        )
+                        .unwrap(),
+                    ),
+                    Box::new(
+                        VatTransformer::new(
+                            "public",
+                            "customers",
+                            "vat_number",
+                            VatOptions {
+                                country_code: "DE".to_string(),
+                            },
+                        )
+                        .unwrap(),
+                    ),
+                ],
+                seed,
+            )
+        };
+
+        let values = [
+            "Berlin",
+            "Sales Representative",
+            "1968-12-08",
+            "DE123456789",
+        ];
+        let first_run = transform(&transformers(42), &values);
+        assert_eq!(transform(&transformers(42), &values), first_run);
+
+        let jitter = seed_transformers(
+            vec![Box::new(
+                JitterTransformer::new(
+                    "public",
+                    "products",
+                    "unit_price",
+                    JitterOptions { percent: 10.0 },
+                )
+                .unwrap(),
+            )],
+            42,
+        );
+        let unit_price = || Column::FloatNumberValue("unit_price".to_string(), 18.0);
+        assert_eq!(
+            jitter[0].transform(unit_price()).float_value(),
+            jitter[0].transform(unit_price()).float_value()
+        );
+    }
+
+    #[test]
+    fn transform_the_same_value_to_the_same_unique_output() {
+        let transformer = seed_transformers(
+            vec![Box::new(UniqueTransformer::new(Box::new(
+                RandomTransformer::new("public", "customers", "id"),
+            )))],
+            42,
+        );
+
+        // two runs give the same values, even when a value has to be generated again to be unique
+        let run = |transformer: &dyn Transformer| {
+            ["A", "A", "B"]
+                .iter()
+                .map(|value| {
+                    let column = Column::StringValue("id".to_string(), value.to_string());
+                    transformer
+                        .transform(column)
+                        .string_value()
+                        .unwrap()
+                        .to_string()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let first_run = run(transformer[0].as_ref());
+        assert_ne!(first_run[0], first_run[1]);
+
+        let transformer = seed_transformers(
+            vec![Box::new(UniqueTransformer::new(Box::new(
+                RandomTransformer::new("public", "customers", "id"),
+            )))],
+            42,
+        );
+        assert_eq!(run(transformer[0].as_ref()), first_run);
+    }
+}
@@ -0,0 +1,217 @@
+use chrono::{Duration, NaiveDate};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::transformer::seeded::rng;
+use crate::transformer::{hash_row, Transformer};
+use crate::types::Column;
+
+/// This struct is dedicated to shifting a date by a number of days picked for the whole row.
+/// All the shift-date transformers of a row use the same offset, so the order and the gaps between the dates
+/// of the row (e.g. `created_at` <= `updated_at`) are kept.
+pub struct ShiftDateTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    options: ShiftDateOptions,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct ShiftDateOptions {
+    /// maximum number of days the dates are shifted by, backward or forward
+    pub max_days: u32,
+    /// column identifying the row (e.g. id) - the whole row is used if not set
+    pub key_column: Option<String>,
+}
+
+impl ShiftDateTransformer {
+    pub fn new<S>(
+        database_name: S,
+        table_name: S,
+        column_name: S,
+        options: ShiftDateOptions,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        ShiftDateTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            options,
+        }
+    }
+
+    /// number of days between -max_days and max_days, never 0 so the dates are always changed
+    fn offset(&self, hash: u64) -> i64 {
+        if self.options.max_days == 0 {
+            return 0;
+        }
+
+        let days = (hash >> 1) % self.options.max_days as u64 + 1;
+        match hash % 2 {
+            0 => days as i64,
+            _ => -(days as i64),
+        }
+    }
+
+    fn transform_with_offset(&self, column: Column, offset: i64) -> Column {
+        match column {
+            Column::StringValue(column_name, value) => {
+                // only ISO 8601 dates and timestamps are shifted (e.g. 1987-05-12 or 1987-05-12T08:30:00Z)
+                let date = match value
+                    .get(..10)
+                    .map(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d"))
+                {
+                    Some(Ok(date)) => date,
+                    _ => return Column::StringValue(column_name, value),
+                };
+
+                // the time and the time zone are kept as they are
+                let shifted_date = date
+                    .checked_add_signed(Duration::days(offset))
+                    .unwrap_or(date);
+
+                Column::StringValue(
+                    column_name,
+                    format!("{}{}", shifted_date.format("%Y-%m-%d"), &value[10..]),
+                )
+            }
+            column => column,
+        }
+    }
+}
+
+impl Default for ShiftDateTransformer {
+    fn default() -> Self {
+        ShiftDateTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            options: ShiftDateOptions {
+                max_days: 30,
+                key_column: None,
+            },
+        }
+    }
+}
+
+impl Transformer for ShiftDateTransformer {
+    fn id(&self) -> &str {
+        "shift-date"
+    }
+
+    fn description(&self) -> &str {
+        "Shift the dates of a row by the same number of days (string only). [2022-01-10, 2022-01-12]->[2021-12-25, 2021-12-27]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        // without the row, the offset can't be shared with the other dates of the row
+        let offset = self.offset(rng().gen());
+        self.transform_with_offset(column, offset)
+    }
+
+    fn transform_with_row(&self, column: Column, row: &[Column]) -> Column {
+        let offset = self.offset(hash_row(row, self.options.key_column.as_deref()));
+        self.transform_with_offset(column, offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDateTime;
+
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{ShiftDateOptions, ShiftDateTransformer};
+
+    fn get_transformer(column_name: &str, max_days: u32) -> ShiftDateTransformer {
+        ShiftDateTransformer::new(
+            "github",
+            "orders",
+            column_name,
+            ShiftDateOptions {
+                max_days,
+                key_column: None,
+            },
+        )
+    }
+
+    fn get_row(id: i128, created_at: &str, updated_at: &str) -> Vec<Column> {
+        vec![
+            Column::NumberValue("id".to_string(), id),
+            Column::StringValue("created_at".to_string(), created_at.to_string()),
+            Column::StringValue("updated_at".to_string(), updated_at.to_string()),
+        ]
+    }
+
+    fn transform(row: &[Column], column_idx: usize, max_days: u32) -> String {
+        let column = row[column_idx].clone();
+        let transformer = get_transformer(column.name(), max_days);
+        let transformed_column = transformer.transform_with_row(column, row);
+        transformed_column.string_value().unwrap().to_string()
+    }
+
+    fn parse(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = ShiftDateTransformer::default();
+        let column = Column::NumberValue("created_at".to_string(), 42);
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.number_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 42)
+    }
+
+    #[test]
+    fn transform_keeps_the_order_of_the_dates_of_a_row() {
+        for id in 0..100 {
+            let row = get_row(id, "2022-01-10 08:30:00", "2022-01-12 17:45:10");
+
+            let created_at = transform(&row, 1, 365);
+            let updated_at = transform(&row, 2, 365);
+            assert_ne!(created_at, "2022-01-10 08:30:00");
+
+            let created_at = parse(created_at.as_str());
+            let updated_at = parse(updated_at.as_str());
+            assert!(updated_at >= created_at);
+            assert_eq!(
+                updated_at - created_at,
+                parse("2022-01-12 17:45:10") - parse("2022-01-10 08:30:00")
+            );
+            assert!((created_at - parse("2022-01-10 08:30:00")).num_days().abs() <= 365);
+        }
+    }
+
+    #[test]
+    fn transform_keeps_the_time_and_the_time_zone() {
+        let row = get_row(1, "2022-01-10T08:30:00+02:00", "2022-01-10");
+
+        let created_at = transform(&row, 1, 10);
+        assert_eq!(&created_at[10..], "T08:30:00+02:00");
+        assert_eq!(transform(&row, 2, 10), &created_at[..10]);
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_another_format() {
+        let row = get_row(1, "10/01/2022", "");
+
+        assert_eq!(transform(&row, 1, 10), "10/01/2022");
+        assert_eq!(transform(&row, 2, 10), "");
+    }
+}
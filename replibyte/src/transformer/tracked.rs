@@ -0,0 +1,123 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+/// This struct is dedicated to recording if the wrapped transformer has been applied at least once.
+pub struct TrackedTransformer {
+    transformer: Box<dyn Transformer>,
+    applied: Rc<Cell<bool>>,
+}
+
+/// Keep track of the transformers applied during a dump.
+pub struct TransformersUsage {
+    transformers: Vec<(String, Rc<Cell<bool>>)>,
+}
+
+impl TransformersUsage {
+    /// name of the transformers which never matched a column
+    pub fn unused_transformers(&self) -> Vec<&str> {
+        self.transformers
+            .iter()
+            .filter(|(_, applied)| !applied.get())
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+}
+
+/// Wrap the transformers to know which ones are applied while reading the source.
+pub fn track(
+    transformers: Vec<Box<dyn Transformer>>,
+) -> (Vec<Box<dyn Transformer>>, TransformersUsage) {
+    let mut usage = TransformersUsage {
+        transformers: Vec::with_capacity(transformers.len()),
+    };
+
+    let transformers = transformers
+        .into_iter()
+        .map(|transformer| {
+            let applied = Rc::new(Cell::new(false));
+            usage.transformers.push((
+                format!(
+                    "{} ({})",
+                    transformer.database_and_table_and_column_name(),
+                    transformer.id()
+                ),
+                applied.clone(),
+            ));
+
+            Box::new(TrackedTransformer {
+                transformer,
+                applied,
+            }) as Box<dyn Transformer>
+        })
+        .collect::<Vec<_>>();
+
+    (transformers, usage)
+}
+
+impl Transformer for TrackedTransformer {
+    fn id(&self) -> &str {
+        self.transformer.id()
+    }
+
+    fn description(&self) -> &str {
+        self.transformer.description()
+    }
+
+    fn database_name(&self) -> &str {
+        self.transformer.database_name()
+    }
+
+    fn table_name(&self) -> &str {
+        self.transformer.table_name()
+    }
+
+    fn column_name(&self) -> &str {
+        self.transformer.column_name()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        self.applied.set(true);
+        self.transformer.transform(column)
+    }
+
+    fn transform_with_row(&self, column: Column, row: &[Column]) -> Column {
+        self.applied.set(true);
+        self.transformer.transform_with_row(column, row)
+    }
+
+    fn needs_transformed_row(&self) -> bool {
+        self.transformer.needs_transformed_row()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::transformer::random::RandomTransformer;
+    use crate::transformer::transient::TransientTransformer;
+    use crate::transformer::Transformer;
+    use crate::types::Column;
+
+    use super::track;
+
+    #[test]
+    fn track_applied_transformers() {
+        let t1: Box<dyn Transformer> =
+            Box::new(RandomTransformer::new("public", "employees", "last_name"));
+        let t2: Box<dyn Transformer> =
+            Box::new(TransientTransformer::new("public", "employees", "emial"));
+
+        let (transformers, usage) = track(vec![t1, t2]);
+        assert_eq!(usage.unused_transformers().len(), 2);
+
+        let column = Column::StringValue("last_name".to_string(), "Davolio".to_string());
+        let _ = transformers[0].transform(column);
+
+        assert_eq!(
+            usage.unused_transformers(),
+            vec!["public.employees.emial (transient)"]
+        );
+    }
+}
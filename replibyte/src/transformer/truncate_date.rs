@@ -0,0 +1,214 @@
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+/// This struct is dedicated to coarsening a date to the first day of its year or month.
+pub struct TruncateDateTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    options: DateOptions,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+pub struct DateOptions {
+    pub granularity: DateGranularity,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum DateGranularity {
+    /// January 1st of the year (e.g. 1987-01-01)
+    Year,
+    /// first day of the month (e.g. 1987-05-01)
+    Month,
+    /// same day, midnight for a timestamp (e.g. 1987-05-12 00:00:00)
+    Day,
+}
+
+impl TruncateDateTransformer {
+    pub fn new<S>(database_name: S, table_name: S, column_name: S, options: DateOptions) -> Self
+    where
+        S: Into<String>,
+    {
+        TruncateDateTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            options,
+        }
+    }
+
+    fn truncate(&self, date: NaiveDate) -> NaiveDate {
+        let (month, day) = match self.options.granularity {
+            DateGranularity::Year => (1, 1),
+            DateGranularity::Month => (date.month(), 1),
+            DateGranularity::Day => (date.month(), date.day()),
+        };
+
+        NaiveDate::from_ymd_opt(date.year(), month, day).unwrap_or(date)
+    }
+}
+
+impl Default for TruncateDateTransformer {
+    fn default() -> Self {
+        TruncateDateTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            options: DateOptions {
+                granularity: DateGranularity::Month,
+            },
+        }
+    }
+}
+
+/// set the time of a timestamp (e.g. ` 12:34:56.789+02`) to midnight, keeping its format and time zone
+fn truncate_time(time: &str) -> String {
+    let mut is_time_zone = false;
+
+    time.chars()
+        .enumerate()
+        .map(|(idx, c)| {
+            if idx > 0 && matches!(c, '+' | '-' | 'Z') {
+                is_time_zone = true;
+            }
+
+            match !is_time_zone && c.is_ascii_digit() {
+                true => '0',
+                false => c,
+            }
+        })
+        .collect()
+}
+
+impl Transformer for TruncateDateTransformer {
+    fn id(&self) -> &str {
+        "truncate-date"
+    }
+
+    fn description(&self) -> &str {
+        "Truncate a date to its year, month or day (string only). [1987-05-12 08:30:00]->[1987-05-01 00:00:00]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        match column {
+            Column::StringValue(column_name, value) => {
+                // only ISO 8601 dates and timestamps are truncated (e.g. 1987-05-12 or 1987-05-12T08:30:00Z)
+                let date = match value
+                    .get(..10)
+                    .map(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d"))
+                {
+                    Some(Ok(date)) => date,
+                    _ => return Column::StringValue(column_name, value),
+                };
+
+                Column::StringValue(
+                    column_name,
+                    format!(
+                        "{}{}",
+                        self.truncate(date).format("%Y-%m-%d"),
+                        truncate_time(&value[10..])
+                    ),
+                )
+            }
+            column => column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{DateGranularity, DateOptions, TruncateDateTransformer};
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = get_transformer(DateGranularity::Year);
+        let column = Column::NumberValue("created_at".to_string(), 42);
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.number_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 42)
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_a_string_which_is_not_a_date() {
+        let transformer = get_transformer(DateGranularity::Year);
+        let column = Column::StringValue("created_at".to_string(), "yesterday".to_string());
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.string_value().unwrap();
+
+        assert_eq!(transformed_value, "yesterday")
+    }
+
+    #[test]
+    fn transform_truncates_to_the_year() {
+        let transformer = get_transformer(DateGranularity::Year);
+
+        for (value, expected_value) in [
+            ("1987-05-12", "1987-01-01"),
+            ("1987-05-12 08:30:00", "1987-01-01 00:00:00"),
+        ] {
+            let column = Column::StringValue("created_at".to_string(), value.to_string());
+            let transformed_column = transformer.transform(column);
+
+            assert_eq!(transformed_column.string_value().unwrap(), expected_value);
+        }
+    }
+
+    #[test]
+    fn transform_truncates_to_the_month() {
+        let transformer = get_transformer(DateGranularity::Month);
+
+        for (value, expected_value) in [
+            ("1987-05-12", "1987-05-01"),
+            ("1987-05-12T08:30:00.123Z", "1987-05-01T00:00:00.000Z"),
+            ("1987-05-12 08:30:00+02", "1987-05-01 00:00:00+02"),
+        ] {
+            let column = Column::StringValue("created_at".to_string(), value.to_string());
+            let transformed_column = transformer.transform(column);
+
+            assert_eq!(transformed_column.string_value().unwrap(), expected_value);
+        }
+    }
+
+    #[test]
+    fn transform_truncates_to_the_day() {
+        let transformer = get_transformer(DateGranularity::Day);
+        let column = Column::StringValue(
+            "created_at".to_string(),
+            "1987-05-12 08:30:00-05:00".to_string(),
+        );
+        let transformed_column = transformer.transform(column);
+
+        assert_eq!(
+            transformed_column.string_value().unwrap(),
+            "1987-05-12 00:00:00-05:00"
+        );
+    }
+
+    fn get_transformer(granularity: DateGranularity) -> TruncateDateTransformer {
+        TruncateDateTransformer::new(
+            "github",
+            "orders",
+            "created_at",
+            DateOptions { granularity },
+        )
+    }
+}
@@ -0,0 +1,150 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+/// number of attempts to generate a new value before suffixing it to make it unique
+const MAX_ATTEMPTS: usize = 10;
+
+/// This struct is dedicated to guaranteeing that the wrapped transformer never generates the same string twice.
+/// The generated values are kept for the whole dump, so it is suited to columns with a UNIQUE constraint.
+pub struct UniqueTransformer {
+    transformer: Box<dyn Transformer>,
+    values: RefCell<HashSet<String>>,
+}
+
+impl UniqueTransformer {
+    pub fn new(transformer: Box<dyn Transformer>) -> Self {
+        UniqueTransformer {
+            transformer,
+            values: RefCell::new(HashSet::new()),
+        }
+    }
+
+    fn unique<F>(&self, column: Column, transform: F) -> Column
+    where
+        F: Fn(Column) -> Column,
+    {
+        let mut values = self.values.borrow_mut();
+
+        for _ in 0..MAX_ATTEMPTS {
+            match transform(column.clone()) {
+                Column::StringValue(column_name, value) => {
+                    if values.insert(value.clone()) {
+                        return Column::StringValue(column_name, value);
+                    }
+                }
+                // only strings are made unique
+                transformed_column => return transformed_column,
+            }
+        }
+
+        // the transformer keeps generating existing values, suffix the last one until it is unique
+        match transform(column) {
+            Column::StringValue(column_name, value) => {
+                let mut suffix = values.len();
+                let mut new_value = format!("{}{}", value, suffix);
+
+                while !values.insert(new_value.clone()) {
+                    suffix += 1;
+                    new_value = format!("{}{}", value, suffix);
+                }
+
+                Column::StringValue(column_name, new_value)
+            }
+            transformed_column => transformed_column,
+        }
+    }
+}
+
+impl Transformer for UniqueTransformer {
+    fn id(&self) -> &str {
+        self.transformer.id()
+    }
+
+    fn description(&self) -> &str {
+        self.transformer.description()
+    }
+
+    fn database_name(&self) -> &str {
+        self.transformer.database_name()
+    }
+
+    fn table_name(&self) -> &str {
+        self.transformer.table_name()
+    }
+
+    fn column_name(&self) -> &str {
+        self.transformer.column_name()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        self.unique(column, |column| self.transformer.transform(column))
+    }
+
+    fn transform_with_row(&self, column: Column, row: &[Column]) -> Column {
+        self.unique(column, |column| {
+            self.transformer.transform_with_row(column, row)
+        })
+    }
+
+    fn needs_transformed_row(&self) -> bool {
+        self.transformer.needs_transformed_row()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::transformer::first_name::FirstNameTransformer;
+    use crate::transformer::redacted::RedactedTransformer;
+    use crate::transformer::Transformer;
+    use crate::types::Column;
+
+    use super::UniqueTransformer;
+
+    #[test]
+    fn transform_doesnt_produce_duplicates() {
+        // there are far less first names than rows
+        let transformer = UniqueTransformer::new(Box::new(FirstNameTransformer::new(
+            "github", "users", "username",
+        )));
+
+        let mut values = HashSet::new();
+        for idx in 0..5000 {
+            let column = Column::StringValue("username".to_string(), format!("user{}", idx));
+            let transformed_column = transformer.transform(column);
+            let transformed_value = transformed_column.string_value().unwrap();
+
+            assert!(values.insert(transformed_value.to_string()));
+        }
+    }
+
+    #[test]
+    fn transform_suffixes_constant_values() {
+        let transformer = UniqueTransformer::new(Box::new(RedactedTransformer::default()));
+
+        let mut values = HashSet::new();
+        for _ in 0..100 {
+            let column = Column::StringValue("username".to_string(), "john.doe".to_string());
+            let transformed_column = transformer.transform(column);
+            let transformed_value = transformed_column.string_value().unwrap();
+
+            assert!(values.insert(transformed_value.to_string()));
+        }
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = UniqueTransformer::new(Box::new(RedactedTransformer::default()));
+
+        for _ in 0..2 {
+            let column = Column::NumberValue("id".to_string(), 42);
+            let transformed_column = transformer.transform(column);
+
+            assert_eq!(transformed_column.number_value().unwrap().to_owned(), 42);
+        }
+    }
+}
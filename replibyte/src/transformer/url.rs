@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+const REDACTED_VALUE: &str = "redacted";
+
+/// This struct is dedicated to redacting the values of some query parameters of a URL.
+/// The rest of the URL (scheme, host, path, other parameters and fragment) is kept as it is.
+pub struct UrlTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    options: UrlOptions,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct UrlOptions {
+    /// names of the query parameters to redact (e.g. email, token)
+    pub redact_params: Vec<String>,
+}
+
+impl UrlTransformer {
+    pub fn new<S>(database_name: S, table_name: S, column_name: S, options: UrlOptions) -> Self
+    where
+        S: Into<String>,
+    {
+        UrlTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            options,
+        }
+    }
+
+    /// redact the listed parameters of the query string, every occurrence of a repeated parameter included
+    fn redact_query(&self, query: &str) -> String {
+        query
+            .split('&')
+            .map(|param| match param.split_once('=') {
+                Some((name, _)) if self.options.redact_params.iter().any(|x| x == name) => {
+                    format!("{}={}", name, REDACTED_VALUE)
+                }
+                _ => param.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("&")
+    }
+
+    fn redact_url(&self, url: &str) -> String {
+        let (url, fragment) = match url.split_once('#') {
+            Some((url, fragment)) => (url, Some(fragment)),
+            None => (url, None),
+        };
+
+        let mut redacted_url = match url.split_once('?') {
+            Some((path, query)) => format!("{}?{}", path, self.redact_query(query)),
+            None => url.to_string(),
+        };
+
+        if let Some(fragment) = fragment {
+            redacted_url.push('#');
+            redacted_url.push_str(fragment);
+        }
+
+        redacted_url
+    }
+}
+
+impl Default for UrlTransformer {
+    fn default() -> Self {
+        UrlTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            options: UrlOptions::default(),
+        }
+    }
+}
+
+impl Transformer for UrlTransformer {
+    fn id(&self) -> &str {
+        "url"
+    }
+
+    fn description(&self) -> &str {
+        "Redact some query parameters of a URL (string only). [/unsubscribe?email=john@gmail.com&lang=en]->[/unsubscribe?email=redacted&lang=en]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        match column {
+            Column::StringValue(column_name, value) => {
+                Column::StringValue(column_name, self.redact_url(value.as_str()))
+            }
+            column => column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{UrlOptions, UrlTransformer};
+
+    fn get_transformer() -> UrlTransformer {
+        UrlTransformer::new(
+            "github",
+            "events",
+            "url",
+            UrlOptions {
+                redact_params: vec!["email".to_string(), "token".to_string()],
+            },
+        )
+    }
+
+    fn transform(url: &str) -> String {
+        let column = Column::StringValue("url".to_string(), url.to_string());
+        let transformed_column = get_transformer().transform(column);
+        transformed_column.string_value().unwrap().to_string()
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = get_transformer();
+        let column = Column::NumberValue("url".to_string(), 42);
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.number_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 42)
+    }
+
+    #[test]
+    fn transform_redacts_the_listed_params() {
+        assert_eq!(
+            transform(
+                "https://example.com/unsubscribe?email=john%40gmail.com&lang=en&token=a1b2#footer"
+            ),
+            "https://example.com/unsubscribe?email=redacted&lang=en&token=redacted#footer"
+        );
+    }
+
+    #[test]
+    fn transform_redacts_repeated_params() {
+        assert_eq!(
+            transform("/share?email=john@gmail.com&email=jane@gmail.com&ref=mail&ref=push"),
+            "/share?email=redacted&email=redacted&ref=mail&ref=push"
+        );
+    }
+
+    #[test]
+    fn transform_keeps_the_other_params() {
+        // a parameter whose name only contains a listed one is kept
+        assert_eq!(
+            transform("https://example.com/search?q=rust&user_email=x&page=2&flag"),
+            "https://example.com/search?q=rust&user_email=x&page=2&flag"
+        );
+        assert_eq!(
+            transform("https://example.com/about"),
+            "https://example.com/about"
+        );
+    }
+}
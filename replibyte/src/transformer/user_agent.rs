@@ -0,0 +1,193 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+/// user agents of the common browsers, on desktop and mobile
+const USER_AGENTS: [&str; 16] = [
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/119.0.0.0 Safari/537.36 Edg/119.0.2151.97",
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:121.0) Gecko/20100101 Firefox/121.0",
+    "Mozilla/5.0 (Windows NT 6.1; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/109.0.0.0 Safari/537.36",
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.2 Safari/605.1.15",
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 10.15; rv:120.0) Gecko/20100101 Firefox/120.0",
+    "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+    "Mozilla/5.0 (X11; Ubuntu; Linux x86_64; rv:121.0) Gecko/20100101 Firefox/121.0",
+    "Mozilla/5.0 (iPhone; CPU iPhone OS 17_2 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.2 Mobile/15E148 Safari/604.1",
+    "Mozilla/5.0 (iPhone; CPU iPhone OS 16_6 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) CriOS/120.0.6099.119 Mobile/15E148 Safari/604.1",
+    "Mozilla/5.0 (iPad; CPU OS 17_2 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.2 Mobile/15E148 Safari/604.1",
+    "Mozilla/5.0 (Linux; Android 14; Pixel 8) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.6099.144 Mobile Safari/537.36",
+    "Mozilla/5.0 (Linux; Android 13; SM-S911B) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/119.0.0.0 Mobile Safari/537.36",
+    "Mozilla/5.0 (Linux; Android 13; SM-A536B) AppleWebKit/537.36 (KHTML, like Gecko) SamsungBrowser/23.0 Chrome/115.0.0.0 Mobile Safari/537.36",
+    "Mozilla/5.0 (Android 14; Mobile; rv:121.0) Gecko/121.0 Firefox/121.0",
+];
+
+/// This struct is dedicated to replacing a user agent by a realistic one of a common browser.
+pub struct UserAgentTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    options: UserAgentOptions,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy, Default)]
+pub struct UserAgentOptions {
+    /// always replace a user agent by the same one (default to false), e.g. to keep the sessions of a browser together
+    pub deterministic: Option<bool>,
+}
+
+impl UserAgentTransformer {
+    pub fn new<S>(
+        database_name: S,
+        table_name: S,
+        column_name: S,
+        options: UserAgentOptions,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        UserAgentTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            options,
+        }
+    }
+
+    /// another user agent of the pool than `value`
+    fn user_agent(&self, value: &str) -> &'static str {
+        let user_agents = USER_AGENTS
+            .iter()
+            .filter(|user_agent| **user_agent != value)
+            .collect::<Vec<_>>();
+
+        let idx = match self.options.deterministic.unwrap_or(false) {
+            true => {
+                let mut hasher = DefaultHasher::new();
+                value.hash(&mut hasher);
+                hasher.finish() as usize % user_agents.len()
+            }
+            false => rand::thread_rng().gen_range(0..user_agents.len()),
+        };
+
+        user_agents[idx]
+    }
+}
+
+impl Default for UserAgentTransformer {
+    fn default() -> Self {
+        UserAgentTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            options: UserAgentOptions::default(),
+        }
+    }
+}
+
+impl Transformer for UserAgentTransformer {
+    fn id(&self) -> &str {
+        "user-agent"
+    }
+
+    fn description(&self) -> &str {
+        "Generate the user agent of a common browser (string only). [curl/8.4.0]->[Mozilla/5.0 (X11; Linux x86_64) ...]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        match column {
+            Column::StringValue(column_name, value) => {
+                if value.is_empty() {
+                    return Column::StringValue(column_name, value);
+                }
+
+                let new_value = self.user_agent(value.as_str()).to_string();
+                Column::StringValue(column_name, new_value)
+            }
+            column => column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{UserAgentOptions, UserAgentTransformer, USER_AGENTS};
+
+    fn transform(transformer: &UserAgentTransformer, value: &str) -> String {
+        let column = Column::StringValue("user_agent".to_string(), value.to_string());
+        let transformed_column = transformer.transform(column);
+        transformed_column.string_value().unwrap().to_string()
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = UserAgentTransformer::default();
+        let column = Column::NumberValue("user_agent".to_string(), 42);
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.number_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 42)
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_empty_string() {
+        let transformer = UserAgentTransformer::default();
+        assert_eq!(transform(&transformer, ""), "");
+    }
+
+    #[test]
+    fn transform_to_another_user_agent_of_the_pool() {
+        let transformer = UserAgentTransformer::default();
+        let mut user_agents = HashSet::new();
+
+        for value in ["curl/8.4.0", USER_AGENTS[0]] {
+            for _ in 0..50 {
+                let user_agent = transform(&transformer, value);
+                assert!(!user_agent.is_empty());
+                assert_ne!(user_agent, value);
+                assert!(USER_AGENTS.contains(&user_agent.as_str()));
+
+                user_agents.insert(user_agent);
+            }
+        }
+
+        assert!(user_agents.len() > 1);
+    }
+
+    #[test]
+    fn transform_the_same_user_agent_to_the_same_one() {
+        let transformer = UserAgentTransformer::new(
+            "github",
+            "page_views",
+            "user_agent",
+            UserAgentOptions {
+                deterministic: Some(true),
+            },
+        );
+
+        let user_agent = transform(&transformer, "curl/8.4.0");
+        assert_ne!(user_agent, "curl/8.4.0");
+        assert_eq!(transform(&transformer, "curl/8.4.0"), user_agent);
+    }
+}
@@ -0,0 +1,232 @@
+use std::io::{Error, ErrorKind};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+/// smallest integer by default - surrogate keys usually start at 1
+const DEFAULT_MIN: i64 = 1;
+/// largest integer by default - fits a 32-bit INTEGER column
+const DEFAULT_MAX: i64 = i32::MAX as i64;
+
+/// This struct is dedicated to replacing a UUID by a stable integer surrogate, e.g. to migrate a UUID primary key to
+/// an integer one. The integer is derived from a SHA-256 of the UUID, so the same UUID always gives the same integer
+/// (from one dump to another too) and the foreign keys still match. Two UUIDs can give the same integer - the larger
+/// the bound, the rarer the collisions.
+pub struct UuidToIntTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    min: i64,
+    max: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy, Default)]
+pub struct UuidToIntOptions {
+    /// smallest generated integer (default to 1)
+    pub min: Option<i64>,
+    /// largest generated integer (default to 2147483647)
+    pub max: Option<i64>,
+}
+
+impl UuidToIntTransformer {
+    pub fn new<S>(
+        database_name: S,
+        table_name: S,
+        column_name: S,
+        options: UuidToIntOptions,
+    ) -> Result<Self, Error>
+    where
+        S: Into<String>,
+    {
+        let min = options.min.unwrap_or(DEFAULT_MIN);
+        let max = options.max.unwrap_or(DEFAULT_MAX);
+
+        if min > max {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!("min ({}) is greater than max ({})", min, max),
+            ));
+        }
+
+        Ok(UuidToIntTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            min,
+            max,
+        })
+    }
+
+    /// integer between min and max of the UUID - none if the value is not a UUID
+    fn to_int(&self, value: &str) -> Option<i64> {
+        let hex_digits = normalize_uuid(value)?;
+
+        let hash = Sha256::digest(hex_digits.as_bytes());
+        let mut bytes = [0u8; 16];
+        bytes.copy_from_slice(&hash[..16]);
+
+        let range = (self.max as i128 - self.min as i128 + 1) as u128;
+        let offset = u128::from_be_bytes(bytes) % range;
+
+        Some((self.min as i128 + offset as i128) as i64)
+    }
+}
+
+/// the 32 lowercase hex digits of a UUID, so `{550E8400-E29B-...}` and `550e8400e29b...` give the same integer
+fn normalize_uuid(value: &str) -> Option<String> {
+    let value = value.trim();
+    let value = match (value.strip_prefix('{'), value.strip_suffix('}')) {
+        (Some(_), Some(_)) => &value[1..value.len() - 1],
+        _ => value,
+    };
+
+    let hex_digits = value
+        .chars()
+        .filter(|c| *c != '-')
+        .map(|c| c.to_ascii_lowercase())
+        .collect::<String>();
+
+    match hex_digits.len() == 32 && hex_digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        true => Some(hex_digits),
+        false => None,
+    }
+}
+
+impl Default for UuidToIntTransformer {
+    fn default() -> Self {
+        UuidToIntTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            min: DEFAULT_MIN,
+            max: DEFAULT_MAX,
+        }
+    }
+}
+
+impl Transformer for UuidToIntTransformer {
+    fn id(&self) -> &str {
+        "uuid-to-int"
+    }
+
+    fn description(&self) -> &str {
+        "Replace a UUID by a stable integer (string only). [550e8400-e29b-41d4-a716-446655440000]->[1505951382]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        match column {
+            Column::StringValue(column_name, value) => match self.to_int(value.as_str()) {
+                Some(int) => Column::NumberValue(column_name, int as i128),
+                None => Column::StringValue(column_name, value),
+            },
+            column => column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{UuidToIntOptions, UuidToIntTransformer};
+
+    fn transform(transformer: &UuidToIntTransformer, value: &str) -> Column {
+        let column = Column::StringValue("user_id".to_string(), value.to_string());
+        transformer.transform(column)
+    }
+
+    fn transform_to_int(transformer: &UuidToIntTransformer, value: &str) -> i128 {
+        *transform(transformer, value).number_value().unwrap()
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = UuidToIntTransformer::default();
+        let column = Column::NumberValue("user_id".to_string(), 42);
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.number_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 42)
+    }
+
+    #[test]
+    fn transform_the_same_uuid_to_the_same_int() {
+        let transformer = UuidToIntTransformer::default();
+        let int = transform_to_int(&transformer, "550e8400-e29b-41d4-a716-446655440000");
+
+        // the integer must not change from one version to another
+        assert_eq!(int, 1505951382);
+        assert_eq!(
+            transform_to_int(&transformer, "550e8400-e29b-41d4-a716-446655440000"),
+            int
+        );
+        assert_eq!(
+            transform_to_int(&transformer, "{550E8400-E29B-41D4-A716-446655440000}"),
+            int
+        );
+        assert_eq!(
+            transform_to_int(&transformer, "550e8400e29b41d4a716446655440000"),
+            int
+        );
+        assert_ne!(
+            transform_to_int(&transformer, "6ba7b810-9dad-11d1-80b4-00c04fd430c8"),
+            int
+        );
+    }
+
+    #[test]
+    fn transform_within_the_bounds() {
+        let transformer = UuidToIntTransformer::new(
+            "github",
+            "users",
+            "user_id",
+            UuidToIntOptions {
+                min: Some(-5),
+                max: Some(5),
+            },
+        )
+        .unwrap();
+
+        for idx in 0..100 {
+            let uuid = format!("550e8400-e29b-41d4-a716-{:012x}", idx);
+            assert!((-5..=5).contains(&transform_to_int(&transformer, uuid.as_str())));
+        }
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_other_string() {
+        let transformer = UuidToIntTransformer::default();
+
+        for value in ["", "not-a-uuid", "550e8400-e29b-41d4-a716-44665544000z"] {
+            assert_eq!(
+                transform(&transformer, value).string_value().unwrap(),
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn new_fails_with_min_greater_than_max() {
+        let options = UuidToIntOptions {
+            min: Some(10),
+            max: Some(1),
+        };
+
+        assert!(UuidToIntTransformer::new("github", "users", "user_id", options).is_err());
+    }
+}
@@ -0,0 +1,332 @@
+use std::io::{Error, ErrorKind};
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::transformer::seeded::rng;
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+/// countries of the generated VAT numbers
+const COUNTRY_CODES: [&str; 5] = ["BE", "DE", "FR", "GB", "NL"];
+
+/// This struct is dedicated to replacing a VAT number by a fake one with the format and the checksum of its country.
+pub struct VatTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    country_code: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct VatOptions {
+    /// country of the VAT numbers (e.g. DE)
+    pub country_code: String,
+}
+
+impl VatTransformer {
+    pub fn new<S>(
+        database_name: S,
+        table_name: S,
+        column_name: S,
+        options: VatOptions,
+    ) -> Result<Self, Error>
+    where
+        S: Into<String>,
+    {
+        let country_code = options.country_code.to_uppercase();
+
+        if !COUNTRY_CODES.contains(&country_code.as_str()) {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!(
+                    "unsupported VAT country code '{}' (supported: {})",
+                    options.country_code,
+                    COUNTRY_CODES.join(", ")
+                ),
+            ));
+        }
+
+        Ok(VatTransformer {
+            database_name: database_name.into(),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            country_code,
+        })
+    }
+
+    /// a random VAT number, without the country code
+    fn fake_vat_number(&self) -> String {
+        match self.country_code.as_str() {
+            "BE" => belgian_vat_number(),
+            "DE" => german_vat_number(),
+            "FR" => french_vat_number(),
+            "GB" => british_vat_number(),
+            _ => dutch_vat_number(),
+        }
+    }
+}
+
+fn random_digits(count: usize) -> Vec<u32> {
+    let mut random = rng();
+    (0..count).map(|_| random.gen_range(0..10)).collect()
+}
+
+fn to_string(digits: &[u32]) -> String {
+    digits.iter().map(|digit| digit.to_string()).collect()
+}
+
+fn to_number(digits: &[u32]) -> u64 {
+    digits
+        .iter()
+        .fold(0, |number, digit| number * 10 + *digit as u64)
+}
+
+/// 10 digits starting with 0 or 1, the last 2 ones are 97 - (the first 8 ones mod 97)
+fn belgian_vat_number() -> String {
+    let mut digits = vec![rng().gen_range(0..2)];
+    digits.extend(random_digits(7));
+    let check = 97 - to_number(&digits) % 97;
+
+    format!("{}{:02}", to_string(&digits), check)
+}
+
+/// 9 digits, the last one is the ISO 7064 MOD 11,10 check digit
+fn german_vat_number() -> String {
+    let mut digits = vec![rng().gen_range(1..10)];
+    digits.extend(random_digits(7));
+
+    let mut product = 10;
+    for digit in digits.iter() {
+        let mut sum = (digit + product) % 10;
+        if sum == 0 {
+            sum = 10;
+        }
+        product = (2 * sum) % 11;
+    }
+
+    let check = match 11 - product {
+        10 => 0,
+        check => check,
+    };
+    digits.push(check);
+
+    to_string(&digits)
+}
+
+/// 2 check digits followed by a SIREN (9 digits with a Luhn checksum)
+fn french_vat_number() -> String {
+    let mut digits = random_digits(8);
+
+    // the check digit doubles the digits at even positions from the end, the check digit excluded
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(idx, digit)| match idx % 2 {
+            0 if digit * 2 > 9 => digit * 2 - 9,
+            0 => digit * 2,
+            _ => *digit,
+        })
+        .sum();
+    digits.push((10 - sum % 10) % 10);
+
+    let siren = to_number(&digits);
+    format!("{:02}{}", (12 + 3 * (siren % 97)) % 97, to_string(&digits))
+}
+
+/// 9 digits, the weighted sum of the first 7 ones plus the last 2 ones is a multiple of 97
+fn british_vat_number() -> String {
+    let digits = random_digits(7);
+    let sum: u32 = digits
+        .iter()
+        .zip((2..=8).rev())
+        .map(|(digit, weight)| digit * weight)
+        .sum();
+
+    format!("{}{:02}", to_string(&digits), (97 - sum % 97) % 97)
+}
+
+/// 9 digits with an "11-proof" checksum, followed by B and a 2 digits branch number
+fn dutch_vat_number() -> String {
+    loop {
+        let digits = random_digits(8);
+        let check = digits
+            .iter()
+            .zip((2..=9).rev())
+            .map(|(digit, weight)| digit * weight)
+            .sum::<u32>()
+            % 11;
+
+        // there is no check digit for this number
+        if check == 10 {
+            continue;
+        }
+
+        let branch = rng().gen_range(1..100);
+        return format!("{}{}B{:02}", to_string(&digits), check, branch);
+    }
+}
+
+impl Default for VatTransformer {
+    fn default() -> Self {
+        VatTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            country_code: "DE".to_string(),
+        }
+    }
+}
+
+impl Transformer for VatTransformer {
+    fn id(&self) -> &str {
+        "vat"
+    }
+
+    fn description(&self) -> &str {
+        "Generate a VAT number with a valid format and checksum for a country (string only). [DE136695976]->[DE281938472]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        match column {
+            Column::StringValue(column_name, value) if value.is_empty() => {
+                Column::StringValue(column_name, value)
+            }
+            Column::StringValue(column_name, value) => {
+                // the country code is kept only if the original value has one
+                let new_value = match value.chars().next() {
+                    Some(c) if c.is_ascii_alphabetic() => {
+                        format!("{}{}", self.country_code, self.fake_vat_number())
+                    }
+                    _ => self.fake_vat_number(),
+                };
+
+                Column::StringValue(column_name, new_value)
+            }
+            column => column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{VatOptions, VatTransformer};
+
+    fn transform(country_code: &str, value: &str) -> String {
+        let options = VatOptions {
+            country_code: country_code.to_string(),
+        };
+        let transformer = VatTransformer::new("github", "companies", "vat", options).unwrap();
+        let column = Column::StringValue("vat".to_string(), value.to_string());
+        let transformed_column = transformer.transform(column);
+        transformed_column.string_value().unwrap().to_string()
+    }
+
+    fn digits(value: &str) -> Vec<u32> {
+        value.chars().filter_map(|c| c.to_digit(10)).collect()
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = VatTransformer::default();
+        let column = Column::NumberValue("vat".to_string(), 42);
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.number_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 42)
+    }
+
+    #[test]
+    fn transform_german_vat_number() {
+        for _ in 0..100 {
+            let value = transform("DE", "DE136695976");
+            assert_eq!(value.len(), 11);
+            assert!(value.starts_with("DE"));
+
+            let digits = digits(&value);
+            assert_eq!(digits.len(), 9);
+            assert_ne!(digits[0], 0);
+
+            // ISO 7064 MOD 11,10
+            let mut product = 10;
+            for digit in digits[..8].iter() {
+                let sum = match (digit + product) % 10 {
+                    0 => 10,
+                    sum => sum,
+                };
+                product = (2 * sum) % 11;
+            }
+            assert_eq!((11 - product) % 10, digits[8], "{}", value);
+        }
+    }
+
+    #[test]
+    fn transform_british_vat_number() {
+        for _ in 0..100 {
+            let value = transform("gb", "GB980780684");
+            assert_eq!(value.len(), 11);
+            assert!(value.starts_with("GB"));
+
+            let digits = digits(&value);
+            let weighted_sum: u32 = [8, 7, 6, 5, 4, 3, 2]
+                .iter()
+                .zip(digits.iter())
+                .map(|(weight, digit)| weight * digit)
+                .sum();
+            let check = digits[7] * 10 + digits[8];
+            assert_eq!((weighted_sum + check) % 97, 0, "{}", value);
+        }
+    }
+
+    #[test]
+    fn transform_french_vat_number() {
+        for _ in 0..100 {
+            let value = transform("FR", "FR40303265045");
+            assert_eq!(value.len(), 13);
+
+            let siren = value[4..].parse::<u64>().unwrap();
+            let key = value[2..4].parse::<u64>().unwrap();
+            assert_eq!(key, (12 + 3 * (siren % 97)) % 97, "{}", value);
+        }
+    }
+
+    #[test]
+    fn transform_keeps_the_missing_country_code() {
+        let value = transform("DE", "136695976");
+        assert_eq!(value.len(), 9);
+        assert!(value.chars().all(|c| c.is_ascii_digit()));
+
+        let value = transform("NL", "123456782B01");
+        assert_eq!(value.len(), 12);
+        assert_eq!(&value[9..10], "B");
+    }
+
+    #[test]
+    fn transform_doesnt_change_with_empty_string() {
+        assert_eq!(transform("BE", ""), "");
+    }
+
+    #[test]
+    fn new_fails_with_an_unsupported_country_code() {
+        let options = VatOptions {
+            country_code: "XX".to_string(),
+        };
+
+        assert!(VatTransformer::new("github", "companies", "vat", options).is_err());
+    }
+}
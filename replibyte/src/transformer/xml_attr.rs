@@ -0,0 +1,446 @@
+use serde::{Deserialize, Serialize};
+
+use crate::config::TransformerTypeConfig;
+use crate::transformer::Transformer;
+use crate::types::Column;
+
+/// This struct is dedicated to transforming the text of some elements, or some attributes, of an XML document.
+/// The rest of the document (other elements, comments, formatting) is kept as it is.
+pub struct XmlAttrTransformer {
+    database_name: String,
+    table_name: String,
+    column_name: String,
+    transformers: Vec<(XmlPath, Box<dyn Transformer>)>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct XmlAttrOptions {
+    pub paths: Vec<XmlPathConfig>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct XmlPathConfig {
+    /// `/customer/email` for the text of an element, `/customer/@id` for an attribute
+    pub path: String,
+
+    #[serde(flatten)]
+    pub transformer: TransformerTypeConfig,
+}
+
+struct XmlPath {
+    elements: Vec<String>,
+    attribute: Option<String>,
+}
+
+impl XmlPath {
+    fn new(path: &str) -> Self {
+        let mut elements = path
+            .split('/')
+            .filter(|element| !element.is_empty())
+            .map(|element| element.to_string())
+            .collect::<Vec<_>>();
+
+        let attribute = match elements.last() {
+            Some(last) if last.starts_with('@') => elements.pop().map(|attr| attr[1..].to_string()),
+            _ => None,
+        };
+
+        XmlPath {
+            elements,
+            attribute,
+        }
+    }
+
+    fn matches(&self, elements: &[&str], attribute: Option<&str>) -> bool {
+        self.attribute.as_deref() == attribute
+            && self.elements.len() == elements.len()
+            && self.elements.iter().zip(elements).all(|(a, b)| a == b)
+    }
+}
+
+impl XmlAttrTransformer {
+    pub fn new<S>(database_name: S, table_name: S, column_name: S, options: &XmlAttrOptions) -> Self
+    where
+        S: Into<String>,
+    {
+        let database_name = database_name.into();
+        let table_name = table_name.into();
+        let column_name = column_name.into();
+
+        let transformers = options
+            .paths
+            .iter()
+            .map(|path| {
+                (
+                    XmlPath::new(path.path.as_str()),
+                    path.transformer.transformer(
+                        database_name.as_str(),
+                        table_name.as_str(),
+                        column_name.as_str(),
+                    ),
+                )
+            })
+            .collect();
+
+        XmlAttrTransformer {
+            database_name,
+            table_name,
+            column_name,
+            transformers,
+        }
+    }
+
+    /// apply the transformer of the path on the value, if any
+    fn transform_value(
+        &self,
+        elements: &[&str],
+        attribute: Option<&str>,
+        value: &str,
+    ) -> Option<String> {
+        let (_, transformer) = self
+            .transformers
+            .iter()
+            .find(|(path, _)| path.matches(elements, attribute))?;
+
+        let column = Column::StringValue(self.column_name.clone(), value.to_string());
+        let new_value = match transformer.transform(column) {
+            Column::NumberValue(_, value) => value.to_string(),
+            Column::FloatNumberValue(_, value) => value.to_string(),
+            Column::StringValue(_, value) => value,
+            Column::CharValue(_, value) => value.to_string(),
+            Column::BooleanValue(_, value) => value.to_string(),
+            Column::None(_) => String::new(),
+        };
+
+        Some(new_value)
+    }
+
+    fn transform_text(&self, elements: &[&str], text: &str) -> String {
+        let value = text.trim();
+        if value.is_empty() {
+            return text.to_string();
+        }
+
+        match self.transform_value(elements, None, unescape(value).as_str()) {
+            Some(new_value) => text.replacen(value, escape(new_value.as_str(), None).as_str(), 1),
+            None => text.to_string(),
+        }
+    }
+
+    /// transform a start tag (e.g. `<customer id="42">`) - returns its length, the new tag, the element name and if
+    /// it is self-closing
+    fn transform_start_tag<'a>(
+        &self,
+        elements: &[&'a str],
+        tag: &'a str,
+    ) -> Option<(usize, String, &'a str, bool)> {
+        let name_end = tag[1..].find(|c: char| c.is_whitespace() || c == '/' || c == '>')? + 1;
+        let name = &tag[1..name_end];
+
+        let mut element_path = elements.to_vec();
+        element_path.push(name);
+
+        let mut new_tag = tag[..name_end].to_string();
+        let mut pos = name_end;
+
+        loop {
+            let whitespaces = tag[pos..].len() - tag[pos..].trim_start().len();
+            new_tag.push_str(&tag[pos..pos + whitespaces]);
+            pos += whitespaces;
+
+            let rest = &tag[pos..];
+            if rest.starts_with("/>") {
+                new_tag.push_str("/>");
+                return Some((pos + 2, new_tag, name, true));
+            }
+
+            if rest.starts_with('>') {
+                new_tag.push('>');
+                return Some((pos + 1, new_tag, name, false));
+            }
+
+            // attribute="value"
+            let equal = rest.find('=')?;
+            let attribute = rest[..equal].trim();
+            if attribute.is_empty() || attribute.contains(['<', '>']) {
+                return None;
+            }
+
+            new_tag.push_str(&rest[..=equal]);
+            pos += equal + 1;
+
+            let whitespaces = tag[pos..].len() - tag[pos..].trim_start().len();
+            new_tag.push_str(&tag[pos..pos + whitespaces]);
+            pos += whitespaces;
+
+            let quote = tag[pos..]
+                .chars()
+                .next()
+                .filter(|c| *c == '"' || *c == '\'')?;
+            let value_end = tag[pos + 1..].find(quote)? + pos + 1;
+            let value = &tag[pos + 1..value_end];
+
+            let new_value = match self.transform_value(
+                &element_path,
+                Some(attribute),
+                unescape(value).as_str(),
+            ) {
+                Some(new_value) => escape(new_value.as_str(), Some(quote)),
+                None => value.to_string(),
+            };
+
+            new_tag.push(quote);
+            new_tag.push_str(new_value.as_str());
+            new_tag.push(quote);
+            pos = value_end + 1;
+        }
+    }
+
+    /// transform the configured paths of the document - `None` if the document is not valid
+    fn transform_xml(&self, xml: &str) -> Option<String> {
+        let mut new_xml = String::with_capacity(xml.len());
+        let mut elements: Vec<&str> = vec![];
+        let mut pos = 0;
+
+        while pos < xml.len() {
+            let rest = &xml[pos..];
+
+            if !rest.starts_with('<') {
+                let text_end = rest.find('<').unwrap_or(rest.len());
+                new_xml.push_str(self.transform_text(&elements, &rest[..text_end]).as_str());
+                pos += text_end;
+            } else if rest.starts_with("<![CDATA[") {
+                let cdata_end = rest.find("]]>")?;
+                let value = &rest[9..cdata_end];
+
+                let new_value = match self.transform_value(&elements, None, value) {
+                    Some(new_value) if !new_value.contains("]]>") => new_value,
+                    _ => value.to_string(),
+                };
+
+                new_xml.push_str(format!("<![CDATA[{}]]>", new_value).as_str());
+                pos += cdata_end + 3;
+            } else if rest.starts_with("<!--") {
+                let comment_end = rest.find("-->")? + 3;
+                new_xml.push_str(&rest[..comment_end]);
+                pos += comment_end;
+            } else if rest.starts_with("<?") {
+                let instruction_end = rest.find("?>")? + 2;
+                new_xml.push_str(&rest[..instruction_end]);
+                pos += instruction_end;
+            } else if rest.starts_with("</") || rest.starts_with("<!") {
+                // end tag or doctype
+                let tag_end = rest.find('>')? + 1;
+                if rest.starts_with("</") && elements.pop()? != rest[2..tag_end - 1].trim() {
+                    return None;
+                }
+
+                new_xml.push_str(&rest[..tag_end]);
+                pos += tag_end;
+            } else {
+                let (tag_len, new_tag, name, is_self_closing) =
+                    self.transform_start_tag(&elements, rest)?;
+
+                if !is_self_closing {
+                    elements.push(name);
+                }
+
+                new_xml.push_str(new_tag.as_str());
+                pos += tag_len;
+            }
+        }
+
+        Some(new_xml)
+    }
+}
+
+impl Default for XmlAttrTransformer {
+    fn default() -> Self {
+        XmlAttrTransformer {
+            database_name: String::default(),
+            table_name: String::default(),
+            column_name: String::default(),
+            transformers: vec![],
+        }
+    }
+}
+
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(start) = rest.find('&') {
+        unescaped.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest.find(';').map(|end| &rest[1..end]);
+        let c = match entity {
+            Some("lt") => Some('<'),
+            Some("gt") => Some('>'),
+            Some("amp") => Some('&'),
+            Some("quot") => Some('"'),
+            Some("apos") => Some('\''),
+            Some(entity) if entity.starts_with("#x") => u32::from_str_radix(&entity[2..], 16)
+                .ok()
+                .and_then(char::from_u32),
+            Some(entity) if entity.starts_with('#') => {
+                entity[1..].parse::<u32>().ok().and_then(char::from_u32)
+            }
+            _ => None,
+        };
+
+        match (c, entity) {
+            (Some(c), Some(entity)) => {
+                unescaped.push(c);
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                unescaped.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    unescaped.push_str(rest);
+    unescaped
+}
+
+fn escape(value: &str, quote: Option<char>) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            '&' => "&amp;".to_string(),
+            '<' => "&lt;".to_string(),
+            '>' => "&gt;".to_string(),
+            '"' if quote == Some('"') => "&quot;".to_string(),
+            '\'' if quote == Some('\'') => "&apos;".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+impl Transformer for XmlAttrTransformer {
+    fn id(&self) -> &str {
+        "xml-attr"
+    }
+
+    fn description(&self) -> &str {
+        "Apply transformers on some elements or attributes of an XML document (string only). [<name>Nancy</name>]->[<name>N</name>]"
+    }
+
+    fn database_name(&self) -> &str {
+        self.database_name.as_str()
+    }
+
+    fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    fn column_name(&self) -> &str {
+        self.column_name.as_str()
+    }
+
+    fn transform(&self, column: Column) -> Column {
+        match column {
+            Column::StringValue(column_name, value) => {
+                // a value which is not a valid XML document is kept as it is
+                let new_value = self.transform_xml(value.as_str()).unwrap_or(value);
+                Column::StringValue(column_name, new_value)
+            }
+            column => column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::TransformerTypeConfig;
+    use crate::{transformer::Transformer, types::Column};
+
+    use super::{XmlAttrOptions, XmlAttrTransformer, XmlPathConfig};
+
+    #[test]
+    fn transform_doesnt_change_with_number_value() {
+        let transformer = get_transformer();
+        let column = Column::NumberValue("profile".to_string(), 42);
+        let transformed_column = transformer.transform(column);
+        let transformed_value = transformed_column.number_value().unwrap();
+
+        assert_eq!(transformed_value.to_owned(), 42)
+    }
+
+    #[test]
+    fn transform_doesnt_change_an_invalid_document() {
+        let transformer = get_transformer();
+        let value = "<customer><name>Nancy</customer";
+        let column = Column::StringValue("profile".to_string(), value.to_string());
+        let transformed_column = transformer.transform(column);
+
+        assert_eq!(transformed_column.string_value().unwrap(), value)
+    }
+
+    #[test]
+    fn transform_targeted_elements_and_attributes() {
+        let transformer = get_transformer();
+        let value = r#"<?xml version="1.0"?>
+<!-- exported from the CRM -->
+<customer id="42" email='nancy.davolio@northwind.com'>
+  <name>Nancy &amp; Co</name>
+  <notes><![CDATA[Davolio]]></notes>
+  <city>Seattle</city>
+  <contact><name>Andrew</name></contact>
+  <empty/>
+</customer>"#;
+
+        let column = Column::StringValue("profile".to_string(), value.to_string());
+        let transformed_column = transformer.transform(column);
+
+        assert_eq!(
+            transformed_column.string_value().unwrap(),
+            r#"<?xml version="1.0"?>
+<!-- exported from the CRM -->
+<customer id="42" email='n'>
+  <name>N</name>
+  <notes><![CDATA[D]]></notes>
+  <city>Seattle</city>
+  <contact><name>Andrew</name></contact>
+  <empty/>
+</customer>"#
+        );
+    }
+
+    #[test]
+    fn deserialize_nested_transformers() {
+        let options: XmlAttrOptions = serde_yaml::from_str(
+            r#"
+paths:
+  - path: /customer/name
+    transformer_name: keep-first-char
+  - path: /customer/@email
+    transformer_name: email
+    transformer_options:
+      preserve_plus_tag: true
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(options.paths.len(), 2);
+        assert_eq!(
+            options.paths[0].transformer,
+            TransformerTypeConfig::KeepFirstChar(None)
+        );
+        assert_eq!(options.paths[1].path, "/customer/@email");
+    }
+
+    fn get_transformer() -> XmlAttrTransformer {
+        let paths = ["/customer/name", "/customer/notes", "/customer/@email"]
+            .iter()
+            .map(|path| XmlPathConfig {
+                path: path.to_string(),
+                transformer: TransformerTypeConfig::KeepFirstChar(None),
+            })
+            .collect();
+
+        XmlAttrTransformer::new("github", "customers", "profile", &XmlAttrOptions { paths })
+    }
+}
@@ -0,0 +1,421 @@
+use std::collections::HashMap;
+use std::io::{Error, ErrorKind};
+
+use crate::translation::postgres_to_mysql::{
+    closing_parenthesis, split_identifier, split_top_level,
+};
+use crate::types::Bytes;
+
+/// number of rows of each INSERT statement
+const ROWS_PER_INSERT: usize = 100;
+
+/// PostgreSQL types whose values are written without quotes
+const NUMBER_TYPES: [&str; 17] = [
+    "smallint",
+    "integer",
+    "bigint",
+    "int2",
+    "int4",
+    "int8",
+    "int",
+    "smallserial",
+    "bigserial",
+    "serial",
+    "numeric",
+    "decimal",
+    "real",
+    "double precision",
+    "float4",
+    "float8",
+    "oid",
+];
+
+const BOOLEAN_TYPES: [&str; 2] = ["boolean", "bool"];
+
+/// first words of the table constraints of a CREATE TABLE statement
+const TABLE_CONSTRAINTS: [&str; 6] = [
+    "CONSTRAINT",
+    "PRIMARY",
+    "UNIQUE",
+    "CHECK",
+    "FOREIGN",
+    "EXCLUDE",
+];
+
+/// Translate the `COPY ... FROM stdin` blocks of a PostgreSQL dump into batched INSERT statements.
+/// The types of the columns are read from the CREATE TABLE statements, the other statements are kept as they are.
+#[derive(Default)]
+pub struct CopyToInsert {
+    /// columns and types of the tables created so far
+    tables: HashMap<String, Vec<(String, String)>>,
+    /// CREATE TABLE statement being read
+    create_table: Option<String>,
+    /// COPY block being read
+    copy: Option<CopyBlock>,
+}
+
+struct CopyBlock {
+    table: String,
+    columns: Vec<String>,
+    /// type of each column, none if the table is unknown
+    types: Vec<Option<String>>,
+    rows: Vec<String>,
+}
+
+impl CopyBlock {
+    fn push_row(&mut self, row: &str) {
+        let values = row
+            .split('\t')
+            .enumerate()
+            .map(|(idx, value)| sql_value(value, self.types.get(idx).cloned().flatten()))
+            .collect::<Vec<_>>();
+
+        self.rows.push(format!("({})", values.join(", ")));
+    }
+
+    /// write the rows read so far as one INSERT statement
+    fn write_insert(&mut self, result: &mut String) {
+        if self.rows.is_empty() {
+            return;
+        }
+
+        let columns = match self.columns.is_empty() {
+            true => String::new(),
+            false => format!(" ({})", self.columns.join(", ")),
+        };
+
+        result.push_str(
+            format!(
+                "INSERT INTO {}{} VALUES {};\n",
+                self.table,
+                columns,
+                self.rows.join(", ")
+            )
+            .as_str(),
+        );
+        self.rows.clear();
+    }
+}
+
+impl CopyToInsert {
+    pub fn translate(&mut self, data: Bytes) -> Result<Bytes, Error> {
+        let data = String::from_utf8(data)
+            .map_err(|err| Error::new(ErrorKind::Other, format!("{}", err)))?;
+        let mut result = String::with_capacity(data.len());
+
+        for line in data.split_inclusive('\n') {
+            self.translate_line(line, &mut result)?;
+        }
+
+        Ok(result.into_bytes())
+    }
+
+    fn translate_line(&mut self, line: &str, result: &mut String) -> Result<(), Error> {
+        if let Some(copy) = self.copy.as_mut() {
+            let row = line.strip_suffix('\n').unwrap_or(line);
+
+            // `\.` ends the data of the COPY block
+            if row == "\\." {
+                copy.write_insert(result);
+                self.copy = None;
+                return Ok(());
+            }
+
+            copy.push_row(row);
+            if copy.rows.len() == ROWS_PER_INSERT {
+                copy.write_insert(result);
+            }
+
+            return Ok(());
+        }
+
+        if let Some(copy) = self.copy_block(line.trim())? {
+            self.copy = Some(copy);
+            return Ok(());
+        }
+
+        self.read_create_table(line);
+        result.push_str(line);
+
+        Ok(())
+    }
+
+    /// parse a `COPY table (columns) FROM stdin;` statement
+    fn copy_block(&self, line: &str) -> Result<Option<CopyBlock>, Error> {
+        let statement = line.strip_prefix('\\').unwrap_or(line);
+        let is_copy =
+            matches!(statement.get(..5), Some(keyword) if keyword.eq_ignore_ascii_case("COPY "));
+        let is_from_stdin = matches!(
+            statement.get(statement.len().saturating_sub(11)..),
+            Some(end) if end.eq_ignore_ascii_case("FROM stdin;")
+        );
+
+        if !is_copy || !is_from_stdin {
+            return Ok(None);
+        }
+
+        let (table, rest) = split_identifier(statement[5..].trim_start());
+        let rest = rest.trim_start();
+
+        let columns = match rest.starts_with('(') {
+            true => {
+                let close = closing_parenthesis(rest, 0).ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::Other,
+                        format!("invalid COPY statement: {}", line),
+                    )
+                })?;
+
+                split_top_level(&rest[1..close], ',')
+                    .into_iter()
+                    .map(|column| column.trim().to_string())
+                    .collect::<Vec<_>>()
+            }
+            false => vec![],
+        };
+
+        let table_columns = self.tables.get(table);
+
+        let types = match (columns.is_empty(), table_columns) {
+            // without column list, the values follow the order of the table columns
+            (true, Some(table_columns)) => table_columns
+                .iter()
+                .map(|(_, column_type)| Some(column_type.clone()))
+                .collect(),
+            (false, Some(table_columns)) => columns
+                .iter()
+                .map(|column| {
+                    table_columns
+                        .iter()
+                        .find(|(name, _)| name == column)
+                        .map(|(_, column_type)| column_type.clone())
+                })
+                .collect(),
+            (_, None) => vec![],
+        };
+
+        Ok(Some(CopyBlock {
+            table: table.to_string(),
+            columns,
+            types,
+            rows: vec![],
+        }))
+    }
+
+    /// keep the column types of the CREATE TABLE statements, written on several lines by pg_dump
+    fn read_create_table(&mut self, line: &str) {
+        let is_create_table = matches!(
+            line.get(..13),
+            Some(keyword) if keyword.eq_ignore_ascii_case("CREATE TABLE ")
+        );
+
+        let statement = match self.create_table.as_mut() {
+            Some(statement) => {
+                statement.push_str(line);
+                statement
+            }
+            None if is_create_table => self.create_table.insert(line.to_string()),
+            None => return,
+        };
+
+        if !statement.trim_end().ends_with(';') {
+            return;
+        }
+
+        let statement = self.create_table.take().unwrap_or_default();
+        let (table, rest) = split_identifier(statement[13..].trim_start());
+
+        let open = match rest.find('(') {
+            Some(open) => open,
+            None => return,
+        };
+
+        let close = match closing_parenthesis(rest, open) {
+            Some(close) => close,
+            None => return,
+        };
+
+        let columns = split_top_level(&rest[open + 1..close], ',')
+            .into_iter()
+            .map(|definition| split_identifier(definition.trim()))
+            .filter(|(column, _)| !TABLE_CONSTRAINTS.contains(&column.to_uppercase().as_str()))
+            .map(|(column, column_type)| (column.to_string(), column_type.trim().to_lowercase()))
+            .collect::<Vec<_>>();
+
+        let _ = self.tables.insert(table.to_string(), columns);
+    }
+}
+
+/// the type is one of the types, with a length or a precision but not an array (e.g. `numeric(10,2)`)
+fn is_type(column_type: &str, types: &[&str]) -> bool {
+    types
+        .iter()
+        .any(|name| match column_type.strip_prefix(name) {
+            Some(rest) => rest.is_empty() || rest.starts_with(' ') || rest.starts_with('('),
+            None => false,
+        })
+}
+
+/// SQL literal of a value of a COPY row - the value is quoted if the type of its column is unknown
+fn sql_value(value: &str, column_type: Option<String>) -> String {
+    if value == "\\N" {
+        return "NULL".to_string();
+    }
+
+    let value = unescape(value);
+
+    match column_type.as_deref() {
+        Some(column_type)
+            if is_type(column_type, &NUMBER_TYPES)
+                && value.parse::<f64>().map(f64::is_finite).unwrap_or(false) =>
+        {
+            value
+        }
+        Some(column_type) if is_type(column_type, &BOOLEAN_TYPES) && value == "t" => {
+            "true".to_string()
+        }
+        Some(column_type) if is_type(column_type, &BOOLEAN_TYPES) && value == "f" => {
+            "false".to_string()
+        }
+        _ => format!("'{}'", value.replace('\'', "''")),
+    }
+}
+
+/// decode the backslash escapes of the COPY text format (e.g. `\t`, `\\` or `\303\251`)
+fn unescape(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut result = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+
+    while idx < bytes.len() {
+        if bytes[idx] != b'\\' || idx + 1 == bytes.len() {
+            result.push(bytes[idx]);
+            idx += 1;
+            continue;
+        }
+
+        idx += 1;
+        let escaped = bytes[idx];
+        idx += 1;
+
+        match escaped {
+            b'b' => result.push(0x08),
+            b'f' => result.push(0x0c),
+            b'n' => result.push(b'\n'),
+            b'r' => result.push(b'\r'),
+            b't' => result.push(b'\t'),
+            b'v' => result.push(0x0b),
+            b'0'..=b'7' => {
+                // up to 3 octal digits
+                let mut byte = (escaped - b'0') as u32;
+                for _ in 0..2 {
+                    match bytes.get(idx) {
+                        Some(digit @ b'0'..=b'7') => {
+                            byte = byte * 8 + (digit - b'0') as u32;
+                            idx += 1;
+                        }
+                        _ => break,
+                    }
+                }
+                result.push(byte as u8);
+            }
+            b'x' if matches!(bytes.get(idx), Some(digit) if digit.is_ascii_hexdigit()) => {
+                // up to 2 hexadecimal digits
+                let end = match bytes.get(idx + 1) {
+                    Some(digit) if digit.is_ascii_hexdigit() => idx + 2,
+                    _ => idx + 1,
+                };
+                let hex = std::str::from_utf8(&bytes[idx..end]).unwrap_or("0");
+                result.push(u8::from_str_radix(hex, 16).unwrap_or(0));
+                idx = end;
+            }
+            other => result.push(other),
+        }
+    }
+
+    String::from_utf8_lossy(&result).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{unescape, CopyToInsert};
+
+    fn translate(translation: &mut CopyToInsert, dump: &str) -> String {
+        String::from_utf8(translation.translate(dump.as_bytes().to_vec()).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn translate_copy_block_into_insert() {
+        let mut translation = CopyToInsert::default();
+        let dump = "CREATE TABLE public.customers (
+    id integer NOT NULL,
+    name character varying(30),
+    balance numeric(10,2),
+    active boolean,
+    CONSTRAINT customers_pkey PRIMARY KEY (id)
+);
+
+COPY public.customers (id, name, balance, active) FROM stdin;
+1\tMaria\t12.50\tt
+2\tO'Brien\\tJr\t\\N\tf
+3\t42\t-7\t\\N
+\\.
+
+SELECT pg_catalog.setval('public.customers_id_seq', 3, true);
+";
+
+        assert_eq!(
+            translate(&mut translation, dump),
+            "CREATE TABLE public.customers (
+    id integer NOT NULL,
+    name character varying(30),
+    balance numeric(10,2),
+    active boolean,
+    CONSTRAINT customers_pkey PRIMARY KEY (id)
+);
+
+INSERT INTO public.customers (id, name, balance, active) VALUES (1, 'Maria', 12.50, true), (2, 'O''Brien\tJr', NULL, false), (3, '42', -7, NULL);
+
+SELECT pg_catalog.setval('public.customers_id_seq', 3, true);
+"
+        );
+    }
+
+    #[test]
+    fn translate_copy_block_in_batches() {
+        let mut translation = CopyToInsert::default();
+        let mut dump = "COPY public.events (id) FROM stdin;\n".to_string();
+        for id in 0..250 {
+            dump.push_str(format!("{}\n", id).as_str());
+        }
+        dump.push_str("\\.\n");
+
+        let result = translate(&mut translation, dump.as_str());
+        let inserts = result.lines().collect::<Vec<_>>();
+
+        // the table is unknown, so the values are quoted
+        assert_eq!(inserts.len(), 3);
+        assert!(inserts[0].starts_with("INSERT INTO public.events (id) VALUES ('0'), ('1'), "));
+        assert!(inserts[2].ends_with("('248'), ('249');"));
+        assert_eq!(inserts[2].matches("), (").count(), 49);
+    }
+
+    #[test]
+    fn translate_copy_block_without_column_list() {
+        let mut translation = CopyToInsert::default();
+        let dump = "CREATE TABLE public.users (id bigint, email text);\nCOPY public.users FROM stdin;\n1\tnancy@northwind.com\n\\.\n";
+
+        assert_eq!(
+            translate(&mut translation, dump),
+            "CREATE TABLE public.users (id bigint, email text);\nINSERT INTO public.users VALUES (1, 'nancy@northwind.com');\n"
+        );
+    }
+
+    #[test]
+    fn unescape_copy_values() {
+        assert_eq!(unescape("C:\\\\temp"), "C:\\temp");
+        assert_eq!(unescape("a\\nb\\tc"), "a\nb\tc");
+        assert_eq!(unescape("caf\\303\\251"), "café");
+        assert_eq!(unescape("\\x41\\x4a"), "AJ");
+    }
+}
@@ -0,0 +1,2 @@
+pub mod copy_to_insert;
+pub mod postgres_to_mysql;
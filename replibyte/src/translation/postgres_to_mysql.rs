@@ -0,0 +1,733 @@
+use std::collections::HashMap;
+use std::io::{BufReader, Error, ErrorKind};
+
+use dump_parser::utils::{list_sql_queries_from_dump_reader, ListQueryResult};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::types::Bytes;
+
+/// PostgreSQL types supported by the translation with their MySQL type without and with a length.
+/// Multi-word names come first to be matched before their first word.
+const TYPES: [(&str, &str, Option<&str>); 35] = [
+    ("timestamp without time zone", "DATETIME", None),
+    ("timestamp with time zone", "DATETIME", None),
+    ("time without time zone", "TIME", None),
+    ("character varying", "TEXT", Some("VARCHAR")),
+    ("double precision", "DOUBLE", None),
+    ("smallserial", "SMALLINT", None),
+    ("bigserial", "BIGINT", None),
+    ("serial", "INT", None),
+    ("timestamptz", "DATETIME", None),
+    ("timestamp", "DATETIME", None),
+    ("time", "TIME", None),
+    ("date", "DATE", None),
+    ("character", "CHAR", Some("CHAR")),
+    ("varchar", "TEXT", Some("VARCHAR")),
+    ("bpchar", "VARCHAR(255)", Some("CHAR")),
+    ("char", "CHAR", Some("CHAR")),
+    ("text", "TEXT", None),
+    ("smallint", "SMALLINT", None),
+    ("integer", "INT", None),
+    ("bigint", "BIGINT", None),
+    ("int2", "SMALLINT", None),
+    ("int4", "INT", None),
+    ("int8", "BIGINT", None),
+    ("int", "INT", None),
+    ("numeric", "DECIMAL(65,30)", Some("DECIMAL")),
+    ("decimal", "DECIMAL(65,30)", Some("DECIMAL")),
+    ("real", "FLOAT", None),
+    ("float4", "FLOAT", None),
+    ("float8", "DOUBLE", None),
+    ("boolean", "TINYINT(1)", None),
+    ("bool", "TINYINT(1)", None),
+    ("bytea", "LONGBLOB", None),
+    ("jsonb", "JSON", None),
+    ("json", "JSON", None),
+    ("uuid", "CHAR(36)", None),
+];
+
+/// statements without MySQL equivalent - they are not restored
+const IGNORED_STATEMENTS: [&str; 10] = [
+    "SET ",
+    "SELECT PG_CATALOG.",
+    "COMMENT ON ",
+    "CREATE EXTENSION ",
+    "CREATE SCHEMA ",
+    "CREATE SEQUENCE ",
+    "ALTER SEQUENCE ",
+    "ALTER SCHEMA ",
+    "GRANT ",
+    "REVOKE ",
+];
+
+lazy_static! {
+    static ref CAST: Regex = Regex::new(&format!(
+        r"(?i)::\s*(?:regclass|{})\b(?:\([0-9, ]*\))?",
+        TYPES
+            .iter()
+            .map(|(name, _, _)| *name)
+            .collect::<Vec<_>>()
+            .join("|")
+    ))
+    .unwrap();
+    static ref NEXTVAL_DEFAULT: Regex = Regex::new(r"(?i)\s*DEFAULT\s+nextval\([^)]*\)").unwrap();
+    static ref REFERENCES_SCHEMA: Regex =
+        Regex::new(r#"(?i)(REFERENCES\s+)(?:"[^"]*"|\w+)\."#).unwrap();
+    static ref CREATE_INDEX: Regex = Regex::new(
+        r"(?is)^CREATE\s+(UNIQUE\s+)?INDEX\s+(\S+)\s+ON\s+(?:ONLY\s+)?(\S+)\s+(?:USING\s+(\w+)\s*)?(\(.*\))$"
+    )
+    .unwrap();
+}
+
+/// Translate the statements of a PostgreSQL dump into MySQL statements.
+/// Only the statements and the types written by `pg_dump --column-inserts` for plain tables are supported,
+/// the translation fails on anything else.
+#[derive(Default)]
+pub struct PostgresToMysql {
+    /// MySQL columns and types of the tables created so far
+    tables: HashMap<String, Vec<(String, String)>>,
+    /// columns with a sequence as default, they become AUTO_INCREMENT once they are a primary key
+    sequence_columns: HashMap<String, String>,
+}
+
+impl PostgresToMysql {
+    pub fn translate(&mut self, data: Bytes) -> Result<Bytes, Error> {
+        let mut queries = vec![];
+        let mut error = None;
+
+        list_sql_queries_from_dump_reader(BufReader::new(data.as_slice()), |query| {
+            if error.is_none() {
+                match self.translate_query(query) {
+                    Ok(Some(query)) => queries.push(query),
+                    Ok(None) => {}
+                    Err(err) => error = Some(err),
+                }
+            }
+
+            ListQueryResult::Continue
+        })?;
+
+        if let Some(err) = error {
+            return Err(err);
+        }
+
+        Ok(queries
+            .into_iter()
+            .flat_map(|query| {
+                let mut bytes = query.into_bytes();
+                bytes.push(b'\n');
+                bytes
+            })
+            .collect::<Vec<_>>())
+    }
+
+    fn translate_query(&mut self, query: &str) -> Result<Option<String>, Error> {
+        let query = query.trim();
+        let query = query.strip_suffix(';').unwrap_or(query).trim_end();
+        let prefix = query.chars().take(32).collect::<String>().to_uppercase();
+
+        if query.is_empty() || query.starts_with("--") {
+            return Ok(None);
+        }
+
+        if IGNORED_STATEMENTS
+            .iter()
+            .any(|statement| prefix.starts_with(statement))
+            || (prefix.starts_with("ALTER ") && query.to_uppercase().contains(" OWNER TO "))
+        {
+            return Ok(None);
+        }
+
+        if prefix.starts_with("CREATE TABLE ") {
+            return self.create_table(query).map(Some);
+        }
+
+        if prefix.starts_with("INSERT INTO ") {
+            return self.insert_into(query).map(Some);
+        }
+
+        if prefix.starts_with("ALTER TABLE ") {
+            return self.alter_table(query);
+        }
+
+        if prefix.starts_with("CREATE INDEX ") || prefix.starts_with("CREATE UNIQUE INDEX ") {
+            return create_index(query).map(Some);
+        }
+
+        if prefix.starts_with("COPY ") || prefix.starts_with("\\COPY ") {
+            return Err(Error::new(
+                ErrorKind::Other,
+                "translation into MySQL does not support COPY, the dump must contain INSERT statements",
+            ));
+        }
+
+        Err(unsupported_statement(query))
+    }
+
+    fn create_table(&mut self, query: &str) -> Result<String, Error> {
+        let open = query
+            .find('(')
+            .ok_or_else(|| unsupported_statement(query))?;
+        let close = closing_parenthesis(query, open).ok_or_else(|| unsupported_statement(query))?;
+
+        // INHERITS, PARTITION BY, WITH (...) ...
+        if !query[close + 1..].trim().is_empty() {
+            return Err(unsupported_statement(query));
+        }
+
+        let table = unquote(strip_schema(query["CREATE TABLE ".len()..open].trim()));
+        let mut columns = vec![];
+        let mut definitions = vec![];
+
+        for definition in split_top_level(&query[open + 1..close], ',') {
+            let definition = definition.trim();
+            let first_word = definition
+                .split(|c: char| c.is_whitespace() || c == '(')
+                .next()
+                .unwrap_or("")
+                .to_uppercase();
+
+            if ["CONSTRAINT", "PRIMARY", "UNIQUE", "CHECK", "FOREIGN"]
+                .contains(&first_word.as_str())
+            {
+                definitions.push(to_mysql_syntax(&without_casts(&without_references_schema(
+                    definition,
+                ))));
+                continue;
+            }
+
+            let (column, rest) = split_identifier(definition);
+            let column = unquote(column);
+            let (mysql_type, rest, is_serial) = column_type(&table, &column, rest.trim_start())?;
+
+            let auto_increment = is_serial || NEXTVAL_DEFAULT.is_match(rest);
+            let rest = without_casts(&NEXTVAL_DEFAULT.replace(rest, ""));
+
+            definitions.push(format!(
+                "{} {}{}{}",
+                quote(&column),
+                mysql_type,
+                if auto_increment {
+                    " AUTO_INCREMENT"
+                } else {
+                    ""
+                },
+                to_mysql_syntax(&rest)
+            ));
+
+            columns.push((column, mysql_type));
+        }
+
+        let _ = self.tables.insert(table.clone(), columns);
+
+        Ok(format!(
+            "CREATE TABLE {} (\n    {}\n);",
+            quote(&table),
+            definitions.join(",\n    ")
+        ))
+    }
+
+    fn insert_into(&self, query: &str) -> Result<String, Error> {
+        let (table, rest) = split_identifier(query["INSERT INTO ".len()..].trim_start());
+        let table = unquote(strip_schema(table));
+        let rest = rest.trim();
+
+        let (column_list, column_names, values) = match rest.starts_with('(') {
+            true => {
+                let close =
+                    closing_parenthesis(rest, 0).ok_or_else(|| unsupported_statement(query))?;
+                let column_names = split_top_level(&rest[1..close], ',')
+                    .into_iter()
+                    .map(|column| unquote(column.trim()))
+                    .collect::<Vec<_>>();
+
+                (
+                    &rest[..=close],
+                    column_names,
+                    rest[close + 1..].trim_start(),
+                )
+            }
+            false => {
+                // without column list, the values follow the order of the table columns
+                let column_names = match self.tables.get(&table) {
+                    Some(columns) => columns.iter().map(|(name, _)| name.clone()).collect(),
+                    None => vec![],
+                };
+
+                ("", column_names, rest)
+            }
+        };
+
+        let values = match values.get(.."VALUES".len()) {
+            Some(keyword) if keyword.eq_ignore_ascii_case("VALUES") => &values["VALUES".len()..],
+            _ => return Err(unsupported_statement(query)),
+        };
+
+        let bytea_indexes = column_names
+            .iter()
+            .enumerate()
+            .filter(|(_, column)| self.column_type(&table, column) == Some("LONGBLOB"))
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+
+        let values = match bytea_indexes.is_empty() {
+            true => values.trim().to_string(),
+            false => split_top_level(values, ',')
+                .into_iter()
+                .map(|row| {
+                    let row = row.trim();
+                    let row = row
+                        .strip_prefix('(')
+                        .and_then(|row| row.strip_suffix(')'))
+                        .unwrap_or(row);
+
+                    let row_values = split_top_level(row, ',')
+                        .into_iter()
+                        .enumerate()
+                        .map(|(idx, value)| match bytea_indexes.contains(&idx) {
+                            true => to_mysql_hex_literal(value.trim()),
+                            false => value.trim().to_string(),
+                        })
+                        .collect::<Vec<_>>();
+
+                    format!("({})", row_values.join(", "))
+                })
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+
+        Ok(format!(
+            "INSERT INTO {}{} VALUES {};",
+            quote(&table),
+            match column_list.is_empty() {
+                true => String::new(),
+                false => format!(" {}", to_mysql_syntax(column_list)),
+            },
+            to_mysql_syntax(&values)
+        ))
+    }
+
+    fn alter_table(&mut self, query: &str) -> Result<Option<String>, Error> {
+        let rest = query["ALTER TABLE ".len()..].trim_start();
+        let rest = match rest.get(.."ONLY ".len()) {
+            Some(keyword) if keyword.eq_ignore_ascii_case("ONLY ") => {
+                rest["ONLY ".len()..].trim_start()
+            }
+            _ => rest,
+        };
+
+        let (table, action) = split_identifier(rest);
+        let table = unquote(strip_schema(table));
+        let action = action.trim();
+        let upper_action = action.to_uppercase();
+
+        if upper_action.starts_with("ALTER COLUMN ")
+            && upper_action.contains("SET DEFAULT NEXTVAL(")
+        {
+            let (column, _) = split_identifier(action["ALTER COLUMN ".len()..].trim_start());
+            let _ = self.sequence_columns.insert(table, unquote(column));
+            return Ok(None);
+        }
+
+        if !upper_action.starts_with("ADD ") {
+            return Err(unsupported_statement(query));
+        }
+
+        let mut statements = vec![format!(
+            "ALTER TABLE {} {};",
+            quote(&table),
+            to_mysql_syntax(&without_casts(&without_references_schema(action)))
+        )];
+
+        // MySQL only accepts AUTO_INCREMENT on a key column
+        if let Some(primary_key) = upper_action.find("PRIMARY KEY") {
+            let open = action[primary_key..].find('(').map(|idx| idx + primary_key);
+            let close = open.and_then(|open| closing_parenthesis(action, open));
+
+            if let (Some(open), Some(close)) = (open, close) {
+                let key_columns = split_top_level(&action[open + 1..close], ',')
+                    .into_iter()
+                    .map(|column| unquote(column.trim()))
+                    .collect::<Vec<_>>();
+
+                if let Some(column) = self.sequence_columns.get(&table) {
+                    if key_columns == [column.clone()] {
+                        if let Some(mysql_type) = self.column_type(&table, column) {
+                            statements.push(format!(
+                                "ALTER TABLE {} MODIFY {} {} NOT NULL AUTO_INCREMENT;",
+                                quote(&table),
+                                quote(column),
+                                mysql_type
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(Some(statements.join("\n")))
+    }
+
+    fn column_type(&self, table: &str, column: &str) -> Option<&str> {
+        self.tables
+            .get(table)?
+            .iter()
+            .find(|(name, _)| name == column)
+            .map(|(_, mysql_type)| mysql_type.as_str())
+    }
+}
+
+fn create_index(query: &str) -> Result<String, Error> {
+    let captures = match CREATE_INDEX.captures(query) {
+        Some(captures) if !query.to_uppercase().contains(" WHERE ") => captures,
+        _ => return Err(unsupported_statement(query)),
+    };
+
+    if let Some(method) = captures.get(4) {
+        if !method.as_str().eq_ignore_ascii_case("btree") {
+            return Err(unsupported_statement(query));
+        }
+    }
+
+    Ok(format!(
+        "CREATE {}INDEX {} ON {} {};",
+        if captures.get(1).is_some() {
+            "UNIQUE "
+        } else {
+            ""
+        },
+        quote(&unquote(&captures[2])),
+        quote(&unquote(strip_schema(&captures[3]))),
+        to_mysql_syntax(&captures[5])
+    ))
+}
+
+/// map the type at the beginning of a column definition and return the rest of the definition
+fn column_type<'a>(
+    table: &str,
+    column: &str,
+    definition: &'a str,
+) -> Result<(String, &'a str, bool), Error> {
+    for (name, mysql_type, mysql_type_with_length) in TYPES {
+        match definition.get(..name.len()) {
+            Some(candidate) if candidate.eq_ignore_ascii_case(name) => {}
+            _ => continue,
+        }
+
+        let rest = &definition[name.len()..];
+        if !(rest.is_empty()
+            || rest.starts_with(|c: char| c.is_whitespace() || c == '(' || c == '['))
+        {
+            continue;
+        }
+
+        let (mysql_type, rest) = match (rest.starts_with('('), mysql_type_with_length) {
+            (false, _) => (mysql_type.to_string(), rest),
+            (true, Some(mysql_type_with_length)) => match rest.find(')') {
+                Some(close) => (
+                    format!("{}{}", mysql_type_with_length, &rest[..=close]),
+                    &rest[close + 1..],
+                ),
+                None => break,
+            },
+            (true, None) => break,
+        };
+
+        // arrays
+        if rest.trim_start().starts_with('[') {
+            break;
+        }
+
+        return Ok((mysql_type, rest, name.ends_with("serial")));
+    }
+
+    Err(Error::new(
+        ErrorKind::Other,
+        format!(
+            "translation into MySQL does not support the type of the column {}.{}: {}",
+            table, column, definition
+        ),
+    ))
+}
+
+fn unsupported_statement(query: &str) -> Error {
+    Error::new(
+        ErrorKind::Other,
+        format!(
+            "translation into MySQL does not support the statement: {}",
+            query.lines().next().unwrap_or(query)
+        ),
+    )
+}
+
+/// convert a PostgreSQL bytea hex literal ('\x0a1b') into a MySQL one (X'0a1b')
+fn to_mysql_hex_literal(value: &str) -> String {
+    match value
+        .strip_prefix("'\\x")
+        .and_then(|value| value.strip_suffix('\''))
+    {
+        Some(hex) if hex.chars().all(|c| c.is_ascii_hexdigit()) => format!("X'{}'", hex),
+        _ => value.to_string(),
+    }
+}
+
+fn without_casts(definition: &str) -> String {
+    CAST.replace_all(definition, "").to_string()
+}
+
+fn without_references_schema(definition: &str) -> String {
+    REFERENCES_SCHEMA.replace_all(definition, "$1").to_string()
+}
+
+/// replace the "quoted identifiers" by `quoted identifiers` and escape the backslashes of the strings,
+/// which are escape characters for MySQL
+fn to_mysql_syntax(query: &str) -> String {
+    let mut result = String::with_capacity(query.len());
+    let mut chars = query.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                result.push('\'');
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\'' if chars.peek() == Some(&'\'') => {
+                            let _ = chars.next();
+                            result.push_str("''");
+                        }
+                        '\'' => {
+                            result.push('\'');
+                            break;
+                        }
+                        '\\' => result.push_str("\\\\"),
+                        c => result.push(c),
+                    }
+                }
+            }
+            '"' => {
+                result.push('`');
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' if chars.peek() == Some(&'"') => {
+                            let _ = chars.next();
+                            result.push('"');
+                        }
+                        '"' => {
+                            result.push('`');
+                            break;
+                        }
+                        '`' => result.push_str("``"),
+                        c => result.push(c),
+                    }
+                }
+            }
+            c => result.push(c),
+        }
+    }
+
+    result
+}
+
+/// split on the separator outside of the strings, the quoted identifiers and the parentheses
+pub(crate) fn split_top_level(value: &str, separator: char) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut start = 0;
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+
+    for (idx, c) in value.char_indices() {
+        match quote {
+            Some(quote_char) if c == quote_char => quote = None,
+            Some(_) => {}
+            None => match c {
+                '\'' | '"' => quote = Some(c),
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                c if c == separator && depth == 0 => {
+                    parts.push(&value[start..idx]);
+                    start = idx + c.len_utf8();
+                }
+                _ => {}
+            },
+        }
+    }
+
+    parts.push(&value[start..]);
+    parts
+}
+
+/// position of the parenthesis closing the one at `open`
+pub(crate) fn closing_parenthesis(value: &str, open: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+
+    for (idx, c) in value[open..].char_indices() {
+        match quote {
+            Some(quote_char) if c == quote_char => quote = None,
+            Some(_) => {}
+            None => match c {
+                '\'' | '"' => quote = Some(c),
+                '(' => depth += 1,
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(open + idx);
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+
+    None
+}
+
+/// split the identifier at the beginning of the value from the rest
+pub(crate) fn split_identifier(value: &str) -> (&str, &str) {
+    let mut in_quotes = false;
+
+    for (idx, c) in value.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            c if !in_quotes && (c.is_whitespace() || c == '(') => {
+                return (&value[..idx], &value[idx..])
+            }
+            _ => {}
+        }
+    }
+
+    (value, "")
+}
+
+fn strip_schema(name: &str) -> &str {
+    split_top_level(name, '.').pop().unwrap_or(name)
+}
+
+fn unquote(identifier: &str) -> String {
+    match identifier
+        .strip_prefix('"')
+        .and_then(|identifier| identifier.strip_suffix('"'))
+    {
+        Some(identifier) => identifier.replace("\"\"", "\""),
+        None => identifier.to_string(),
+    }
+}
+
+fn quote(identifier: &str) -> String {
+    format!("`{}`", identifier.replace('`', "``"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PostgresToMysql;
+
+    fn translate(translation: &mut PostgresToMysql, dump: &str) -> String {
+        String::from_utf8(translation.translate(dump.as_bytes().to_vec()).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn translate_serial_column() {
+        let mut translation = PostgresToMysql::default();
+        let dump = r#"CREATE TABLE public.users (
+    id serial PRIMARY KEY,
+    "group" bigserial NOT NULL,
+    name character varying(30) DEFAULT 'n/a'::character varying NOT NULL
+);
+"#;
+
+        assert_eq!(
+            translate(&mut translation, dump),
+            r#"CREATE TABLE `users` (
+    `id` INT AUTO_INCREMENT PRIMARY KEY,
+    `group` BIGINT AUTO_INCREMENT NOT NULL,
+    `name` VARCHAR(30) DEFAULT 'n/a' NOT NULL
+);
+"#
+        );
+    }
+
+    #[test]
+    fn translate_boolean_column() {
+        let mut translation = PostgresToMysql::default();
+        let dump = r#"CREATE TABLE public.users (
+    id integer NOT NULL,
+    active boolean DEFAULT true,
+    admin bool
+);
+
+INSERT INTO public.users (id, active, admin) VALUES (1, true, false);
+"#;
+
+        assert_eq!(
+            translate(&mut translation, dump),
+            r#"CREATE TABLE `users` (
+    `id` INT NOT NULL,
+    `active` TINYINT(1) DEFAULT true,
+    `admin` TINYINT(1)
+);
+INSERT INTO `users` (id, active, admin) VALUES (1, true, false);
+"#
+        );
+    }
+
+    #[test]
+    fn translate_pg_dump_sequence_and_values() {
+        let mut translation = PostgresToMysql::default();
+        let dump = r#"SET statement_timeout = 0;
+SELECT pg_catalog.set_config('search_path', '', false);
+
+CREATE TABLE public.files (
+    id integer NOT NULL,
+    path text,
+    content bytea
+);
+
+ALTER TABLE public.files OWNER TO root;
+
+CREATE SEQUENCE public.files_id_seq
+    AS integer
+    START WITH 1
+    INCREMENT BY 1
+    NO MINVALUE
+    NO MAXVALUE
+    CACHE 1;
+
+ALTER TABLE ONLY public.files ALTER COLUMN id SET DEFAULT nextval('public.files_id_seq'::regclass);
+
+INSERT INTO public.files (id, path, content) VALUES (1, 'C:\temp\it''s', '\x0a1b');
+
+SELECT pg_catalog.setval('public.files_id_seq', 1, true);
+
+ALTER TABLE ONLY public.files
+    ADD CONSTRAINT files_pkey PRIMARY KEY (id);
+"#;
+
+        assert_eq!(
+            translate(&mut translation, dump),
+            r#"CREATE TABLE `files` (
+    `id` INT NOT NULL,
+    `path` TEXT,
+    `content` LONGBLOB
+);
+INSERT INTO `files` (id, path, content) VALUES (1, 'C:\\temp\\it''s', X'0a1b');
+ALTER TABLE `files` ADD CONSTRAINT files_pkey PRIMARY KEY (id);
+ALTER TABLE `files` MODIFY `id` INT NOT NULL AUTO_INCREMENT;
+"#
+        );
+    }
+
+    #[test]
+    fn translate_fails_on_unsupported_statements() {
+        let mut translation = PostgresToMysql::default();
+
+        for dump in [
+            "CREATE TABLE public.users (id integer NOT NULL, tags text[]);\n",
+            "CREATE TABLE public.users (id integer NOT NULL, address inet);\n",
+            "COPY public.users (id) FROM stdin;\n",
+            "CREATE FUNCTION public.f() RETURNS integer AS 'select 1' LANGUAGE sql;\n",
+        ] {
+            assert!(translation.translate(dump.as_bytes().to_vec()).is_err());
+        }
+    }
+}
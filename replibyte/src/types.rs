@@ -65,7 +65,7 @@ impl Column {
         }
     }
 
-    pub fn float_number_value(&self) -> Option<&f64> {
+    pub fn float_value(&self) -> Option<&f64> {
         match self {
             Column::FloatNumberValue(_, value) => Some(value),
             _ => None,
@@ -79,10 +79,75 @@ impl Column {
         }
     }
 
-    pub fn boolean_value(&self) -> Option<&bool> {
+    pub fn bool_value(&self) -> Option<&bool> {
         match self {
             Column::BooleanValue(_, value) => Some(value),
             _ => None,
         }
     }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Column::None(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Column;
+
+    fn get_columns() -> Vec<Column> {
+        vec![
+            Column::NumberValue("id".to_string(), 42),
+            Column::FloatNumberValue("price".to_string(), 9.99),
+            Column::StringValue("name".to_string(), "Nancy".to_string()),
+            Column::CharValue("grade".to_string(), 'A'),
+            Column::BooleanValue("active".to_string(), true),
+            Column::None("deleted_at".to_string()),
+        ]
+    }
+
+    #[test]
+    fn column_name() {
+        let names = get_columns()
+            .iter()
+            .map(|column| column.name().to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            names,
+            vec!["id", "price", "name", "grade", "active", "deleted_at"]
+        );
+    }
+
+    #[test]
+    fn column_values() {
+        let columns = get_columns();
+
+        let number_values = columns.iter().map(|c| c.number_value()).collect::<Vec<_>>();
+        assert_eq!(number_values, vec![Some(&42), None, None, None, None, None]);
+
+        let float_values = columns.iter().map(|c| c.float_value()).collect::<Vec<_>>();
+        assert_eq!(
+            float_values,
+            vec![None, Some(&9.99), None, None, None, None]
+        );
+
+        let string_values = columns.iter().map(|c| c.string_value()).collect::<Vec<_>>();
+        assert_eq!(
+            string_values,
+            vec![None, None, Some("Nancy"), None, None, None]
+        );
+
+        let char_values = columns.iter().map(|c| c.char_value()).collect::<Vec<_>>();
+        assert_eq!(char_values, vec![None, None, None, Some(&'A'), None, None]);
+
+        let boolean_values = columns.iter().map(|c| c.bool_value()).collect::<Vec<_>>();
+        assert_eq!(
+            boolean_values,
+            vec![None, None, None, None, Some(&true), None]
+        );
+
+        let null_values = columns.iter().map(|c| c.is_null()).collect::<Vec<_>>();
+        assert_eq!(null_values, vec![false, false, false, false, false, true]);
+    }
 }
@@ -1,12 +1,13 @@
 use crate::dedup::does_line_exist_and_set;
-use crate::postgres::SubsetStrategy::RandomPercent;
+use crate::postgres::SubsetStrategy::{Incremental, Limit, RandomPercent, StratifiedPercent};
 use crate::{
     utils, PassthroughTable, Progress, Subset, SubsetOptions, SubsetTable, SubsetTableRelation,
 };
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
 use dump_parser::postgres::{
-    get_column_names_from_insert_into_query, get_column_values_str_from_insert_into_query,
-    get_tokens_from_query_str, get_word_value_at_position, match_keyword_at_position,
-    trim_pre_whitespaces, Keyword, Token,
+    get_column_names_from_insert_into_query, get_column_values_from_insert_into_query,
+    get_column_values_str_from_insert_into_query, get_tokens_from_query_str,
+    get_word_value_at_position, match_keyword_at_position, trim_pre_whitespaces, Keyword, Token,
 };
 use dump_parser::utils::{list_sql_queries_from_dump_reader, ListQueryResult};
 use std::collections::HashMap;
@@ -43,6 +44,24 @@ pub enum SubsetStrategy<'a> {
         table: &'a str,
         percent: u8,
     },
+    StratifiedPercent {
+        database: &'a str,
+        table: &'a str,
+        column: &'a str,
+        percent: u8,
+    },
+    Limit {
+        database: &'a str,
+        table: &'a str,
+        rows: usize,
+    },
+    Incremental {
+        database: &'a str,
+        table: &'a str,
+        column: &'a str,
+        // rows with a `column` value after it are kept - all the rows if none
+        since: Option<&'a str>,
+    },
 }
 
 impl<'a> SubsetStrategy<'a> {
@@ -53,6 +72,37 @@ impl<'a> SubsetStrategy<'a> {
             percent,
         }
     }
+
+    pub fn stratified(database: &'a str, table: &'a str, column: &'a str, percent: u8) -> Self {
+        StratifiedPercent {
+            database,
+            table,
+            column,
+            percent,
+        }
+    }
+
+    pub fn limit(database: &'a str, table: &'a str, rows: usize) -> Self {
+        Limit {
+            database,
+            table,
+            rows,
+        }
+    }
+
+    pub fn incremental(
+        database: &'a str,
+        table: &'a str,
+        column: &'a str,
+        since: Option<&'a str>,
+    ) -> Self {
+        Incremental {
+            database,
+            table,
+            column,
+            since,
+        }
+    }
 }
 
 pub struct PostgresSubset<'a> {
@@ -98,6 +148,43 @@ impl<'a> PostgresSubset<'a> {
                     .unwrap(),
                 self.dump_reader(),
             )?),
+            SubsetStrategy::StratifiedPercent {
+                database,
+                table,
+                column,
+                percent,
+            } => Ok(list_stratified_percent_of_insert_into_rows(
+                column,
+                percent,
+                table_stats
+                    .get(&(database.to_string(), table.to_string()))
+                    .unwrap(),
+                self.dump_reader(),
+            )?),
+            SubsetStrategy::Limit {
+                database,
+                table,
+                rows,
+            } => Ok(list_limit_of_insert_into_rows(
+                rows,
+                table_stats
+                    .get(&(database.to_string(), table.to_string()))
+                    .unwrap(),
+                self.dump_reader(),
+            )?),
+            SubsetStrategy::Incremental {
+                database,
+                table,
+                column,
+                since,
+            } => Ok(list_incremental_insert_into_rows(
+                column,
+                since,
+                table_stats
+                    .get(&(database.to_string(), table.to_string()))
+                    .unwrap(),
+                self.dump_reader(),
+            )?),
         }
     }
 
@@ -330,6 +417,209 @@ fn list_percent_of_insert_into_rows<R: Read>(
     Ok(insert_into_rows)
 }
 
+/// pick `percent` of the rows within each distinct value of `column`.
+/// At least one row is kept per distinct value, so rare values do not vanish from the subset.
+fn list_stratified_percent_of_insert_into_rows<R: Read>(
+    column: &str,
+    percent: u8,
+    table_stats: &TableStats,
+    dump_reader: BufReader<R>,
+) -> Result<Vec<String>, Error> {
+    let mut insert_into_rows = vec![];
+
+    if percent == 0 || table_stats.total_rows == 0 {
+        return Ok(insert_into_rows);
+    }
+
+    let column_idx = match table_stats
+        .columns
+        .iter()
+        .position(|r| r.as_str() == column)
+    {
+        Some(idx) => idx,
+        None => {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!(
+                    "table {} does not contain column {}",
+                    table_stats.table, column
+                ),
+            ));
+        }
+    };
+
+    let percent = if percent > 100 { 100 } else { percent };
+
+    // keep the groups in the dump order to have a deterministic output
+    let mut group_idx_by_value = HashMap::<String, usize>::new();
+    let mut groups: Vec<Vec<String>> = vec![];
+
+    let _ = list_insert_into_rows(dump_reader, table_stats, |row| {
+        let tokens = get_tokens_from_query_str(row);
+        let tokens = trim_tokens(&tokens, Keyword::Insert);
+        let column_values = get_column_values_str_from_insert_into_query(&tokens);
+        let value = column_values.index(column_idx).to_string();
+
+        let group_idx = *group_idx_by_value.entry(value).or_insert_with(|| {
+            groups.push(vec![]);
+            groups.len() - 1
+        });
+
+        groups[group_idx].push(row.to_string());
+    })?;
+
+    for rows in groups {
+        let total_rows_to_pick =
+            ((rows.len() as f32 * percent as f32 / 100.0).ceil() as usize).max(1);
+
+        for i in 0..total_rows_to_pick {
+            insert_into_rows.push(rows[i * rows.len() / total_rows_to_pick].clone());
+        }
+    }
+
+    Ok(insert_into_rows)
+}
+
+/// pick the first `rows` rows of the table, in the dump order so the output is deterministic
+fn list_limit_of_insert_into_rows<R: Read>(
+    rows: usize,
+    table_stats: &TableStats,
+    dump_reader: BufReader<R>,
+) -> Result<Vec<String>, Error> {
+    let mut insert_into_rows = vec![];
+
+    if rows == 0 || table_stats.total_rows == 0 {
+        return Ok(insert_into_rows);
+    }
+
+    let _ = list_insert_into_rows(dump_reader, table_stats, |row| {
+        if insert_into_rows.len() < rows {
+            insert_into_rows.push(row.to_string());
+        }
+    })?;
+
+    Ok(insert_into_rows)
+}
+
+/// pick the rows with a `column` value after `since` (e.g. the rows updated since the last dump), or all the rows.
+/// The values are compared as dates or timestamps - a value that is not one is an error. A NULL value is never after
+/// `since`.
+fn list_incremental_insert_into_rows<R: Read>(
+    column: &str,
+    since: Option<&str>,
+    table_stats: &TableStats,
+    dump_reader: BufReader<R>,
+) -> Result<Vec<String>, Error> {
+    let mut insert_into_rows = vec![];
+
+    if table_stats.total_rows == 0 {
+        return Ok(insert_into_rows);
+    }
+
+    let column_idx = match table_stats
+        .columns
+        .iter()
+        .position(|r| r.as_str() == column)
+    {
+        Some(idx) => idx,
+        None => {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!(
+                    "table {} does not contain column {}",
+                    table_stats.table, column
+                ),
+            ));
+        }
+    };
+
+    let since = match since {
+        Some(since) => match parse_timestamp(since) {
+            Some(since) => Some(since),
+            None => {
+                return Err(Error::new(
+                    ErrorKind::Other,
+                    format!("'{}' is not a date or a timestamp", since),
+                ));
+            }
+        },
+        None => None,
+    };
+
+    let mut invalid_value = None;
+
+    list_insert_into_rows(dump_reader, table_stats, |row| {
+        if invalid_value.is_some() {
+            return;
+        }
+
+        let is_changed = match since {
+            Some(since) => {
+                let tokens = get_tokens_from_query_str(row);
+                let tokens = trim_tokens(&tokens, Keyword::Insert);
+
+                match get_column_values_from_insert_into_query(&tokens).get(column_idx) {
+                    Some(Token::Word(word)) if matches!(word.keyword, Keyword::Null) => false,
+                    Some(Token::SingleQuotedString(value)) => match parse_timestamp(value) {
+                        Some(value) => value > since,
+                        None => {
+                            invalid_value = Some(value.to_string());
+                            false
+                        }
+                    },
+                    Some(Token::Number(value, _)) => {
+                        invalid_value = Some(value.to_string());
+                        false
+                    }
+                    Some(Token::Word(word)) => {
+                        invalid_value = Some(word.value.to_string());
+                        false
+                    }
+                    value => {
+                        invalid_value = Some(format!("{:?}", value));
+                        false
+                    }
+                }
+            }
+            None => true,
+        };
+
+        if is_changed {
+            insert_into_rows.push(row.to_string());
+        }
+    })?;
+
+    if let Some(value) = invalid_value {
+        return Err(Error::new(
+            ErrorKind::Other,
+            format!(
+                "value {} of column {}.{} is not a date or a timestamp",
+                value, table_stats.table, column
+            ),
+        ));
+    }
+
+    Ok(insert_into_rows)
+}
+
+/// parse a date or a timestamp as written by pg_dump (e.g. `1998-05-06`, `1998-05-06 10:00:00.5+02`) - the
+/// timestamps with a time zone are converted to UTC
+fn parse_timestamp(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim().replacen('T', " ", 1);
+
+    if let Ok(timestamp) = NaiveDateTime::parse_from_str(value.as_str(), "%Y-%m-%d %H:%M:%S%.f") {
+        return Some(timestamp);
+    }
+
+    if let Ok(timestamp) = DateTime::parse_from_str(value.as_str(), "%Y-%m-%d %H:%M:%S%.f%#z") {
+        return Some(timestamp.naive_utc());
+    }
+
+    NaiveDate::parse_from_str(value.as_str(), "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+}
+
 fn list_insert_into_rows<R: Read, F: FnMut(&str)>(
     dump_reader: BufReader<R>,
     table_stats: &TableStats,
@@ -739,11 +1029,14 @@ mod tests {
         dump_footer, dump_header, filter_insert_into_rows, first_footer_row_idx,
         get_alter_table_foreign_key, get_create_table_database_and_table_name,
         get_subset_table_by_database_and_table_name, last_header_row_idx,
-        list_percent_of_insert_into_rows, table_stats_by_database_and_table_name, PostgresSubset,
-        SubsetStrategy,
+        list_incremental_insert_into_rows, list_limit_of_insert_into_rows,
+        list_percent_of_insert_into_rows, list_stratified_percent_of_insert_into_rows,
+        parse_timestamp, table_stats_by_database_and_table_name, PostgresSubset, SubsetStrategy,
     };
     use crate::{PassthroughTable, Subset, SubsetOptions};
-    use dump_parser::postgres::Tokenizer;
+    use dump_parser::postgres::{
+        get_column_values_str_from_insert_into_query, get_tokens_from_query_str, Tokenizer,
+    };
     use std::collections::HashSet;
     use std::fs::File;
     use std::io::BufReader;
@@ -850,6 +1143,150 @@ ALTER TABLE ONLY public.territories
         assert!(rows.len() < first_table_stats.total_rows)
     }
 
+    #[test]
+    fn check_stratified_percent_of_rows() {
+        let table_stats = table_stats_by_database_and_table_name(dump_reader()).unwrap();
+        let orders_table_stats = table_stats
+            .get(&("public".to_string(), "orders".to_string()))
+            .unwrap();
+
+        let ship_country_idx = orders_table_stats
+            .columns
+            .iter()
+            .position(|c| c.as_str() == "ship_country")
+            .unwrap();
+
+        let ship_country = |row: &str| {
+            let tokens = get_tokens_from_query_str(row);
+            get_column_values_str_from_insert_into_query(&tokens)[ship_country_idx].to_string()
+        };
+
+        let all_countries =
+            list_percent_of_insert_into_rows(100, orders_table_stats, dump_reader())
+                .unwrap()
+                .iter()
+                .map(|row| ship_country(row))
+                .collect::<HashSet<_>>();
+
+        let rows = list_stratified_percent_of_insert_into_rows(
+            "ship_country",
+            5,
+            orders_table_stats,
+            dump_reader(),
+        )
+        .unwrap();
+
+        let sampled_countries = rows.iter().map(|row| ship_country(row)).collect::<HashSet<_>>();
+
+        assert!(rows.len() < orders_table_stats.total_rows);
+        // every distinct ship_country is still represented in the subset
+        assert_eq!(sampled_countries, all_countries);
+
+        assert!(list_stratified_percent_of_insert_into_rows(
+            "does_not_exist",
+            5,
+            orders_table_stats,
+            dump_reader(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn check_limit_of_rows() {
+        let table_stats = table_stats_by_database_and_table_name(dump_reader()).unwrap();
+        let orders_table_stats = table_stats
+            .get(&("public".to_string(), "orders".to_string()))
+            .unwrap();
+
+        let all_rows =
+            list_percent_of_insert_into_rows(100, orders_table_stats, dump_reader()).unwrap();
+        let rows = list_limit_of_insert_into_rows(10, orders_table_stats, dump_reader()).unwrap();
+
+        assert_eq!(rows.len(), 10);
+        // the first rows of the table are kept
+        assert_eq!(rows, all_rows[..10].to_vec());
+
+        let rows = list_limit_of_insert_into_rows(
+            orders_table_stats.total_rows + 10,
+            orders_table_stats,
+            dump_reader(),
+        )
+        .unwrap();
+
+        assert_eq!(rows.len(), orders_table_stats.total_rows);
+    }
+
+    #[test]
+    fn check_incremental_rows() {
+        let table_stats = table_stats_by_database_and_table_name(dump_reader()).unwrap();
+        let orders_table_stats = table_stats
+            .get(&("public".to_string(), "orders".to_string()))
+            .unwrap();
+
+        // the first run keeps all the rows
+        let rows = list_incremental_insert_into_rows(
+            "order_date",
+            None,
+            orders_table_stats,
+            dump_reader(),
+        )
+        .unwrap();
+        assert_eq!(rows.len(), orders_table_stats.total_rows);
+
+        let rows = list_incremental_insert_into_rows(
+            "order_date",
+            Some("1998-05-01 00:00:00"),
+            orders_table_stats,
+            dump_reader(),
+        )
+        .unwrap();
+
+        assert!(!rows.is_empty());
+        assert!(rows.len() < orders_table_stats.total_rows);
+        assert!(rows.iter().all(|row| row.contains("'1998-05-")));
+
+        assert!(list_incremental_insert_into_rows(
+            "does_not_exist",
+            None,
+            orders_table_stats,
+            dump_reader(),
+        )
+        .is_err());
+
+        // the values must be dates or timestamps
+        assert!(list_incremental_insert_into_rows(
+            "employee_id",
+            Some("1998-05-01 00:00:00"),
+            orders_table_stats,
+            dump_reader(),
+        )
+        .is_err());
+
+        assert!(list_incremental_insert_into_rows(
+            "order_date",
+            Some("yesterday"),
+            orders_table_stats,
+            dump_reader(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn check_parse_timestamp() {
+        let since = parse_timestamp("1998-05-06 09:00:00").unwrap();
+
+        assert!(parse_timestamp("1998-05-06 09:00:00.5").unwrap() > since);
+        assert!(parse_timestamp("1998-05-06T09:30:00").unwrap() > since);
+        assert!(parse_timestamp("1998-05-07").unwrap() > since);
+        assert!(parse_timestamp("1998-05-06").unwrap() < since);
+        // 08:00 UTC, after `since` as text
+        assert!(parse_timestamp("1998-05-06 10:00:00+02").unwrap() < since);
+        assert!(parse_timestamp("1998-05-06 10:00:00+00:00").unwrap() > since);
+
+        assert!(parse_timestamp("10248").is_none());
+        assert!(parse_timestamp("May 6 1998").is_none());
+    }
+
     #[test]
     fn check_filter_insert_into_rows() {
         let table_stats = table_stats_by_database_and_table_name(dump_reader()).unwrap();